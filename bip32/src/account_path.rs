@@ -0,0 +1,399 @@
+//! BIP43 purpose-based derivation paths (BIP44, BIP49, BIP84, BIP86).
+
+use crate::{ChildNumber, DerivationPath, Error, Prefix, Result};
+use core::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+/// SLIP-44 coin type used by all testnets.
+const TESTNET_COIN_TYPE: u32 = 1;
+
+/// BIP43 purposes: the first level of a purpose-based derivation path.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum Purpose {
+    /// BIP44: legacy pay-to-pubkey-hash (P2PKH) accounts.
+    Bip44,
+
+    /// BIP49: P2WPKH nested in P2SH accounts.
+    Bip49,
+
+    /// BIP84: native SegWit v0 P2WPKH accounts.
+    Bip84,
+
+    /// BIP86: single key Taproot (P2TR) accounts.
+    Bip86,
+}
+
+impl Purpose {
+    /// Get the purpose number, e.g. `44` for BIP44.
+    pub fn number(self) -> u32 {
+        match self {
+            Purpose::Bip44 => 44,
+            Purpose::Bip49 => 49,
+            Purpose::Bip84 => 84,
+            Purpose::Bip86 => 86,
+        }
+    }
+
+    /// Get the (hardened) [`ChildNumber`] for this purpose.
+    pub fn child_number(self) -> ChildNumber {
+        ChildNumber(self.number() | ChildNumber::HARDENED_FLAG)
+    }
+
+    /// Get the [`Prefix`] for extended private keys derived under this
+    /// purpose on Bitcoin mainnet.
+    pub fn private_prefix(self) -> Prefix {
+        match self {
+            Purpose::Bip44 | Purpose::Bip86 => Prefix::XPRV,
+            Purpose::Bip49 => Prefix::YPRV,
+            Purpose::Bip84 => Prefix::ZPRV,
+        }
+    }
+
+    /// Get the [`Prefix`] for extended public keys derived under this
+    /// purpose on Bitcoin mainnet.
+    pub fn public_prefix(self) -> Prefix {
+        match self {
+            Purpose::Bip44 | Purpose::Bip86 => Prefix::XPUB,
+            Purpose::Bip49 => Prefix::YPUB,
+            Purpose::Bip84 => Prefix::ZPUB,
+        }
+    }
+
+    /// Get the [`Prefix`] for extended private keys derived under this
+    /// purpose on Bitcoin testnet.
+    pub fn testnet_private_prefix(self) -> Prefix {
+        match self {
+            Purpose::Bip44 | Purpose::Bip86 => Prefix::TPRV,
+            Purpose::Bip49 => Prefix::UPRV,
+            Purpose::Bip84 => Prefix::VPRV,
+        }
+    }
+
+    /// Get the [`Prefix`] for extended public keys derived under this
+    /// purpose on Bitcoin testnet.
+    pub fn testnet_public_prefix(self) -> Prefix {
+        match self {
+            Purpose::Bip44 | Purpose::Bip86 => Prefix::TPUB,
+            Purpose::Bip49 => Prefix::UPUB,
+            Purpose::Bip84 => Prefix::VPUB,
+        }
+    }
+}
+
+impl TryFrom<ChildNumber> for Purpose {
+    type Error = Error;
+
+    fn try_from(child_number: ChildNumber) -> Result<Self> {
+        if !child_number.is_hardened() {
+            return Err(Error::ChildNumber);
+        }
+
+        match child_number.index() {
+            44 => Ok(Purpose::Bip44),
+            49 => Ok(Purpose::Bip49),
+            84 => Ok(Purpose::Bip84),
+            86 => Ok(Purpose::Bip86),
+            _ => Err(Error::ChildNumber),
+        }
+    }
+}
+
+/// Components of a purpose-based derivation path:
+/// `m/purpose'/coin_type'/account'/change/address_index`
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+struct Components {
+    coin_type: u32,
+    account: u32,
+    change: u32,
+    address_index: u32,
+}
+
+impl Components {
+    /// Create path components, validating them.
+    fn new(coin_type: u32, account: u32, change: u32, address_index: u32) -> Result<Self> {
+        for index in [coin_type, account, address_index] {
+            if index >= ChildNumber::HARDENED_FLAG {
+                return Err(Error::ChildNumber);
+            }
+        }
+
+        // 0 is the external chain, 1 is the internal (i.e. change) chain
+        if change > 1 {
+            return Err(Error::ChildNumber);
+        }
+
+        Ok(Self {
+            coin_type,
+            account,
+            change,
+            address_index,
+        })
+    }
+
+    /// Parse path components from a derivation path with the given purpose,
+    /// checking that each level is hardened (or not) as required.
+    fn from_derivation_path(path: &DerivationPath, purpose: Purpose) -> Result<Self> {
+        let &[purpose_number, coin_type, account, change, address_index] = path.as_ref() else {
+            return Err(Error::Decode);
+        };
+
+        if Purpose::try_from(purpose_number)? != purpose
+            || !coin_type.is_hardened()
+            || !account.is_hardened()
+            || change.is_hardened()
+            || address_index.is_hardened()
+        {
+            return Err(Error::ChildNumber);
+        }
+
+        Self::new(
+            coin_type.index(),
+            account.index(),
+            change.index(),
+            address_index.index(),
+        )
+    }
+
+    /// Get the account-level derivation path: `m/purpose'/coin_type'/account'`
+    fn account_path(self, purpose: Purpose) -> DerivationPath {
+        let mut path = DerivationPath::default();
+        path.push(purpose.child_number());
+        path.push(ChildNumber(self.coin_type | ChildNumber::HARDENED_FLAG));
+        path.push(ChildNumber(self.account | ChildNumber::HARDENED_FLAG));
+        path
+    }
+
+    /// Get the full derivation path.
+    fn to_derivation_path(self, purpose: Purpose) -> DerivationPath {
+        let mut path = self.account_path(purpose);
+        path.push(ChildNumber(self.change));
+        path.push(ChildNumber(self.address_index));
+        path
+    }
+}
+
+/// Define a typed derivation path for a particular [`Purpose`].
+macro_rules! define_path {
+    ($name:ident, $purpose:expr, $doc:expr) => {
+        #[doc = $doc]
+        ///
+        /// Paths have the form `m/purpose'/coin_type'/account'/change/address_index`,
+        /// where the purpose, coin type, and account are hardened.
+        #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+        pub struct $name(Components);
+
+        impl $name {
+            /// Purpose of this path.
+            pub const PURPOSE: Purpose = $purpose;
+
+            /// Create a new path from the given (unhardened) indices.
+            ///
+            /// `change` must be `0` (external chain) or `1` (internal chain),
+            /// and all other indices must be less than
+            /// [`ChildNumber::HARDENED_FLAG`].
+            pub fn new(
+                coin_type: u32,
+                account: u32,
+                change: u32,
+                address_index: u32,
+            ) -> Result<Self> {
+                Components::new(coin_type, account, change, address_index).map(Self)
+            }
+
+            /// Get the coin type (e.g. `0` for Bitcoin, `1` for testnets).
+            pub fn coin_type(&self) -> u32 {
+                self.0.coin_type
+            }
+
+            /// Get the account index.
+            pub fn account(&self) -> u32 {
+                self.0.account
+            }
+
+            /// Get the change index: `0` for the external chain, `1` for the
+            /// internal chain.
+            pub fn change(&self) -> u32 {
+                self.0.change
+            }
+
+            /// Get the address index.
+            pub fn address_index(&self) -> u32 {
+                self.0.address_index
+            }
+
+            /// Get the account-level derivation path:
+            /// `m/purpose'/coin_type'/account'`
+            ///
+            /// This is the path of the account's extended public key, which
+            /// is serialized with [`Self::public_prefix`].
+            pub fn account_path(&self) -> DerivationPath {
+                self.0.account_path(Self::PURPOSE)
+            }
+
+            /// Get the full [`DerivationPath`].
+            pub fn to_derivation_path(&self) -> DerivationPath {
+                self.0.to_derivation_path(Self::PURPOSE)
+            }
+
+            /// Get the [`Prefix`] for extended private keys derived along
+            /// this path: the Bitcoin testnet prefix if the coin type is `1`
+            /// (testnet), or the mainnet prefix otherwise.
+            pub fn private_prefix(&self) -> Prefix {
+                if self.0.coin_type == TESTNET_COIN_TYPE {
+                    Self::PURPOSE.testnet_private_prefix()
+                } else {
+                    Self::PURPOSE.private_prefix()
+                }
+            }
+
+            /// Get the [`Prefix`] for extended public keys derived along
+            /// this path: the Bitcoin testnet prefix if the coin type is `1`
+            /// (testnet), or the mainnet prefix otherwise.
+            pub fn public_prefix(&self) -> Prefix {
+                if self.0.coin_type == TESTNET_COIN_TYPE {
+                    Self::PURPOSE.testnet_public_prefix()
+                } else {
+                    Self::PURPOSE.public_prefix()
+                }
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                Display::fmt(&self.to_derivation_path(), f)
+            }
+        }
+
+        impl From<$name> for DerivationPath {
+            fn from(path: $name) -> DerivationPath {
+                path.to_derivation_path()
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = Error;
+
+            fn from_str(path: &str) -> Result<Self> {
+                Self::try_from(&path.parse::<DerivationPath>()?)
+            }
+        }
+
+        impl TryFrom<&DerivationPath> for $name {
+            type Error = Error;
+
+            fn try_from(path: &DerivationPath) -> Result<Self> {
+                Components::from_derivation_path(path, Self::PURPOSE).map(Self)
+            }
+        }
+    };
+}
+
+define_path!(
+    Bip44Path,
+    Purpose::Bip44,
+    "BIP44 derivation path for legacy P2PKH accounts."
+);
+define_path!(
+    Bip49Path,
+    Purpose::Bip49,
+    "BIP49 derivation path for P2WPKH-nested-in-P2SH accounts."
+);
+define_path!(
+    Bip84Path,
+    Purpose::Bip84,
+    "BIP84 derivation path for native SegWit P2WPKH accounts."
+);
+define_path!(
+    Bip86Path,
+    Purpose::Bip86,
+    "BIP86 derivation path for single key Taproot (P2TR) accounts."
+);
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::{Bip44Path, Bip49Path, Bip84Path, Bip86Path, Purpose};
+    use crate::{DerivationPath, Error, Prefix};
+    use alloc::string::ToString;
+
+    #[test]
+    fn bip44_path() {
+        let path = Bip44Path::new(0, 1, 0, 5).unwrap();
+        assert_eq!(path.to_string(), "m/44'/0'/1'/0/5");
+        assert_eq!(path.account_path().to_string(), "m/44'/0'/1'");
+        assert_eq!(path.public_prefix(), Prefix::XPUB);
+        assert_eq!("m/44'/0'/1'/0/5".parse::<Bip44Path>().unwrap(), path);
+    }
+
+    #[test]
+    fn prefixes() {
+        assert_eq!(Purpose::Bip49.public_prefix(), Prefix::YPUB);
+        assert_eq!(Purpose::Bip49.private_prefix(), Prefix::YPRV);
+        assert_eq!(Purpose::Bip84.public_prefix(), Prefix::ZPUB);
+        assert_eq!(Purpose::Bip84.private_prefix(), Prefix::ZPRV);
+        assert_eq!(Purpose::Bip86.public_prefix(), Prefix::XPUB);
+        assert_eq!(Purpose::Bip84.testnet_public_prefix(), Prefix::VPUB);
+        assert_eq!(Purpose::Bip84.testnet_private_prefix(), Prefix::VPRV);
+    }
+
+    #[test]
+    fn testnet_prefixes() {
+        let path = Bip44Path::new(1, 0, 0, 0).unwrap();
+        assert_eq!(path.public_prefix(), Prefix::TPUB);
+        assert_eq!(path.private_prefix(), Prefix::TPRV);
+
+        let path = Bip49Path::new(1, 0, 0, 0).unwrap();
+        assert_eq!(path.public_prefix(), Prefix::UPUB);
+        assert_eq!(path.private_prefix(), Prefix::UPRV);
+
+        let path = Bip84Path::new(1, 0, 0, 0).unwrap();
+        assert_eq!(path.public_prefix(), Prefix::VPUB);
+        assert_eq!(path.private_prefix(), Prefix::VPRV);
+
+        let path = Bip86Path::new(1, 0, 0, 0).unwrap();
+        assert_eq!(path.public_prefix(), Prefix::TPUB);
+        assert_eq!(path.private_prefix(), Prefix::TPRV);
+    }
+
+    #[test]
+    fn to_derivation_path() {
+        let path = DerivationPath::from(Bip84Path::new(1, 0, 1, 2).unwrap());
+        assert_eq!(path, "m/84'/1'/0'/1/2".parse().unwrap());
+
+        let path = DerivationPath::from(Bip86Path::new(0, 0, 0, 0).unwrap());
+        assert_eq!(path, "m/86'/0'/0'/0/0".parse().unwrap());
+    }
+
+    #[test]
+    fn invalid_indices() {
+        assert_eq!(Bip44Path::new(1 << 31, 0, 0, 0), Err(Error::ChildNumber));
+        assert_eq!(Bip44Path::new(0, 0, 2, 0), Err(Error::ChildNumber));
+    }
+
+    #[test]
+    fn invalid_hardening() {
+        // Unhardened account
+        assert_eq!(
+            "m/49'/0'/0/0/0".parse::<Bip49Path>(),
+            Err(Error::ChildNumber)
+        );
+
+        // Hardened address index
+        assert_eq!(
+            "m/49'/0'/0'/0/0'".parse::<Bip49Path>(),
+            Err(Error::ChildNumber)
+        );
+    }
+
+    #[test]
+    fn wrong_purpose() {
+        assert_eq!(
+            "m/44'/0'/0'/0/0".parse::<Bip84Path>(),
+            Err(Error::ChildNumber)
+        );
+        assert_eq!("m/84'/0'/0'/0".parse::<Bip84Path>(), Err(Error::Decode));
+    }
+}
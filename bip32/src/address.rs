@@ -0,0 +1,180 @@
+//! Address encodings for secp256k1 extended public keys.
+//!
+//! Supports the following address types:
+//!
+//! - Bitcoin: P2PKH, P2SH-P2WPKH, P2WPKH, and P2TR (key path spending only)
+//! - Cosmos: bech32-encoded account addresses with a configurable prefix
+//! - Ethereum: EIP-55 mixed-case checksummed hex addresses
+
+use crate::{
+    Error, ExtendedPublicKey, PublicKey, Result,
+    bech32::{self, BECH32_CONST, BECH32M_CONST},
+    taproot,
+};
+use alloc::string::String;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+
+/// Lower-case hex characters.
+const HEX_CHARS: &[u8; 16] = b"0123456789abcdef";
+
+/// Size of a key hash (i.e. `RIPEMD160(SHA256(public_key))`).
+const KEY_HASH_SIZE: usize = 20;
+
+/// Bitcoin network, which determines the version bytes and human-readable
+/// part of addresses.
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Network {
+    /// Bitcoin mainnet.
+    #[default]
+    Bitcoin,
+
+    /// Bitcoin testnet (also used for signet).
+    Testnet,
+
+    /// Bitcoin regression test network.
+    Regtest,
+}
+
+impl Network {
+    /// Base58Check version byte for P2PKH addresses.
+    pub fn p2pkh_version(self) -> u8 {
+        match self {
+            Network::Bitcoin => 0x00,
+            Network::Testnet | Network::Regtest => 0x6f,
+        }
+    }
+
+    /// Base58Check version byte for P2SH addresses.
+    pub fn p2sh_version(self) -> u8 {
+        match self {
+            Network::Bitcoin => 0x05,
+            Network::Testnet | Network::Regtest => 0xc4,
+        }
+    }
+
+    /// Human-readable part of SegWit addresses.
+    pub fn hrp(self) -> &'static str {
+        match self {
+            Network::Bitcoin => "bc",
+            Network::Testnet => "tb",
+            Network::Regtest => "bcrt",
+        }
+    }
+}
+
+impl<K> ExtendedPublicKey<K>
+where
+    K: PublicKey + Clone + Into<k256::PublicKey>,
+{
+    /// Encode this key as a legacy pay-to-public-key-hash (P2PKH) address,
+    /// as used with BIP44.
+    pub fn p2pkh_address(&self, network: Network) -> String {
+        base58_check(network.p2pkh_version(), &self.key_hash())
+    }
+
+    /// Encode this key as a P2WPKH address nested in pay-to-script-hash
+    /// (P2SH-P2WPKH), as used with BIP49.
+    pub fn p2sh_p2wpkh_address(&self, network: Network) -> String {
+        let mut redeem_script = [0u8; KEY_HASH_SIZE + 2];
+        redeem_script[0] = 0x00; // OP_0
+        redeem_script[1] = KEY_HASH_SIZE as u8;
+        redeem_script[2..].copy_from_slice(&self.key_hash());
+
+        base58_check(network.p2sh_version(), &hash160(&redeem_script))
+    }
+
+    /// Encode this key as a native SegWit v0 pay-to-witness-public-key-hash
+    /// (P2WPKH) address, as used with BIP84.
+    pub fn p2wpkh_address(&self, network: Network) -> Result<String> {
+        segwit_address(network, 0, &self.key_hash())
+    }
+
+    /// Encode this key as a SegWit v1 pay-to-taproot (P2TR) address with no
+    /// script tree, as used with BIP86.
+    ///
+    /// The output key is this key tweaked as described in BIP341.
+    pub fn p2tr_address(&self, network: Network) -> Result<String> {
+        let output_key = taproot::output_key(&self.public_key().clone().into(), None)?;
+        let output_key = taproot::x_only(&output_key);
+        segwit_address(network, 1, &output_key)
+    }
+
+    /// Encode this key as a Cosmos SDK account address with the given
+    /// human-readable prefix, e.g. `cosmos`.
+    ///
+    /// The prefix must be 1-83 lower-case ASCII characters in the range
+    /// 33-126, as specified in BIP173.
+    pub fn cosmos_address(&self, hrp: &str) -> Result<String> {
+        if !bech32::is_valid_hrp(hrp) {
+            return Err(Error::Hrp);
+        }
+
+        Ok(subtle_encoding::bech32::encode(hrp, self.key_hash()))
+    }
+
+    /// Encode this key as an Ethereum address with an EIP-55 checksum.
+    pub fn ethereum_address(&self) -> String {
+        let public_key: k256::PublicKey = self.public_key().clone().into();
+        let encoded = public_key.to_encoded_point(false);
+        let hash = Keccak256::digest(&encoded.as_bytes()[1..]);
+
+        let mut address = String::with_capacity(42);
+        address.push_str("0x");
+
+        let mut hex = [0u8; 40];
+        for (i, byte) in hash[12..].iter().enumerate() {
+            hex[2 * i] = HEX_CHARS[(byte >> 4) as usize];
+            hex[2 * i + 1] = HEX_CHARS[(byte & 0xf) as usize];
+        }
+
+        // EIP-55: capitalize letters whose nibble in the hash of the
+        // lower-case hex address is 8 or higher
+        let checksum = Keccak256::digest(hex);
+        for (i, c) in hex.iter().enumerate() {
+            let nibble = (checksum[i / 2] >> (4 * (1 - i % 2))) & 0xf;
+
+            if nibble >= 8 {
+                address.push(c.to_ascii_uppercase() as char);
+            } else {
+                address.push(*c as char);
+            }
+        }
+
+        address
+    }
+
+    /// Compute `RIPEMD160(SHA256(public_key))`.
+    fn key_hash(&self) -> [u8; KEY_HASH_SIZE] {
+        hash160(&self.public_key().to_bytes())
+    }
+}
+
+/// Compute `RIPEMD160(SHA256(data))`.
+fn hash160(data: &[u8]) -> [u8; KEY_HASH_SIZE] {
+    Ripemd160::digest(Sha256::digest(data)).into()
+}
+
+/// Encode a version byte and payload as Base58Check.
+fn base58_check(version: u8, payload: &[u8; KEY_HASH_SIZE]) -> String {
+    let mut bytes = [0u8; KEY_HASH_SIZE + 1];
+    bytes[0] = version;
+    bytes[1..].copy_from_slice(payload);
+    bs58::encode(&bytes).with_check().into_string()
+}
+
+/// Encode a SegWit address as specified in BIP173 (v0) and BIP350 (v1+).
+fn segwit_address(network: Network, version: u8, program: &[u8]) -> Result<String> {
+    let mut data = alloc::vec![version];
+    bech32::to_base32(program, &mut data);
+
+    let constant = if version == 0 {
+        BECH32_CONST
+    } else {
+        BECH32M_CONST
+    };
+
+    bech32::encode(network.hrp(), &data, constant)
+}
@@ -0,0 +1,242 @@
+//! BCH checksums over 5-bit values, as used by bech32 (BIP173 and BIP350)
+//! and output descriptor (BIP380) checksums.
+
+use crate::{Error, Result};
+use alloc::{string::String, vec::Vec};
+
+/// Characters used to encode 5-bit values.
+pub(crate) const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// BCH code used to compute a checksum.
+pub(crate) struct Code {
+    /// Generator of the code.
+    generator: [u64; 5],
+
+    /// Size of a checksum in characters.
+    size: usize,
+}
+
+/// Bech32 and bech32m checksums.
+pub(crate) const BECH32: Code = Code {
+    generator: [
+        0x3b6a_57b2,
+        0x2650_8e6d,
+        0x1ea1_19fa,
+        0x3d42_33dd,
+        0x2a14_62b3,
+    ],
+    size: 6,
+};
+
+/// Descriptor checksums.
+#[cfg(feature = "descriptor")]
+pub(crate) const DESCRIPTOR: Code = Code {
+    generator: [
+        0xf5dee51989,
+        0xa9fdca3312,
+        0x1bab10e32d,
+        0x3706b1677a,
+        0x644d626ffd,
+    ],
+    size: 8,
+};
+
+impl Code {
+    /// Feed one 5-bit value into the checksum.
+    pub(crate) fn polymod(&self, chk: u64, value: u64) -> u64 {
+        let shift = 5 * (self.size - 1);
+        let top = chk >> shift;
+        let mut chk = ((chk & ((1 << shift) - 1)) << 5) ^ value;
+
+        for (i, generator) in self.generator.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= generator;
+            }
+        }
+
+        chk
+    }
+
+    /// Finish computing a checksum, XORing it with the given constant, and
+    /// encode it using [`CHARSET`].
+    pub(crate) fn finish(&self, mut chk: u64, constant: u64) -> impl Iterator<Item = u8> + '_ {
+        for _ in 0..self.size {
+            chk = self.polymod(chk, 0);
+        }
+
+        chk ^= constant;
+
+        (0..self.size).map(move |i| CHARSET[((chk >> (5 * (self.size - 1 - i))) & 31) as usize])
+    }
+}
+
+/// Bech32 checksum constant (BIP173).
+pub(crate) const BECH32_CONST: u64 = 1;
+
+/// Bech32m checksum constant (BIP350).
+pub(crate) const BECH32M_CONST: u64 = 0x2bc8_30a3;
+
+/// Maximum length of a bech32 human-readable part.
+const MAX_HRP_LEN: usize = 83;
+
+/// Maximum length of a bech32 string.
+#[cfg(feature = "descriptor")]
+const MAX_LEN: usize = 90;
+
+/// Is the given string a valid (lower-case) bech32 human-readable part?
+pub(crate) fn is_valid_hrp(hrp: &str) -> bool {
+    !hrp.is_empty()
+        && hrp.len() <= MAX_HRP_LEN
+        && hrp
+            .bytes()
+            .all(|b| (33..=126).contains(&b) && !b.is_ascii_uppercase())
+}
+
+/// Start computing a checksum over the given human-readable part.
+fn hrp_checksum(hrp: &str) -> u64 {
+    let mut chk = 1;
+    for b in hrp.bytes() {
+        chk = BECH32.polymod(chk, u64::from(b >> 5));
+    }
+
+    chk = BECH32.polymod(chk, 0);
+    for b in hrp.bytes() {
+        chk = BECH32.polymod(chk, u64::from(b & 31));
+    }
+
+    chk
+}
+
+/// Encode 5-bit values with the given human-readable part as bech32 (or
+/// bech32m, depending on `constant`).
+///
+/// The human-readable part must be 1-83 lower-case ASCII characters in the
+/// range 33-126.
+#[cfg(feature = "address")]
+pub(crate) fn encode(hrp: &str, data: &[u8], constant: u64) -> Result<String> {
+    if !is_valid_hrp(hrp) {
+        return Err(Error::Hrp);
+    }
+
+    let mut chk = hrp_checksum(hrp);
+    for value in data {
+        chk = BECH32.polymod(chk, u64::from(*value));
+    }
+
+    let mut encoded = String::with_capacity(hrp.len() + 1 + data.len() + 6);
+    encoded.push_str(hrp);
+    encoded.push('1');
+    encoded.extend(data.iter().map(|value| CHARSET[*value as usize] as char));
+    encoded.extend(BECH32.finish(chk, constant).map(char::from));
+    Ok(encoded)
+}
+
+/// Decode a bech32 or bech32m string, returning its (lower-case)
+/// human-readable part, its 5-bit values without the checksum, and the
+/// checksum constant, i.e. [`BECH32_CONST`] or [`BECH32M_CONST`] for valid
+/// strings.
+///
+/// Strings may be either all lower-case or all upper-case.
+#[cfg(feature = "descriptor")]
+pub(crate) fn decode(s: &str) -> Result<(String, Vec<u8>, u64)> {
+    if s.len() > MAX_LEN
+        || (s.bytes().any(|b| b.is_ascii_lowercase()) && s.bytes().any(|b| b.is_ascii_uppercase()))
+    {
+        return Err(Error::Decode);
+    }
+
+    let s = s.to_ascii_lowercase();
+    let (hrp, data) = s.rsplit_once('1').ok_or(Error::Decode)?;
+
+    if !is_valid_hrp(hrp) || data.len() < BECH32.size {
+        return Err(Error::Decode);
+    }
+
+    let mut values = data
+        .bytes()
+        .map(|b| {
+            CHARSET
+                .iter()
+                .position(|&c| c == b)
+                .map(|value| value as u8)
+        })
+        .collect::<Option<Vec<u8>>>()
+        .ok_or(Error::Decode)?;
+
+    let mut chk = hrp_checksum(hrp);
+    for value in &values {
+        chk = BECH32.polymod(chk, u64::from(*value));
+    }
+
+    values.truncate(values.len() - BECH32.size);
+    Ok((hrp.into(), values, chk))
+}
+
+/// Convert bytes to 5-bit values, padding the last value with zeros.
+#[cfg(feature = "address")]
+pub(crate) fn to_base32(data: &[u8], out: &mut Vec<u8>) {
+    let mut acc = 0u32;
+    let mut bits = 0;
+    for byte in data {
+        acc = (acc << 8) | u32::from(*byte);
+        bits += 8;
+
+        while bits >= 5 {
+            bits -= 5;
+            out.push(((acc >> bits) & 31) as u8);
+        }
+    }
+
+    if bits > 0 {
+        out.push(((acc << (5 - bits)) & 31) as u8);
+    }
+}
+
+/// Convert 5-bit values to bytes, rejecting more than 4 bits of padding or
+/// non-zero padding.
+#[cfg(feature = "descriptor")]
+pub(crate) fn from_base32(data: &[u8]) -> Result<Vec<u8>> {
+    let mut out = Vec::with_capacity(data.len() * 5 / 8);
+    let mut acc = 0u32;
+    let mut bits = 0;
+    for value in data {
+        acc = (acc << 5) | u32::from(*value);
+        bits += 5;
+
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+        }
+    }
+
+    if bits >= 5 || acc & ((1 << bits) - 1) != 0 {
+        return Err(Error::Decode);
+    }
+
+    Ok(out)
+}
+
+#[cfg(all(test, feature = "address"))]
+mod tests {
+    use super::{BECH32_CONST, BECH32M_CONST, encode};
+    use crate::Error;
+    use alloc::vec::Vec;
+
+    /// Valid strings from the BIP173 and BIP350 test vectors
+    #[test]
+    fn encode_test_vectors() {
+        assert_eq!(encode("a", &[], BECH32_CONST).unwrap(), "a12uel5l");
+        assert_eq!(encode("a", &[], BECH32M_CONST).unwrap(), "a1lqfn3a");
+        assert_eq!(
+            encode("abcdef", &(0..32).collect::<Vec<_>>(), BECH32_CONST).unwrap(),
+            "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw"
+        );
+    }
+
+    #[test]
+    fn invalid_hrp() {
+        for hrp in ["", "A", "a b", "a\u{7f}", "a".repeat(84).as_str()] {
+            assert_eq!(encode(hrp, &[], BECH32_CONST), Err(Error::Hrp), "{hrp:?}");
+        }
+    }
+}
@@ -0,0 +1,189 @@
+//! Deterministic entropy from extended private keys as specified in BIP85.
+//!
+//! Entropy is derived from a master [`XPrv`] using a hardened path under the
+//! BIP85 purpose, `m/83696968'`, which identifies the application the
+//! entropy is used for. Supported applications are:
+//!
+//! - BIP39: mnemonic phrases of any length, in any enabled language
+//! - HD-Seed WIF: WIF-encoded secp256k1 private keys
+//! - XPRV: extended private keys
+//! - HEX: 16 to 64 bytes of hex-encoded entropy
+
+use crate::{
+    ChildNumber, DerivationPath, Error, ExtendedKey, ExtendedKeyAttrs, KEY_SIZE, KeyFingerprint,
+    Prefix, Result, XPrv, private_key::hmac_split,
+};
+use alloc::string::String;
+use core::str;
+use subtle_encoding::hex;
+use zeroize::{Zeroize, Zeroizing};
+
+#[cfg(feature = "bip39")]
+use crate::{Language, Mnemonic, WordCount};
+
+/// BIP85 purpose.
+const PURPOSE: u32 = 83_696_968;
+
+/// BIP39 application number.
+#[cfg(feature = "bip39")]
+const APP_BIP39: u32 = 39;
+
+/// HD-Seed WIF application number.
+const APP_WIF: u32 = 2;
+
+/// XPRV application number.
+const APP_XPRV: u32 = 32;
+
+/// HEX application number.
+const APP_HEX: u32 = 128_169;
+
+/// HMAC key used to derive entropy from a derived private key.
+const HMAC_KEY: &[u8] = b"bip-entropy-from-k";
+
+/// Size of the entropy derived for each path.
+const ENTROPY_SIZE: usize = 64;
+
+/// Version byte of mainnet WIF private keys.
+const WIF_VERSION: u8 = 0x80;
+
+impl XPrv {
+    /// Derive 64 bytes of BIP85 entropy for the given derivation path.
+    ///
+    /// All child numbers in the path must be hardened, and the path should
+    /// start with the BIP85 purpose, `83696968'`.
+    pub fn bip85_entropy(&self, path: &DerivationPath) -> Result<Zeroizing<[u8; ENTROPY_SIZE]>> {
+        let mut xprv = self.clone();
+
+        for child_number in path.iter() {
+            if !child_number.is_hardened() {
+                return Err(Error::ChildNumber);
+            }
+
+            xprv = xprv.derive_child(child_number)?;
+        }
+
+        let (mut left, mut right) = hmac_split(HMAC_KEY, &[&xprv.to_bytes()])?;
+        let mut entropy = Zeroizing::new([0u8; ENTROPY_SIZE]);
+        entropy[..KEY_SIZE].copy_from_slice(&left);
+        entropy[KEY_SIZE..].copy_from_slice(&right);
+        left.zeroize();
+        right.zeroize();
+
+        Ok(entropy)
+    }
+
+    /// Derive a BIP39 mnemonic phrase with the given language, length, and
+    /// index, using the path `m/83696968'/39'/{language}'/{words}'/{index}'`.
+    #[cfg(feature = "bip39")]
+    pub fn bip85_mnemonic(
+        &self,
+        language: Language,
+        word_count: WordCount,
+        index: u32,
+    ) -> Result<Mnemonic> {
+        let entropy = self.bip85_entropy(&bip85_path(&[
+            APP_BIP39,
+            language_code(language),
+            word_count.words() as u32,
+            index,
+        ])?)?;
+
+        Mnemonic::from_entropy(&entropy[..word_count.entropy_size()], language)
+    }
+
+    /// Derive a WIF-encoded (compressed, mainnet) private key with the given
+    /// index, using the path `m/83696968'/2'/{index}'`.
+    pub fn bip85_wif(&self, index: u32) -> Result<Zeroizing<String>> {
+        let entropy = self.bip85_entropy(&bip85_path(&[APP_WIF, index])?)?;
+
+        // Ensure the key is valid
+        k256::SecretKey::from_slice(&entropy[..KEY_SIZE]).map_err(|_| Error::Crypto)?;
+
+        let mut bytes = Zeroizing::new([0u8; KEY_SIZE + 2]);
+        bytes[0] = WIF_VERSION;
+        bytes[1..=KEY_SIZE].copy_from_slice(&entropy[..KEY_SIZE]);
+        bytes[KEY_SIZE + 1] = 0x01; // compressed public key
+
+        Ok(Zeroizing::new(
+            bs58::encode(bytes.as_slice()).with_check().into_string(),
+        ))
+    }
+
+    /// Derive a root extended private key with the given index, using the
+    /// path `m/83696968'/32'/{index}'`.
+    pub fn bip85_xprv(&self, index: u32) -> Result<XPrv> {
+        let entropy = self.bip85_entropy(&bip85_path(&[APP_XPRV, index])?)?;
+
+        // The first half of the entropy is the chain code, and the second
+        // half is the private key (the reverse of BIP32 master keys)
+        let mut key_bytes = Zeroizing::new([0u8; KEY_SIZE + 1]);
+        key_bytes[1..].copy_from_slice(&entropy[KEY_SIZE..]);
+
+        let extended_key = ExtendedKey {
+            prefix: Prefix::XPRV,
+            attrs: ExtendedKeyAttrs {
+                depth: 0,
+                parent_fingerprint: KeyFingerprint::default(),
+                child_number: ChildNumber::default(),
+                chain_code: entropy[..KEY_SIZE].try_into()?,
+            },
+            key_bytes: *key_bytes,
+        };
+
+        XPrv::try_from(extended_key)
+    }
+
+    /// Derive `num_bytes` bytes of hex-encoded entropy with the given index,
+    /// using the path `m/83696968'/128169'/{num_bytes}'/{index}'`.
+    ///
+    /// Returns [`Error::SeedLength`] unless `num_bytes` is between 16 and 64.
+    pub fn bip85_hex(&self, num_bytes: usize, index: u32) -> Result<Zeroizing<String>> {
+        if !(16..=ENTROPY_SIZE).contains(&num_bytes) {
+            return Err(Error::SeedLength);
+        }
+
+        let entropy = self.bip85_entropy(&bip85_path(&[APP_HEX, num_bytes as u32, index])?)?;
+        let hex = Zeroizing::new(hex::encode(&entropy[..num_bytes]));
+        let hex = str::from_utf8(&hex).map_err(|_| Error::Decode)?;
+        Ok(Zeroizing::new(hex.into()))
+    }
+}
+
+/// Build a BIP85 derivation path for the given application and parameters,
+/// hardening each child number.
+fn bip85_path(indexes: &[u32]) -> Result<DerivationPath> {
+    let mut path = DerivationPath::default();
+    path.push(ChildNumber::new(PURPOSE, true)?);
+
+    for &index in indexes {
+        path.push(ChildNumber::new(index, true)?);
+    }
+
+    Ok(path)
+}
+
+/// Get the BIP85 code of a BIP39 wordlist language.
+#[cfg(feature = "bip39")]
+fn language_code(language: Language) -> u32 {
+    match language {
+        Language::English => 0,
+        #[cfg(feature = "japanese")]
+        Language::Japanese => 1,
+        #[cfg(feature = "korean")]
+        Language::Korean => 2,
+        #[cfg(feature = "spanish")]
+        Language::Spanish => 3,
+        #[cfg(feature = "chinese-simplified")]
+        Language::ChineseSimplified => 4,
+        #[cfg(feature = "chinese-traditional")]
+        Language::ChineseTraditional => 5,
+        #[cfg(feature = "french")]
+        Language::French => 6,
+        #[cfg(feature = "italian")]
+        Language::Italian => 7,
+        #[cfg(feature = "czech")]
+        Language::Czech => 8,
+        #[cfg(feature = "portuguese")]
+        Language::Portuguese => 9,
+    }
+}
@@ -6,6 +6,12 @@ use core::{
     str::FromStr,
 };
 
+#[cfg(feature = "serde")]
+use {
+    alloc::string::String,
+    serde::{Deserialize, Serialize, de, ser},
+};
+
 /// Index of a particular child key for a given (extended) private key.
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq, PartialOrd, Ord)]
 pub struct ChildNumber(pub u32);
@@ -50,6 +56,13 @@ impl ChildNumber {
     pub fn is_hardened(&self) -> bool {
         self.0 & Self::HARDENED_FLAG != 0
     }
+
+    /// Get the child number with the next index and the same hardened flag.
+    ///
+    /// Returns an error if the index would overflow.
+    pub(crate) fn next(self) -> Result<Self> {
+        Self::new(self.index() + 1, self.is_hardened())
+    }
 }
 
 impl Display for ChildNumber {
@@ -93,6 +106,29 @@ impl FromStr for ChildNumber {
     }
 }
 
+/// Serialized as a string, e.g. `44'`.
+#[cfg(feature = "serde")]
+impl Serialize for ChildNumber {
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for ChildNumber {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::ChildNumber;
@@ -7,6 +7,12 @@ use core::{
     str::FromStr,
 };
 
+#[cfg(feature = "serde")]
+use {
+    alloc::string::String,
+    serde::{Deserialize, Serialize, de, ser},
+};
+
 /// Prefix for all derivation paths.
 const PREFIX: &str = "m";
 
@@ -87,7 +93,10 @@ impl FromStr for DerivationPath {
         }
 
         Ok(DerivationPath {
-            path: path.map(str::parse).collect::<Result<_>>()?,
+            path: path
+                .enumerate()
+                .map(|(index, child)| child.parse().map_err(|_| Error::PathSegment { index }))
+                .collect::<Result<_>>()?,
         })
     }
 }
@@ -101,9 +110,33 @@ impl IntoIterator for DerivationPath {
     }
 }
 
+/// Serialized as a string, e.g. `m/44'/0'/0'`.
+#[cfg(feature = "serde")]
+impl Serialize for DerivationPath {
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for DerivationPath {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::DerivationPath;
+    use crate::Error;
     use alloc::string::ToString;
 
     /// BIP32 test vectors
@@ -169,4 +202,16 @@ mod tests {
         assert_eq!("m", path_m.to_string());
         assert_eq!(path_m.parent(), None);
     }
+
+    #[test]
+    fn invalid_segment() {
+        assert_eq!(
+            "m/44'/0'/x/0".parse::<DerivationPath>(),
+            Err(Error::PathSegment { index: 2 })
+        );
+        assert_eq!(
+            "m/0/2147483648".parse::<DerivationPath>(),
+            Err(Error::PathSegment { index: 1 })
+        );
+    }
 }
@@ -0,0 +1,658 @@
+//! Output descriptors (BIP380-386).
+//!
+//! Descriptors describe the output scripts of a wallet in a standard string
+//! format, e.g. `wpkh([d34db33f/84'/0'/0']xpub.../0/*)#checksum`, which allows
+//! watch-only wallets to be configured from an extended public key along with
+//! its key origin information.
+//!
+//! Only public keys are supported, and `tr()` descriptors may not contain
+//! script trees.
+
+mod checksum;
+mod key;
+
+pub use self::key::{DescriptorPublicKey, KeyOrigin, Wildcard};
+
+use self::key::ResolvedKey;
+use crate::{
+    Error, PublicKey, Result,
+    bech32::{self, BECH32_CONST, BECH32M_CONST},
+};
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::{
+    fmt::{self, Display, Write},
+    ops::Range,
+    str::{self, FromStr},
+};
+use subtle_encoding::hex;
+
+/// Maximum number of keys in a `multi()` or `sortedmulti()` expression.
+const MAX_MULTI_KEYS: usize = 20;
+
+/// Maximum number of keys in a `multi()` or `sortedmulti()` expression inside
+/// `sh()`, which is limited by the 520 byte redeem script size.
+const MAX_MULTI_KEYS_SH: usize = 15;
+
+/// Output descriptor.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Descriptor {
+    /// `pk(KEY)`: pay-to-pubkey (BIP381)
+    Pk(DescriptorPublicKey),
+
+    /// `pkh(KEY)`: pay-to-pubkey-hash (BIP381)
+    Pkh(DescriptorPublicKey),
+
+    /// `sh(SCRIPT)`: pay-to-script-hash (BIP381)
+    Sh(Box<Descriptor>),
+
+    /// `wpkh(KEY)`: pay-to-witness-pubkey-hash (BIP382)
+    Wpkh(DescriptorPublicKey),
+
+    /// `wsh(SCRIPT)`: pay-to-witness-script-hash (BIP382)
+    Wsh(Box<Descriptor>),
+
+    /// `multi(k,KEY_1,...,KEY_n)`: k-of-n multisig (BIP383)
+    Multi(Multi),
+
+    /// `sortedmulti(k,KEY_1,...,KEY_n)`: k-of-n multisig with keys sorted
+    /// lexicographically (BIP383)
+    SortedMulti(Multi),
+
+    /// `combo(KEY)`: P2PK, P2PKH, P2WPKH, and P2SH-P2WPKH outputs (BIP384)
+    Combo(DescriptorPublicKey),
+
+    /// `raw(HEX)`: raw output script (BIP385)
+    Raw(Vec<u8>),
+
+    /// `addr(ADDR)`: output script for an address (BIP385)
+    Addr(DescriptorAddress),
+
+    /// `tr(KEY)`: single key Taproot output (BIP386)
+    Tr(DescriptorPublicKey),
+}
+
+/// Context in which a script expression appears.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Context {
+    /// Top-level expression.
+    Top,
+
+    /// Inside `sh()`.
+    Sh,
+
+    /// Inside `wsh()`.
+    Wsh,
+}
+
+impl Descriptor {
+    /// Get the public keys in this descriptor, in the order they appear.
+    pub fn keys(&self) -> Vec<&DescriptorPublicKey> {
+        match self {
+            Descriptor::Pk(key)
+            | Descriptor::Pkh(key)
+            | Descriptor::Wpkh(key)
+            | Descriptor::Combo(key)
+            | Descriptor::Tr(key) => alloc::vec![key],
+            Descriptor::Sh(inner) | Descriptor::Wsh(inner) => inner.keys(),
+            Descriptor::Multi(multi) | Descriptor::SortedMulti(multi) => {
+                multi.keys.iter().collect()
+            }
+            Descriptor::Raw(_) | Descriptor::Addr(_) => Vec::new(),
+        }
+    }
+
+    /// Does this descriptor contain any ranged keys?
+    pub fn is_ranged(&self) -> bool {
+        self.keys().iter().any(|key| key.is_ranged())
+    }
+
+    /// Derive the public keys of this descriptor at the given index.
+    ///
+    /// Keys are returned in the order they appear in the output script, i.e.
+    /// the keys of `sortedmulti()` are sorted.
+    pub fn derive<K: PublicKey + Clone>(&self, index: u32) -> Result<Vec<K>> {
+        self.derive_range(index..index.saturating_add(1))?
+            .next()
+            .unwrap_or(Err(Error::ChildNumber))
+    }
+
+    /// Iterate over the public keys of this descriptor for a range of
+    /// indices.
+    ///
+    /// The non-ranged part of each key's derivation path is derived once
+    /// up front, so each iteration only needs to derive one child per key.
+    pub fn derive_range<K: PublicKey + Clone>(
+        &self,
+        indices: Range<u32>,
+    ) -> Result<DescriptorKeys<K>> {
+        let keys = self
+            .keys()
+            .into_iter()
+            .map(DescriptorPublicKey::resolve)
+            .collect::<Result<_>>()?;
+
+        Ok(DescriptorKeys {
+            keys,
+            sorted: self.is_sorted(),
+            indices,
+        })
+    }
+
+    /// Compute the checksum of this descriptor.
+    ///
+    /// Returns [`Error::Descriptor`] if the descriptor contains a character
+    /// which can't appear in a descriptor.
+    pub fn checksum(&self) -> Result<String> {
+        let checksum = checksum::checksum(&self.to_string_without_checksum())?;
+        Ok(checksum.iter().copied().map(char::from).collect())
+    }
+
+    /// Serialize this descriptor without a checksum.
+    pub fn to_string_without_checksum(&self) -> String {
+        let mut s = String::new();
+        self.write_body(&mut s).expect("fmt error");
+        s
+    }
+
+    /// Are the keys of this descriptor sorted in the output script?
+    fn is_sorted(&self) -> bool {
+        match self {
+            Descriptor::SortedMulti(_) => true,
+            Descriptor::Sh(inner) | Descriptor::Wsh(inner) => inner.is_sorted(),
+            _ => false,
+        }
+    }
+
+    /// Parse a script expression in the given context.
+    fn parse(s: &str, context: Context) -> Result<Self> {
+        let (name, args) = s
+            .strip_suffix(')')
+            .and_then(|s| s.split_once('('))
+            .ok_or(Error::Descriptor)?;
+
+        let descriptor = match (name, context) {
+            ("pk", _) => Descriptor::Pk(parse_key(args, context)?),
+            ("pkh", _) => Descriptor::Pkh(parse_key(args, context)?),
+            ("sh", Context::Top) => Descriptor::Sh(Box::new(Self::parse(args, Context::Sh)?)),
+            ("wpkh", Context::Top | Context::Sh) => {
+                Descriptor::Wpkh(parse_key(args, Context::Wsh)?)
+            }
+            ("wsh", Context::Top | Context::Sh) => {
+                Descriptor::Wsh(Box::new(Self::parse(args, Context::Wsh)?))
+            }
+            ("multi", _) => Descriptor::Multi(Multi::parse(args, context)?),
+            ("sortedmulti", _) => Descriptor::SortedMulti(Multi::parse(args, context)?),
+            ("combo", Context::Top) => Descriptor::Combo(parse_key(args, context)?),
+            ("raw", Context::Top) => {
+                Descriptor::Raw(hex::decode(args).map_err(|_| Error::Descriptor)?)
+            }
+            ("addr", Context::Top) => Descriptor::Addr(args.parse()?),
+            ("tr", Context::Top) => {
+                let key = args.parse::<DescriptorPublicKey>()?;
+
+                if key.is_uncompressed() {
+                    return Err(Error::Descriptor);
+                }
+
+                Descriptor::Tr(key)
+            }
+            _ => return Err(Error::Descriptor),
+        };
+
+        Ok(descriptor)
+    }
+
+    /// Write this descriptor without a checksum.
+    fn write_body(&self, f: &mut impl Write) -> fmt::Result {
+        match self {
+            Descriptor::Pk(key) => write!(f, "pk({})", key),
+            Descriptor::Pkh(key) => write!(f, "pkh({})", key),
+            Descriptor::Sh(inner) => {
+                f.write_str("sh(")?;
+                inner.write_body(f)?;
+                f.write_str(")")
+            }
+            Descriptor::Wpkh(key) => write!(f, "wpkh({})", key),
+            Descriptor::Wsh(inner) => {
+                f.write_str("wsh(")?;
+                inner.write_body(f)?;
+                f.write_str(")")
+            }
+            Descriptor::Multi(multi) => write!(f, "multi({})", multi),
+            Descriptor::SortedMulti(multi) => write!(f, "sortedmulti({})", multi),
+            Descriptor::Combo(key) => write!(f, "combo({})", key),
+            Descriptor::Raw(script) => {
+                f.write_str("raw(")?;
+
+                for byte in script {
+                    write!(f, "{:02x}", byte)?;
+                }
+
+                f.write_str(")")
+            }
+            Descriptor::Addr(addr) => write!(f, "addr({})", addr),
+            Descriptor::Tr(key) => write!(f, "tr({})", key),
+        }
+    }
+}
+
+/// Serializes the descriptor with its checksum.
+impl Display for Descriptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let checksum = self.checksum().map_err(|_| fmt::Error)?;
+        write!(f, "{}#{}", self.to_string_without_checksum(), checksum)
+    }
+}
+
+/// Parses a descriptor, verifying its checksum if present.
+impl FromStr for Descriptor {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(checksum::verify(s)?, Context::Top)
+    }
+}
+
+/// Address in an `addr()` expression.
+///
+/// Addresses must be valid Base58Check addresses with a 20-byte payload
+/// (i.e. P2PKH or P2SH), or valid SegWit addresses (BIP173 and BIP350), for
+/// any network.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DescriptorAddress(String);
+
+impl DescriptorAddress {
+    /// Borrow the address as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for DescriptorAddress {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Display for DescriptorAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for DescriptorAddress {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if !is_base58_address(s) && !is_segwit_address(s) {
+            return Err(Error::Descriptor);
+        }
+
+        Ok(Self(s.into()))
+    }
+}
+
+/// Is the given string a Base58Check encoded P2PKH or P2SH address?
+fn is_base58_address(s: &str) -> bool {
+    // Version byte, hash, and checksum
+    let mut buffer = [0u8; 25];
+    bs58::decode(s)
+        .with_check(None)
+        .onto(buffer.as_mut_slice())
+        .is_ok_and(|len| len == 21)
+}
+
+/// Is the given string a SegWit address (BIP173 and BIP350)?
+fn is_segwit_address(s: &str) -> bool {
+    let Ok((_, data, constant)) = bech32::decode(s) else {
+        return false;
+    };
+
+    let Some((&version, program)) = data.split_first() else {
+        return false;
+    };
+
+    let Ok(program) = bech32::from_base32(program) else {
+        return false;
+    };
+
+    match version {
+        0 => constant == BECH32_CONST && matches!(program.len(), 20 | 32),
+        1..=16 => constant == BECH32M_CONST && (2..=40).contains(&program.len()),
+        _ => false,
+    }
+}
+
+/// Threshold and keys of a `multi()` or `sortedmulti()` expression.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Multi {
+    threshold: usize,
+    keys: Vec<DescriptorPublicKey>,
+}
+
+impl Multi {
+    /// Create a new k-of-n multisig expression.
+    ///
+    /// The threshold must be at least 1 and no more than the number of keys,
+    /// and there may be at most 20 keys (or 15 inside `sh()`).
+    pub fn new(threshold: usize, keys: Vec<DescriptorPublicKey>) -> Result<Self> {
+        Self::new_in_context(threshold, keys, Context::Top)
+    }
+
+    /// Create a new k-of-n multisig expression in the given context.
+    fn new_in_context(
+        threshold: usize,
+        keys: Vec<DescriptorPublicKey>,
+        context: Context,
+    ) -> Result<Self> {
+        let max_keys = match context {
+            Context::Sh => MAX_MULTI_KEYS_SH,
+            Context::Top | Context::Wsh => MAX_MULTI_KEYS,
+        };
+
+        if threshold == 0 || threshold > keys.len() || keys.len() > max_keys {
+            return Err(Error::Descriptor);
+        }
+
+        Ok(Self { threshold, keys })
+    }
+
+    /// Get the number of signatures required.
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Get the keys in this expression.
+    pub fn keys(&self) -> &[DescriptorPublicKey] {
+        &self.keys
+    }
+
+    /// Parse the arguments of a multisig expression in the given context.
+    fn parse(args: &str, context: Context) -> Result<Self> {
+        let mut args = args.split(',');
+        let threshold = args
+            .next()
+            .and_then(|k| k.parse().ok())
+            .ok_or(Error::Descriptor)?;
+
+        let keys = args
+            .map(|key| parse_key(key, context))
+            .collect::<Result<_>>()?;
+
+        Self::new_in_context(threshold, keys, context)
+    }
+}
+
+impl Display for Multi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.threshold)?;
+
+        for key in &self.keys {
+            write!(f, ",{}", key)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Iterator over the public keys of a [`Descriptor`] for a range of indices.
+///
+/// Created by [`Descriptor::derive_range`].
+pub struct DescriptorKeys<K: PublicKey> {
+    keys: Vec<ResolvedKey<K>>,
+    sorted: bool,
+    indices: Range<u32>,
+}
+
+impl<K: PublicKey + Clone> Iterator for DescriptorKeys<K> {
+    type Item = Result<Vec<K>>;
+
+    fn next(&mut self) -> Option<Result<Vec<K>>> {
+        let index = self.indices.next()?;
+        let keys = self
+            .keys
+            .iter()
+            .map(|key| key.derive(index))
+            .collect::<Result<Vec<_>>>();
+
+        Some(keys.map(|mut keys| {
+            if self.sorted {
+                keys.sort_by_key(|key| key.to_bytes());
+            }
+
+            keys
+        }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indices.size_hint()
+    }
+}
+
+/// Parse a (non-Taproot) key expression in the given context.
+fn parse_key(s: &str, context: Context) -> Result<DescriptorPublicKey> {
+    let key = s.parse::<DescriptorPublicKey>()?;
+
+    // X-only keys are only valid in `tr()`, and uncompressed keys aren't valid
+    // in SegWit contexts
+    if key.is_x_only() || (context == Context::Wsh && key.is_uncompressed()) {
+        return Err(Error::Descriptor);
+    }
+
+    Ok(key)
+}
+
+#[cfg(all(test, feature = "secp256k1"))]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::{Descriptor, DescriptorAddress, Wildcard};
+    use crate::{ChildNumber, Error, XPub};
+    use alloc::string::ToString;
+    use hex_literal::hex;
+
+    /// Descriptors from BIP380-386 and Bitcoin Core
+    const DESCRIPTORS: &[&str] = &[
+        "pk(020000000000000000000000000000000000000000000000000000000000000002)#7yxkn84h",
+        "pkh(020000000000000000000000000000000000000000000000000000000000000002)#ma7nspkf",
+        "wpkh(020000000000000000000000000000000000000000000000000000000000000002)#d3xz2xye",
+        "sh(wpkh(020000000000000000000000000000000000000000000000000000000000000002))#45zpjtet",
+        "tr(020000000000000000000000000000000000000000000000000000000000000002)#8hc7wq5h",
+        "sh(sortedmulti(1,03fff97bd5755eeea420453a14355235d382f6472f8568a18b2f057a1460297556,0250863ad64a87ae8a2fe83c1af1a8403cb53f53e486d8511dad8a04887e5b2352))#uetvewm2",
+        "sh(wsh(sortedmulti(1,xpub661MyMwAqRbcFW31YEwpkMuc5THy2PSt5bDMsktWQcFF8syAmRUapSCGu8ED9W6oDMSgv6Zz8idoc4a6mr8BDzTJY47LJhkJ8UB7WEGuduB/1/0/*,xpub69H7F5d8KSRgmmdJg2KhpAK8SR3DjMwAdkxj3ZuxV27CprR9LgpeyGmXUbC6wb7ERfvrnKZjXoUmmDznezpbZb7ap6r1D3tgFxHmwMkQTPH/0/0/*)))#u60cee0u",
+        "sh(multi(2,[00000000/111'/222]xpub6ERApfZwUNrhLCkDtcHTcxd75RbzS1ed54G1LkBUHQVHQKqhMkhgbmJbZRkrgZw4koxb5JaHWkY4ALHY2grBGRjaDMzQLcgJvLJuZZvRcEL,xpub68NZiKmJWnxxS6aaHmn81bvJeTESw724CRDs6HbuccFQN9Ku14VQrADWgqbhhTHBaohPX4CjNLf9fq9MYo6oDaPPLPxSb7gwQN3ih19Zm4Y/0))#tjg09x5t",
+        "wpkh([2cbe2a6d/44'/0'/0']tpubDCvNhURocXGZsLNqWcqD3syHTqPXrMSTwi8feKVwAcpi29oYKsDD3Vex7x2TDneKMVN23RbLprfxB69v94iYqdaYHsVz3kPR37NQXeqouVz/0/*)#nhdxg96s",
+        "raw(deadbeef)#89f8spxm",
+    ];
+
+    #[test]
+    fn round_trip() {
+        for &descriptor in DESCRIPTORS {
+            assert_eq!(
+                descriptor.parse::<Descriptor>().unwrap().to_string(),
+                descriptor
+            );
+        }
+    }
+
+    #[test]
+    fn without_checksum() {
+        let descriptor =
+            "sh(wpkh(020000000000000000000000000000000000000000000000000000000000000002))"
+                .parse::<Descriptor>()
+                .unwrap();
+
+        assert_eq!(descriptor.checksum().unwrap(), "45zpjtet");
+    }
+
+    #[test]
+    fn key_origin() {
+        let descriptor = DESCRIPTORS[8].parse::<Descriptor>().unwrap();
+        let Descriptor::Wpkh(key) = &descriptor else {
+            panic!("expected wpkh");
+        };
+
+        let origin = key.origin().unwrap();
+        assert_eq!(origin.fingerprint, hex!("2cbe2a6d"));
+        assert_eq!(origin.path, "m/44'/0'/0'".parse().unwrap());
+        assert_eq!(key.wildcard(), Wildcard::Unhardened);
+        assert!(descriptor.is_ranged());
+    }
+
+    #[test]
+    fn derive_range() {
+        let xpub = "xpub6ERApfZwUNrhLCkDtcHTcxd75RbzS1ed54G1LkBUHQVHQKqhMkhgbmJbZRkrgZw4koxb5JaHWkY4ALHY2grBGRjaDMzQLcgJvLJuZZvRcEL";
+        let descriptor = alloc::format!("wpkh([d34db33f/84'/0'/0']{xpub}/0/*)")
+            .parse::<Descriptor>()
+            .unwrap();
+
+        let xpub = xpub.parse::<XPub>().unwrap();
+        let change = xpub.derive_child(ChildNumber(0)).unwrap();
+
+        for (index, keys) in descriptor
+            .derive_range::<k256::ecdsa::VerifyingKey>(0..5)
+            .unwrap()
+            .enumerate()
+        {
+            let expected = change.derive_child(ChildNumber(index as u32)).unwrap();
+            assert_eq!(keys.unwrap(), [*expected.public_key()]);
+        }
+
+        assert_eq!(
+            descriptor.derive::<k256::ecdsa::VerifyingKey>(3).unwrap(),
+            descriptor
+                .derive_range(3..4)
+                .unwrap()
+                .next()
+                .unwrap()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn sortedmulti_order() {
+        let descriptor = DESCRIPTORS[5].parse::<Descriptor>().unwrap();
+        let keys = descriptor.derive::<k256::PublicKey>(0).unwrap();
+        assert!(keys[0] < keys[1]);
+    }
+
+    #[test]
+    fn invalid() {
+        for invalid in [
+            // Invalid checksum
+            "pk(020000000000000000000000000000000000000000000000000000000000000002)#7yxkn84x",
+            // Nested `sh()`
+            "sh(sh(pk(020000000000000000000000000000000000000000000000000000000000000002)))",
+            // `wpkh()` inside `wsh()`
+            "wsh(wpkh(020000000000000000000000000000000000000000000000000000000000000002))",
+            // Uncompressed key inside `wpkh()`
+            "wpkh(04a34b99f22c790c4e36b2b3c2c35a36db06226e41c692fc82b8b56ac1c540c5bd5b8dec5235a0fa8722476c7709c02559e3aa73aa03918ba2d492eea75abea235)",
+            // X-only key outside `tr()`
+            "pk(0000000000000000000000000000000000000000000000000000000000000002)",
+            // Threshold larger than the number of keys
+            "multi(2,020000000000000000000000000000000000000000000000000000000000000002)",
+            // Private key
+            "pkh(xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi)",
+            // Wildcard in the middle of a path
+            "pkh(xpub661MyMwAqRbcFW31YEwpkMuc5THy2PSt5bDMsktWQcFF8syAmRUapSCGu8ED9W6oDMSgv6Zz8idoc4a6mr8BDzTJY47LJhkJ8UB7WEGuduB/*/0)",
+        ] {
+            assert_eq!(
+                invalid.parse::<Descriptor>(),
+                Err(Error::Descriptor),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn addr() {
+        let descriptor = "addr(bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq)"
+            .parse::<Descriptor>()
+            .unwrap();
+
+        let Descriptor::Addr(addr) = &descriptor else {
+            panic!("expected addr");
+        };
+
+        assert_eq!(addr.as_str(), "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq");
+        assert_eq!(
+            descriptor.to_string().parse::<Descriptor>().unwrap(),
+            descriptor
+        );
+
+        for valid in [
+            "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2",
+            "3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy",
+            "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4",
+            "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+            "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+        ] {
+            assert_eq!(valid.parse::<DescriptorAddress>().unwrap().as_str(), valid);
+        }
+
+        for invalid in [
+            "",
+            "bc1q ar0s",
+            "bc1q#ar0s",
+            "bc1q(ar0s)",
+            "bc1q,ar0s",
+            // Bad checksums
+            "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN3",
+            "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdr",
+            // Mixed case
+            "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mDq",
+            // SegWit v1 with a bech32 checksum, and v0 with bech32m (BIP350)
+            "bc1p38j9r5y49hruaue7wxjce0updqjuyyx0kh56v8s25huc6995vvpql3jow4",
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kemeawh",
+            // Invalid program length
+            "bc1pw5dgrnzv",
+        ] {
+            assert_eq!(
+                invalid.parse::<DescriptorAddress>(),
+                Err(Error::Descriptor),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn multi_key_limit() {
+        let key = "020000000000000000000000000000000000000000000000000000000000000002";
+        let multi = |n: usize| alloc::format!("multi(1{})", alloc::format!(",{key}").repeat(n));
+
+        assert!(
+            alloc::format!("sh({})", multi(15))
+                .parse::<Descriptor>()
+                .is_ok()
+        );
+        assert_eq!(
+            alloc::format!("sh({})", multi(16)).parse::<Descriptor>(),
+            Err(Error::Descriptor)
+        );
+        assert!(
+            alloc::format!("wsh({})", multi(20))
+                .parse::<Descriptor>()
+                .is_ok()
+        );
+        assert_eq!(
+            alloc::format!("wsh({})", multi(21)).parse::<Descriptor>(),
+            Err(Error::Descriptor)
+        );
+    }
+
+    #[test]
+    fn hardened_wildcard() {
+        let descriptor = "pkh(xpub661MyMwAqRbcFW31YEwpkMuc5THy2PSt5bDMsktWQcFF8syAmRUapSCGu8ED9W6oDMSgv6Zz8idoc4a6mr8BDzTJY47LJhkJ8UB7WEGuduB/0/*h)"
+            .parse::<Descriptor>()
+            .unwrap();
+
+        assert_eq!(descriptor.keys()[0].wildcard(), Wildcard::Hardened);
+        assert!(descriptor.to_string().starts_with(
+            "pkh(xpub661MyMwAqRbcFW31YEwpkMuc5THy2PSt5bDMsktWQcFF8syAmRUapSCGu8ED9W6oDMSgv6Zz8idoc4a6mr8BDzTJY47LJhkJ8UB7WEGuduB/0/*')#"
+        ));
+        assert_eq!(
+            descriptor.derive::<k256::PublicKey>(0),
+            Err(Error::ChildNumber)
+        );
+    }
+}
@@ -0,0 +1,88 @@
+//! Descriptor checksums as specified in BIP380.
+
+use crate::{Error, Result, bech32};
+
+/// Characters which may appear in a descriptor, in the order used to compute
+/// the checksum.
+const INPUT_CHARSET: &str = "0123456789()[],'/*abcdefgh@:$%{}\
+    IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~\
+    ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+
+/// Size of a checksum in characters.
+pub(super) const CHECKSUM_SIZE: usize = 8;
+
+/// Compute the checksum of the given descriptor (without a `#` suffix).
+pub(super) fn checksum(descriptor: &str) -> Result<[u8; CHECKSUM_SIZE]> {
+    let mut chk = 1;
+    let mut class = 0;
+    let mut class_count = 0;
+
+    for c in descriptor.chars() {
+        let position = INPUT_CHARSET.find(c).ok_or(Error::Descriptor)? as u64;
+
+        // Each character is encoded as its position modulo 32, and every
+        // three characters their groups (position / 32) are encoded together
+        chk = bech32::DESCRIPTOR.polymod(chk, position & 31);
+        class = class * 3 + (position >> 5);
+        class_count += 1;
+
+        if class_count == 3 {
+            chk = bech32::DESCRIPTOR.polymod(chk, class);
+            class = 0;
+            class_count = 0;
+        }
+    }
+
+    if class_count > 0 {
+        chk = bech32::DESCRIPTOR.polymod(chk, class);
+    }
+
+    let mut result = [0u8; CHECKSUM_SIZE];
+    for (byte, c) in result.iter_mut().zip(bech32::DESCRIPTOR.finish(chk, 1)) {
+        *byte = c;
+    }
+
+    Ok(result)
+}
+
+/// Strip and verify the checksum of the given descriptor, if present.
+pub(super) fn verify(descriptor: &str) -> Result<&str> {
+    let Some((descriptor, expected)) = descriptor.split_once('#') else {
+        // Ensure the descriptor only contains valid characters
+        checksum(descriptor)?;
+        return Ok(descriptor);
+    };
+
+    if checksum(descriptor)?.as_slice() == expected.as_bytes() {
+        Ok(descriptor)
+    } else {
+        Err(Error::Descriptor)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::{checksum, verify};
+    use crate::Error;
+
+    /// BIP380 test vectors
+    #[test]
+    fn bip380_checksums() {
+        assert_eq!(&checksum("raw(deadbeef)").unwrap(), b"89f8spxm");
+        assert_eq!(verify("raw(deadbeef)#89f8spxm"), Ok("raw(deadbeef)"));
+        assert_eq!(verify("raw(deadbeef)"), Ok("raw(deadbeef)"));
+
+        for invalid in [
+            "raw(deadbeef)#",
+            "raw(deadbeef)#89f8spxmx",
+            "raw(deadbeef)#89f8spx",
+            "raw(deadbeef)#89f8spxx",
+            "raw(dedbeef)#89f8spxm",
+            "raw(deadbeef)##9f8spxm",
+            "raw(Ü)#00000000",
+        ] {
+            assert_eq!(verify(invalid), Err(Error::Descriptor), "{invalid}");
+        }
+    }
+}
@@ -0,0 +1,347 @@
+//! Key expressions as specified in BIP380.
+
+use crate::{
+    ChildNumber, DerivationPath, Error, ExtendedKey, ExtendedKeyAttrs, ExtendedPublicKey, KEY_SIZE,
+    KeyFingerprint, Prefix, PublicKey, PublicKeyBytes, Result,
+};
+use alloc::vec::Vec;
+use core::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+use subtle_encoding::hex;
+
+/// SEC1 tag for uncompressed public keys.
+const UNCOMPRESSED_TAG: u8 = 0x04;
+
+/// Size of a compressed SEC1 public key.
+const KEY_SIZE_COMPRESSED: usize = KEY_SIZE + 1;
+
+/// Size of an uncompressed SEC1 public key.
+const KEY_SIZE_UNCOMPRESSED: usize = 2 * KEY_SIZE + 1;
+
+/// Key origin information: the fingerprint of the master key and the
+/// derivation path from it to the key, i.e. `[d34db33f/44'/0'/0']`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeyOrigin {
+    /// Fingerprint of the master key.
+    pub fingerprint: KeyFingerprint,
+
+    /// Derivation path from the master key.
+    pub path: DerivationPath,
+}
+
+impl Display for KeyOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+
+        for byte in self.fingerprint {
+            write!(f, "{:02x}", byte)?;
+        }
+
+        for child_number in self.path.iter() {
+            write!(f, "/{}", child_number)?;
+        }
+
+        f.write_str("]")
+    }
+}
+
+impl FromStr for KeyOrigin {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s
+            .strip_prefix('[')
+            .and_then(|s| s.strip_suffix(']'))
+            .ok_or(Error::Descriptor)?;
+
+        let mut parts = s.split('/');
+        let fingerprint = parts.next().ok_or(Error::Descriptor)?;
+
+        if fingerprint.len() != 8 {
+            return Err(Error::Descriptor);
+        }
+
+        let fingerprint = hex::decode(fingerprint)
+            .map_err(|_| Error::Descriptor)?
+            .as_slice()
+            .try_into()?;
+
+        let mut path = DerivationPath::default();
+        for child_number in parts {
+            path.push(child_number.parse()?);
+        }
+
+        Ok(KeyOrigin { fingerprint, path })
+    }
+}
+
+/// Wildcard at the end of a ranged key expression.
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Wildcard {
+    /// Not a ranged key.
+    #[default]
+    None,
+
+    /// Unhardened wildcard: `/*`
+    Unhardened,
+
+    /// Hardened wildcard: `/*'`
+    Hardened,
+}
+
+/// Public key expression in an output descriptor, e.g.
+/// `[d34db33f/44'/0'/0']xpub.../0/*`.
+///
+/// Keys are either hex-encoded public keys or extended public keys
+/// followed by a derivation path. Extended private keys are not supported.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DescriptorPublicKey {
+    /// Key origin information.
+    origin: Option<KeyOrigin>,
+
+    /// Key value.
+    key: Key,
+}
+
+/// Key value of a [`DescriptorPublicKey`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Key {
+    /// Hex-encoded public key: compressed, uncompressed, or x-only.
+    Single(Vec<u8>),
+
+    /// Extended public key, followed by a path and optional wildcard.
+    Extended {
+        xpub: ExtendedPublicKeyData,
+        path: DerivationPath,
+        wildcard: Wildcard,
+    },
+}
+
+/// Serialized extended public key.
+///
+/// This is the public subset of [`ExtendedKey`], which is kept separately
+/// so we can compare keys and don't need to pick a [`PublicKey`] type when
+/// parsing descriptors.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct ExtendedPublicKeyData {
+    prefix: Prefix,
+    attrs: ExtendedKeyAttrs,
+    key_bytes: PublicKeyBytes,
+}
+
+impl DescriptorPublicKey {
+    /// Get the key origin information, if any.
+    pub fn origin(&self) -> Option<&KeyOrigin> {
+        self.origin.as_ref()
+    }
+
+    /// Get the wildcard at the end of this key expression, if any.
+    pub fn wildcard(&self) -> Wildcard {
+        match &self.key {
+            Key::Single(_) => Wildcard::None,
+            Key::Extended { wildcard, .. } => *wildcard,
+        }
+    }
+
+    /// Is this a ranged key, i.e. does it end with a wildcard?
+    pub fn is_ranged(&self) -> bool {
+        self.wildcard() != Wildcard::None
+    }
+
+    /// Is this an x-only public key (only valid in `tr()`)?
+    pub fn is_x_only(&self) -> bool {
+        matches!(&self.key, Key::Single(bytes) if bytes.len() == KEY_SIZE)
+    }
+
+    /// Is this an uncompressed public key (not valid in SegWit contexts)?
+    pub fn is_uncompressed(&self) -> bool {
+        matches!(&self.key, Key::Single(bytes) if bytes.len() == KEY_SIZE_UNCOMPRESSED)
+    }
+
+    /// Derive the public key at the given index.
+    ///
+    /// The index is ignored unless this is a ranged key. X-only keys are
+    /// returned with an even Y coordinate, as specified in BIP340.
+    ///
+    /// Uncompressed keys and keys which are derived along hardened paths
+    /// can't be derived, and return an error.
+    pub fn derive<K: PublicKey + Clone>(&self, index: u32) -> Result<K> {
+        self.resolve()?.derive(index)
+    }
+
+    /// Resolve the fixed part of this key expression, i.e. everything up to
+    /// the wildcard, so ranged keys can be derived efficiently.
+    pub(super) fn resolve<K: PublicKey + Clone>(&self) -> Result<ResolvedKey<K>> {
+        match &self.key {
+            Key::Single(bytes) => {
+                let mut key_bytes = [0u8; KEY_SIZE_COMPRESSED];
+
+                match bytes.len() {
+                    KEY_SIZE => {
+                        key_bytes[0] = 0x02;
+                        key_bytes[1..].copy_from_slice(bytes);
+                    }
+                    KEY_SIZE_UNCOMPRESSED => return Err(Error::Decode),
+                    _ => key_bytes.copy_from_slice(bytes),
+                }
+
+                K::from_bytes(key_bytes).map(ResolvedKey::Single)
+            }
+            Key::Extended {
+                xpub,
+                path,
+                wildcard,
+            } => {
+                let mut xpub =
+                    ExtendedPublicKey::new(K::from_bytes(xpub.key_bytes)?, xpub.attrs.clone());
+
+                for child_number in path.iter() {
+                    xpub = xpub.derive_child(child_number)?;
+                }
+
+                match wildcard {
+                    Wildcard::None => Ok(ResolvedKey::Single(xpub.public_key().clone())),
+                    Wildcard::Unhardened => {
+                        let fingerprint = xpub.fingerprint();
+                        Ok(ResolvedKey::Ranged(xpub, fingerprint))
+                    }
+                    Wildcard::Hardened => Err(Error::ChildNumber),
+                }
+            }
+        }
+    }
+}
+
+impl Display for DescriptorPublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(origin) = &self.origin {
+            write!(f, "{}", origin)?;
+        }
+
+        match &self.key {
+            Key::Single(bytes) => {
+                for byte in bytes {
+                    write!(f, "{:02x}", byte)?;
+                }
+            }
+            Key::Extended {
+                xpub,
+                path,
+                wildcard,
+            } => {
+                let xkey = ExtendedKey {
+                    prefix: xpub.prefix,
+                    attrs: xpub.attrs.clone(),
+                    key_bytes: xpub.key_bytes,
+                };
+
+                write!(f, "{}", xkey)?;
+
+                for child_number in path.iter() {
+                    write!(f, "/{}", child_number)?;
+                }
+
+                match wildcard {
+                    Wildcard::None => (),
+                    Wildcard::Unhardened => f.write_str("/*")?,
+                    Wildcard::Hardened => f.write_str("/*'")?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for DescriptorPublicKey {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (origin, key) = match s.find(']') {
+            Some(pos) => (Some(s[..=pos].parse()?), &s[(pos + 1)..]),
+            None => (None, s),
+        };
+
+        let key = if key.bytes().all(|b| b.is_ascii_hexdigit()) {
+            let bytes = hex::decode(key).map_err(|_| Error::Descriptor)?;
+
+            let valid = match bytes.len() {
+                KEY_SIZE => true,
+                KEY_SIZE_UNCOMPRESSED => bytes[0] == UNCOMPRESSED_TAG,
+                KEY_SIZE_COMPRESSED => bytes[0] == 0x02 || bytes[0] == 0x03,
+                _ => false,
+            };
+
+            if !valid {
+                return Err(Error::Descriptor);
+            }
+
+            Key::Single(bytes)
+        } else {
+            let mut parts = key.split('/');
+            let xkey = parts
+                .next()
+                .ok_or(Error::Descriptor)?
+                .parse::<ExtendedKey>()?;
+
+            if !xkey.prefix.is_public() {
+                return Err(Error::Descriptor);
+            }
+
+            let mut path = DerivationPath::default();
+            let mut wildcard = Wildcard::None;
+
+            for part in parts {
+                if wildcard != Wildcard::None {
+                    // Wildcards are only allowed at the end of the path
+                    return Err(Error::Descriptor);
+                }
+
+                wildcard = match part {
+                    "*" => Wildcard::Unhardened,
+                    "*'" | "*h" => Wildcard::Hardened,
+                    _ => {
+                        path.push(part.parse::<ChildNumber>()?);
+                        continue;
+                    }
+                };
+            }
+
+            Key::Extended {
+                xpub: ExtendedPublicKeyData {
+                    prefix: xkey.prefix,
+                    attrs: xkey.attrs.clone(),
+                    key_bytes: xkey.key_bytes,
+                },
+                path,
+                wildcard,
+            }
+        };
+
+        Ok(DescriptorPublicKey { origin, key })
+    }
+}
+
+/// Key expression with its fixed derivation path applied.
+pub(super) enum ResolvedKey<K: PublicKey> {
+    /// Key which is the same at every index.
+    Single(K),
+
+    /// Parent of a ranged key, along with its fingerprint.
+    Ranged(ExtendedPublicKey<K>, KeyFingerprint),
+}
+
+impl<K: PublicKey + Clone> ResolvedKey<K> {
+    /// Derive the public key at the given index.
+    pub(super) fn derive(&self, index: u32) -> Result<K> {
+        match self {
+            ResolvedKey::Single(key) => Ok(key.clone()),
+            ResolvedKey::Ranged(xpub, fingerprint) => Ok(xpub
+                .derive_child_with_fingerprint(ChildNumber::new(index, false)?, *fingerprint)?
+                .public_key()
+                .clone()),
+        }
+    }
+}
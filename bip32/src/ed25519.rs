@@ -0,0 +1,207 @@
+//! Ed25519 keys derived using SLIP-10.
+//!
+//! SLIP-10 only supports hardened derivation of Ed25519 keys: child public
+//! keys cannot be derived from a parent public key.
+
+use crate::{
+    ChainCode, ChildNumber, Error, KEY_SIZE, PrivateKey, PrivateKeyBytes, PublicKey,
+    PublicKeyBytes, Result, private_key::hmac_split,
+};
+use core::fmt::{self, Debug};
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use sha2::{Digest, Sha512};
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Extended Ed25519 signing key.
+pub type ExtendedSigningKey = crate::ExtendedPrivateKey<SigningKey>;
+
+/// Ed25519 signing key, i.e. the 32-byte "seed" from which the signing
+/// scalar is computed as described in RFC 8032.
+///
+/// The bytes of this key are compatible with e.g. `ed25519-dalek`.
+#[derive(Clone)]
+pub struct SigningKey {
+    bytes: PrivateKeyBytes,
+}
+
+impl SigningKey {
+    /// Initialize a signing key from bytes.
+    pub fn from_bytes(bytes: &PrivateKeyBytes) -> Self {
+        Self { bytes: *bytes }
+    }
+
+    /// Serialize this key as bytes.
+    pub fn to_bytes(&self) -> PrivateKeyBytes {
+        self.bytes
+    }
+
+    /// Get the [`VerifyingKey`] which corresponds to this signing key.
+    pub fn verifying_key(&self) -> VerifyingKey {
+        let mut hash = Sha512::digest(self.bytes);
+        let mut scalar_bytes = [0u8; KEY_SIZE];
+        scalar_bytes.copy_from_slice(&hash[..KEY_SIZE]);
+
+        let point = EdwardsPoint::mul_base_clamped(scalar_bytes);
+        hash.zeroize();
+        scalar_bytes.zeroize();
+
+        VerifyingKey {
+            bytes: point.compress().to_bytes(),
+        }
+    }
+}
+
+impl ConstantTimeEq for SigningKey {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.bytes.ct_eq(&other.bytes)
+    }
+}
+
+impl Debug for SigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SigningKey").finish_non_exhaustive()
+    }
+}
+
+impl Drop for SigningKey {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SigningKey {}
+
+impl PrivateKey for SigningKey {
+    type PublicKey = VerifyingKey;
+
+    const SEED_KEY: &'static [u8] = b"ed25519 seed";
+
+    fn from_bytes(bytes: &PrivateKeyBytes) -> Result<Self> {
+        Ok(SigningKey::from_bytes(bytes))
+    }
+
+    fn to_bytes(&self) -> PrivateKeyBytes {
+        SigningKey::to_bytes(self)
+    }
+
+    /// SLIP-10 Ed25519 child keys are "I sub L" itself: every 32-byte value
+    /// is a valid signing key.
+    fn derive_child(&self, other: PrivateKeyBytes) -> Result<Self> {
+        Ok(SigningKey::from_bytes(&other))
+    }
+
+    fn public_key(&self) -> Self::PublicKey {
+        self.verifying_key()
+    }
+
+    /// Only hardened child numbers are supported for Ed25519.
+    fn derive_tweak(
+        &self,
+        chain_code: &ChainCode,
+        child_number: ChildNumber,
+    ) -> Result<(PrivateKeyBytes, ChainCode)> {
+        if !child_number.is_hardened() {
+            return Err(Error::ChildNumber);
+        }
+
+        hmac_split(chain_code, &[&[0], &self.bytes, &child_number.to_bytes()])
+    }
+}
+
+/// Ed25519 verifying key, i.e. a compressed Edwards point.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub struct VerifyingKey {
+    bytes: [u8; KEY_SIZE],
+}
+
+impl VerifyingKey {
+    /// Initialize a verifying key from its 32-byte compressed encoding.
+    pub fn from_bytes(bytes: &[u8; KEY_SIZE]) -> Result<Self> {
+        CompressedEdwardsY(*bytes)
+            .decompress()
+            .map(|_| Self { bytes: *bytes })
+            .ok_or(Error::Crypto)
+    }
+
+    /// Borrow the 32-byte compressed encoding of this key.
+    pub fn as_bytes(&self) -> &[u8; KEY_SIZE] {
+        &self.bytes
+    }
+}
+
+/// Serialized with a leading `0` byte as specified in SLIP-10, which is
+/// also used when computing key fingerprints.
+impl PublicKey for VerifyingKey {
+    fn from_bytes(bytes: PublicKeyBytes) -> Result<Self> {
+        let (&[0], key) = bytes.split_at(1) else {
+            return Err(Error::Decode);
+        };
+
+        VerifyingKey::from_bytes(key.try_into()?)
+    }
+
+    fn to_bytes(&self) -> PublicKeyBytes {
+        let mut bytes = [0u8; KEY_SIZE + 1];
+        bytes[1..].copy_from_slice(&self.bytes);
+        bytes
+    }
+
+    /// Ed25519 public keys cannot be derived from a parent public key.
+    fn derive_child(&self, _other: PrivateKeyBytes) -> Result<Self> {
+        Err(Error::ChildNumber)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::{ExtendedSigningKey, SigningKey, VerifyingKey};
+    use crate::{ChildNumber, Error, PublicKey};
+    use hex_literal::hex;
+
+    /// RFC 8032 test 1
+    #[test]
+    fn rfc8032_public_key() {
+        let signing_key = SigningKey::from_bytes(&hex!(
+            "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60"
+        ));
+
+        assert_eq!(
+            signing_key.verifying_key().as_bytes(),
+            &hex!("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a")
+        );
+    }
+
+    #[test]
+    fn public_key_encoding() {
+        let verifying_key = SigningKey::from_bytes(&[1; 32]).verifying_key();
+        let bytes = PublicKey::to_bytes(&verifying_key);
+        assert_eq!(bytes[0], 0);
+        assert_eq!(
+            <VerifyingKey as PublicKey>::from_bytes(bytes).unwrap(),
+            verifying_key
+        );
+
+        let mut bytes = bytes;
+        bytes[0] = 2;
+        assert_eq!(
+            <VerifyingKey as PublicKey>::from_bytes(bytes),
+            Err(Error::Decode)
+        );
+    }
+
+    #[test]
+    fn non_hardened_derivation() {
+        let xprv = ExtendedSigningKey::new([0u8; 32]).unwrap();
+        assert_eq!(
+            xprv.derive_child(ChildNumber::new(0, false).unwrap()),
+            Err(Error::ChildNumber)
+        );
+        assert_eq!(
+            xprv.public_key()
+                .derive_child(ChildNumber::new(0, false).unwrap()),
+            Err(Error::ChildNumber)
+        );
+    }
+}
@@ -1,5 +1,6 @@
 //! Error type.
 
+use crate::Version;
 use core::fmt::{self, Display};
 
 /// Result type.
@@ -15,6 +16,12 @@ pub enum Error {
     /// BIP39-related errors.
     Bip39,
 
+    /// Derived child key is invalid (the next child number can be used
+    /// instead, see e.g. [`ExtendedPrivateKey::derive_child_skip_invalid`]).
+    ///
+    /// [`ExtendedPrivateKey::derive_child_skip_invalid`]: crate::ExtendedPrivateKey::derive_child_skip_invalid
+    ChildKey,
+
     /// Child number-related errors.
     ChildNumber,
 
@@ -27,8 +34,61 @@ pub enum Error {
     /// Maximum derivation depth exceeded.
     Depth,
 
+    /// Output descriptor-related errors.
+    Descriptor,
+
+    /// Electrum seed phrase-related errors.
+    Electrum,
+
+    /// Bech32 human-readable part is invalid.
+    Hrp,
+
+    /// Mnemonic phrase checksum is invalid.
+    MnemonicChecksum,
+
+    /// Parent fingerprint of a root key (i.e. with depth 0) isn't zero.
+    ParentFingerprint,
+
+    /// Maximum derivation depth exceeded when deriving the child number at
+    /// the given index of a derivation path.
+    PathDepth {
+        /// Index of the child number in the derivation path.
+        index: usize,
+    },
+
+    /// Child number at the given index of a derivation path is invalid.
+    PathSegment {
+        /// Index of the child number in the derivation path.
+        index: usize,
+    },
+
+    /// Extended key version number is already in use by a different prefix.
+    PrefixConflict,
+
+    /// Private key is invalid, i.e. out of range for the curve or not
+    /// prefixed with a zero byte.
+    PrivateKey,
+
+    /// Partially signed Bitcoin transaction-related errors.
+    Psbt,
+
+    /// Public key is invalid, i.e. incorrectly encoded or not on the curve.
+    PublicKey,
+
     /// Seed length invalid.
     SeedLength,
+
+    /// SLIP-39 share-related errors.
+    Slip39,
+
+    /// Mnemonic phrase word at the given index isn't in the wordlist.
+    UnknownWord {
+        /// Index of the word in the phrase.
+        index: usize,
+    },
+
+    /// Extended key version is unknown, or is for the wrong type of key.
+    Version(Version),
 }
 
 impl Display for Error {
@@ -36,11 +96,31 @@ impl Display for Error {
         match self {
             Error::Base58 => f.write_str("base58 error"),
             Error::Bip39 => f.write_str("bip39 error"),
+            Error::ChildKey => f.write_str("invalid child key"),
             Error::ChildNumber => f.write_str("invalid child number"),
             Error::Crypto => f.write_str("cryptographic error"),
             Error::Decode => f.write_str("decoding error"),
             Error::Depth => f.write_str("maximum derivation depth exceeded"),
+            Error::Descriptor => f.write_str("invalid output descriptor"),
+            Error::Electrum => f.write_str("invalid electrum seed phrase"),
+            Error::Hrp => f.write_str("invalid bech32 human-readable part"),
+            Error::MnemonicChecksum => f.write_str("invalid mnemonic checksum"),
+            Error::ParentFingerprint => f.write_str("non-zero parent fingerprint for root key"),
+            Error::PathDepth { index } => write!(
+                f,
+                "maximum derivation depth exceeded at derivation path index {index}"
+            ),
+            Error::PathSegment { index } => {
+                write!(f, "invalid child number at derivation path index {index}")
+            }
+            Error::PrefixConflict => f.write_str("extended key version already in use"),
+            Error::PrivateKey => f.write_str("invalid private key"),
+            Error::Psbt => f.write_str("invalid PSBT"),
+            Error::PublicKey => f.write_str("invalid public key"),
             Error::SeedLength => f.write_str("seed length invalid"),
+            Error::Slip39 => f.write_str("slip39 error"),
+            Error::UnknownWord { index } => write!(f, "unknown mnemonic word at index {index}"),
+            Error::Version(version) => write!(f, "invalid extended key version: {version:#010x}"),
         }
     }
 }
@@ -86,6 +166,22 @@ impl From<k256::ecdsa::Error> for Error {
     }
 }
 
+// NOTE: `k256` and `p256` share these error types, so they only need to be
+// converted when `secp256k1` is disabled.
+#[cfg(all(feature = "nistp256", not(feature = "secp256k1")))]
+impl From<p256::elliptic_curve::Error> for Error {
+    fn from(_: p256::elliptic_curve::Error) -> Error {
+        Error::Crypto
+    }
+}
+
+#[cfg(all(feature = "nistp256", not(feature = "secp256k1")))]
+impl From<p256::ecdsa::Error> for Error {
+    fn from(_: p256::ecdsa::Error) -> Error {
+        Error::Crypto
+    }
+}
+
 #[cfg(feature = "secp256k1-ffi")]
 impl From<secp256k1_ffi::Error> for Error {
     fn from(_: secp256k1_ffi::Error) -> Error {
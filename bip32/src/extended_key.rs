@@ -3,8 +3,11 @@
 pub(crate) mod attrs;
 pub(crate) mod private_key;
 pub(crate) mod public_key;
+pub(crate) mod scan;
 
-use crate::{ChildNumber, Error, ExtendedKeyAttrs, Prefix, Result, Version, KEY_SIZE};
+use crate::{
+    ChildNumber, Error, ExtendedKeyAttrs, KeyFingerprint, Prefix, Result, Version, KEY_SIZE,
+};
 use core::{
     fmt::{self, Display},
     str::{self, FromStr},
@@ -79,16 +82,31 @@ impl FromStr for ExtendedKey {
         let prefix = base58.get(..4).ok_or(Error::Decode).and_then(|chars| {
             Prefix::validate_str(chars)?;
             let version = Version::from_be_bytes(bytes[..4].try_into()?);
-            Ok(Prefix::from_parts_unchecked(chars, version))
+
+            match Prefix::lookup(version) {
+                Some((prefix, _)) => Ok(prefix),
+                None => Ok(Prefix::from_parts_unchecked(chars, version)),
+            }
         })?;
 
         let depth = bytes[4];
-        let parent_fingerprint = bytes[5..9].try_into()?;
+        let parent_fingerprint: KeyFingerprint = bytes[5..9].try_into()?;
         let child_number = ChildNumber::from_bytes(bytes[9..13].try_into()?);
         let chain_code = bytes[13..45].try_into()?;
         let key_bytes = bytes[45..78].try_into()?;
         bytes.zeroize();
 
+        // Root keys have no parent
+        if depth == 0 {
+            if parent_fingerprint != [0; 4] {
+                return Err(Error::ParentFingerprint);
+            }
+
+            if child_number != ChildNumber::default() {
+                return Err(Error::ChildNumber);
+            }
+        }
+
         let attrs = ExtendedKeyAttrs {
             depth,
             parent_fingerprint,
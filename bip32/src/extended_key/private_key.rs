@@ -1,14 +1,14 @@
 //! Extended private keys
 
 use crate::{
-    ChildNumber, Depth, Error, ExtendedKey, ExtendedKeyAttrs, ExtendedPublicKey, HmacSha512,
-    KeyFingerprint, Prefix, PrivateKey, PrivateKeyBytes, PublicKey, Result, KEY_SIZE,
+    ChildNumber, Depth, Error, ExtendedKey, ExtendedKeyAttrs, ExtendedPublicKey, KEY_SIZE,
+    KeyFingerprint, Prefix, PrivateKey, PrivateKeyBytes, PublicKey, Result,
+    private_key::hmac_split,
 };
 use core::{
     fmt::{self, Debug},
     str::FromStr,
 };
-use hmac::{KeyInit, Mac};
 use subtle::{Choice, ConstantTimeEq};
 use zeroize::Zeroize;
 
@@ -19,10 +19,8 @@ use {
     zeroize::Zeroizing,
 };
 
-/// Derivation domain separator for BIP39 keys.
-const BIP39_DOMAIN_SEPARATOR: [u8; 12] = [
-    0x42, 0x69, 0x74, 0x63, 0x6f, 0x69, 0x6e, 0x20, 0x73, 0x65, 0x65, 0x64,
-];
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize, de, ser};
 
 /// Extended private secp256k1 ECDSA signing key.
 #[cfg(feature = "secp256k1")]
@@ -55,9 +53,14 @@ where
     where
         S: AsRef<[u8]>,
     {
-        path.iter().fold(Self::new(seed), |maybe_key, child_num| {
-            maybe_key.and_then(|key| key.derive_child(child_num))
-        })
+        path.iter()
+            .enumerate()
+            .fold(Self::new(seed), |maybe_key, (index, child_num)| {
+                maybe_key.and_then(|key| match key.derive_child(child_num) {
+                    Err(Error::Depth) => Err(Error::PathDepth { index }),
+                    result => result,
+                })
+            })
     }
 
     /// Create the root extended key for the given seed value.
@@ -69,17 +72,25 @@ where
             return Err(Error::SeedLength);
         }
 
-        let mut hmac = HmacSha512::new_from_slice(&BIP39_DOMAIN_SEPARATOR)?;
-        hmac.update(seed.as_ref());
+        let (mut secret_key, mut chain_code) = hmac_split(K::SEED_KEY, &[seed.as_ref()])?;
+
+        // SLIP-10: if the master key is invalid, derive it again from the
+        // previous HMAC output until it is valid.
+        let private_key = loop {
+            match K::from_bytes(&secret_key) {
+                Err(Error::Crypto) if K::RETRY_INVALID_KEYS => {
+                    (secret_key, chain_code) =
+                        hmac_split(K::SEED_KEY, &[&secret_key, &chain_code])?;
+                }
+                result => break result?,
+            }
+        };
 
-        let result = hmac.finalize().into_bytes();
-        let (secret_key, chain_code) = result.split_at(KEY_SIZE);
-        let private_key = PrivateKey::from_bytes(secret_key.try_into()?)?;
         let attrs = ExtendedKeyAttrs {
             depth: 0,
             parent_fingerprint: KeyFingerprint::default(),
             child_number: ChildNumber::default(),
-            chain_code: chain_code.try_into()?,
+            chain_code,
         };
 
         Ok(ExtendedPrivateKey { private_key, attrs })
@@ -88,20 +99,34 @@ where
     /// Derive a child key for a particular [`ChildNumber`].
     pub fn derive_child(&self, child_number: ChildNumber) -> Result<Self> {
         let depth = self.attrs.depth.checked_add(1).ok_or(Error::Depth)?;
-        let (tweak, chain_code) = self
+        let (mut tweak, mut chain_code) = self
             .private_key
             .derive_tweak(&self.attrs.chain_code, child_number)?;
 
-        // We should technically loop here if the tweak is zero or overflows
-        // the order of the underlying elliptic curve group, incrementing the
+        // If the tweak is zero or overflows the order of the underlying
+        // elliptic curve group, BIP32 specifies proceeding with the next
         // index, however per "Child key derivation (CKD) functions":
         // https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki#child-key-derivation-ckd-functions
         //
         // > "Note: this has probability lower than 1 in 2^127."
         //
-        // ...so instead, we simply return an error if this were ever to happen,
-        // as the chances of it happening are vanishingly small.
-        let private_key = self.private_key.derive_child(tweak)?;
+        // ...so instead, we return `Error::ChildKey` if this were ever to
+        // happen, and leave skipping to `derive_child_skip_invalid`.
+        //
+        // SLIP-10 keys instead retry with a tweak derived from the invalid one
+        // (which is much more likely to be needed for e.g. NIST P-256).
+        let private_key = loop {
+            match self.private_key.derive_child(tweak) {
+                Err(Error::Crypto) if K::RETRY_INVALID_KEYS => {
+                    (tweak, chain_code) = hmac_split(
+                        &self.attrs.chain_code,
+                        &[&[1], &chain_code, &child_number.to_bytes()],
+                    )?;
+                }
+                Err(Error::Crypto) => return Err(Error::ChildKey),
+                result => break result?,
+            }
+        };
 
         let attrs = ExtendedKeyAttrs {
             parent_fingerprint: self.private_key.public_key().fingerprint(),
@@ -113,6 +138,21 @@ where
         Ok(ExtendedPrivateKey { private_key, attrs })
     }
 
+    /// Derive a child key for a particular [`ChildNumber`], proceeding with
+    /// the next child number if the derived key is invalid, as specified in
+    /// BIP32.
+    ///
+    /// The child number which was used can be obtained from the
+    /// [`ExtendedKeyAttrs`] of the returned key.
+    pub fn derive_child_skip_invalid(&self, mut child_number: ChildNumber) -> Result<Self> {
+        loop {
+            match self.derive_child(child_number) {
+                Err(Error::ChildKey) => child_number = child_number.next()?,
+                result => return result,
+            }
+        }
+    }
+
     /// Borrow the derived private key value.
     pub fn private_key(&self) -> &K {
         &self.private_key
@@ -160,7 +200,7 @@ where
 {
     fn ct_eq(&self, other: &Self) -> Choice {
         let mut key_a = self.to_bytes();
-        let mut key_b = self.to_bytes();
+        let mut key_b = other.to_bytes();
 
         let result = key_a.ct_eq(&key_b)
             & self.attrs.depth.ct_eq(&other.attrs.depth)
@@ -222,13 +262,171 @@ where
     type Error = Error;
 
     fn try_from(extended_key: ExtendedKey) -> Result<ExtendedPrivateKey<K>> {
-        if extended_key.prefix.is_private() && extended_key.key_bytes[0] == 0 {
-            Ok(ExtendedPrivateKey {
-                private_key: PrivateKey::from_bytes(extended_key.key_bytes[1..].try_into()?)?,
-                attrs: extended_key.attrs.clone(),
-            })
-        } else {
-            Err(Error::Crypto)
+        if !extended_key.prefix.is_private() {
+            return Err(Error::Version(extended_key.prefix.version()));
+        }
+
+        if extended_key.key_bytes[0] != 0 {
+            return Err(Error::PrivateKey);
+        }
+
+        let private_key = PrivateKey::from_bytes(extended_key.key_bytes[1..].try_into()?)
+            .map_err(|_| Error::PrivateKey)?;
+
+        Ok(ExtendedPrivateKey {
+            private_key,
+            attrs: extended_key.attrs.clone(),
+        })
+    }
+}
+
+/// Wrapper which opts into (de)serializing an [`ExtendedPrivateKey`] with
+/// `serde`, as a Base58 string with the `xprv` prefix.
+///
+/// [`ExtendedPrivateKey`] deliberately doesn't impl `Serialize` itself, to
+/// prevent private keys from accidentally being written out along with the
+/// rest of a wallet configuration.
+#[cfg(feature = "serde")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SerializableExtendedPrivateKey<K: PrivateKey>(pub ExtendedPrivateKey<K>);
+
+#[cfg(feature = "serde")]
+impl<K> From<ExtendedPrivateKey<K>> for SerializableExtendedPrivateKey<K>
+where
+    K: PrivateKey,
+{
+    fn from(xprv: ExtendedPrivateKey<K>) -> SerializableExtendedPrivateKey<K> {
+        SerializableExtendedPrivateKey(xprv)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<K> Serialize for SerializableExtendedPrivateKey<K>
+where
+    K: PrivateKey,
+{
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.collect_str(&self.0.to_extended_key(Prefix::XPRV))
+    }
+}
+
+/// Keys with any prefix other than `xprv` (e.g. `tprv` or `zprv`) are
+/// rejected when deserializing, since they would be serialized with a
+/// different prefix.
+#[cfg(feature = "serde")]
+impl<'de, K> Deserialize<'de> for SerializableExtendedPrivateKey<K>
+where
+    K: PrivateKey,
+{
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let xprv = Zeroizing::new(String::deserialize(deserializer)?);
+        let extended_key = xprv.parse::<ExtendedKey>().map_err(de::Error::custom)?;
+
+        if extended_key.prefix != Prefix::XPRV {
+            return Err(de::Error::custom(Error::Version(
+                extended_key.prefix.version(),
+            )));
+        }
+
+        extended_key.try_into().map(Self).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ChildNumber, Error, ExtendedPrivateKey, PrivateKey, PrivateKeyBytes, PublicKey,
+        PublicKeyBytes, Result,
+    };
+
+    /// Toy private key for which derivation fails when the tweak has an odd
+    /// first byte, as if it overflowed the order of the curve.
+    #[derive(Clone)]
+    struct ToyPrivateKey(PrivateKeyBytes);
+
+    /// Public key for [`ToyPrivateKey`]: the private key with a zero prefix.
+    #[derive(Clone, Debug, PartialEq)]
+    struct ToyPublicKey(PublicKeyBytes);
+
+    impl PrivateKey for ToyPrivateKey {
+        type PublicKey = ToyPublicKey;
+
+        fn from_bytes(bytes: &PrivateKeyBytes) -> Result<Self> {
+            Ok(Self(*bytes))
+        }
+
+        fn to_bytes(&self) -> PrivateKeyBytes {
+            self.0
+        }
+
+        fn derive_child(&self, other: PrivateKeyBytes) -> Result<Self> {
+            match other[0] % 2 {
+                0 => Ok(Self(other)),
+                _ => Err(Error::Crypto),
+            }
+        }
+
+        fn public_key(&self) -> ToyPublicKey {
+            let mut bytes = [0; 33];
+            bytes[1..].copy_from_slice(&self.0);
+            ToyPublicKey(bytes)
         }
     }
+
+    impl PublicKey for ToyPublicKey {
+        fn from_bytes(bytes: PublicKeyBytes) -> Result<Self> {
+            Ok(Self(bytes))
+        }
+
+        fn to_bytes(&self) -> PublicKeyBytes {
+            self.0
+        }
+
+        fn derive_child(&self, other: PrivateKeyBytes) -> Result<Self> {
+            ToyPrivateKey(other)
+                .derive_child(other)
+                .map(|key| key.public_key())
+        }
+    }
+
+    #[test]
+    fn derive_child_skip_invalid() {
+        let xprv = ExtendedPrivateKey::<ToyPrivateKey>::new([1; 32]).unwrap();
+        let xpub = xprv.public_key();
+
+        let invalid = (0..)
+            .map(|index| ChildNumber::new(index, false).unwrap())
+            .find(|&child_number| xprv.derive_child(child_number).is_err())
+            .unwrap();
+
+        assert_eq!(xprv.derive_child(invalid).err(), Some(Error::ChildKey));
+        assert_eq!(xpub.derive_child(invalid).err(), Some(Error::ChildKey));
+
+        let child = xprv.derive_child_skip_invalid(invalid).unwrap();
+        let child_number = child.attrs().child_number;
+        assert!(child_number > invalid);
+        assert!(child == xprv.derive_child(child_number).unwrap());
+
+        // Public derivation skips to the same child
+        let child_xpub = xpub.derive_child_skip_invalid(invalid).unwrap();
+        assert_eq!(child_xpub, child.public_key());
+    }
+
+    #[test]
+    fn eq_compares_private_keys() {
+        let xprv = ExtendedPrivateKey::<ToyPrivateKey>::new([1; 32]).unwrap();
+        let other = ExtendedPrivateKey {
+            private_key: ToyPrivateKey([2; 32]),
+            attrs: xprv.attrs.clone(),
+        };
+
+        assert!(xprv == xprv.clone());
+        assert!(xprv != other);
+    }
 }
@@ -3,12 +3,17 @@
 use crate::{
     ChildNumber, Error, ExtendedKey, ExtendedKeyAttrs, ExtendedPrivateKey, KeyFingerprint, Prefix,
     PrivateKey, PublicKey, PublicKeyBytes, Result,
+    extended_key::scan::{DeriveRange, GapLimitScan},
+    private_key::hmac_split,
 };
-use core::str::FromStr;
+use core::{ops::Range, str::FromStr};
 
 #[cfg(feature = "alloc")]
 use alloc::string::{String, ToString};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize, de, ser};
+
 /// Extended public secp256k1 ECDSA verification key.
 #[cfg(feature = "secp256k1")]
 pub type XPub = ExtendedPublicKey<k256::ecdsa::VerifyingKey>;
@@ -54,24 +59,127 @@ where
 
     /// Derive a child key for a particular [`ChildNumber`].
     pub fn derive_child(&self, child_number: ChildNumber) -> Result<Self> {
+        self.derive_child_with_fingerprint(child_number, self.fingerprint())
+    }
+
+    /// Derive a child key for a particular [`ChildNumber`], proceeding with
+    /// the next child number if the derived key is invalid, as specified in
+    /// BIP32.
+    ///
+    /// The child number which was used can be obtained from the
+    /// [`ExtendedKeyAttrs`] of the returned key.
+    pub fn derive_child_skip_invalid(&self, mut child_number: ChildNumber) -> Result<Self> {
+        let fingerprint = self.fingerprint();
+
+        loop {
+            match self.derive_child_with_fingerprint(child_number, fingerprint) {
+                Err(Error::ChildKey) => child_number = child_number.next()?,
+                result => return result,
+            }
+        }
+    }
+
+    /// Derive the unhardened child keys for the given range of indices.
+    ///
+    /// The fingerprint of this key is computed once rather than for each
+    /// child, making this more efficient than calling [`Self::derive_child`]
+    /// in a loop. Indices which are out of range for unhardened keys (i.e.
+    /// 2<sup>31</sup> or more) produce an error.
+    pub fn derive_range(&self, indices: Range<u32>) -> DeriveRange<'_, K> {
+        DeriveRange::new(self, indices)
+    }
+
+    /// Derive the unhardened child keys of the given chain of this key, e.g.
+    /// `0` for receiving or `1` for change addresses of a BIP44 account, for
+    /// the given range of indices.
+    ///
+    /// The chain key is derived once and cached by the returned iterator, so
+    /// each index only requires deriving a single child.
+    pub fn derive_chain_range(
+        &self,
+        chain: u32,
+        indices: Range<u32>,
+    ) -> Result<DeriveRange<'_, K>> {
+        let chain = self.derive_child(ChildNumber::new(chain, false)?)?;
+        Ok(DeriveRange::new_chain(chain, indices))
+    }
+
+    /// Scan the unhardened child keys of this key in order, yielding the
+    /// ones for which `is_used` returns `true`, until `gap_limit` consecutive
+    /// unused keys are found.
+    ///
+    /// This is the account discovery procedure used to recover wallets,
+    /// where `is_used` checks if e.g. a key's address has any transactions.
+    /// BIP44 specifies a gap limit of 20.
+    pub fn gap_limit_scan<F>(&self, gap_limit: u32, is_used: F) -> GapLimitScan<'_, K, F>
+    where
+        F: FnMut(&Self) -> bool,
+    {
+        GapLimitScan::new(
+            self.derive_range(0..ChildNumber::HARDENED_FLAG),
+            gap_limit,
+            is_used,
+        )
+    }
+
+    /// Scan the unhardened child keys of the given chain of this key, as in
+    /// [`Self::gap_limit_scan`].
+    ///
+    /// The chain key is derived once and cached, see
+    /// [`Self::derive_chain_range`].
+    pub fn gap_limit_scan_chain<F>(
+        &self,
+        chain: u32,
+        gap_limit: u32,
+        is_used: F,
+    ) -> Result<GapLimitScan<'_, K, F>>
+    where
+        F: FnMut(&Self) -> bool,
+    {
+        Ok(GapLimitScan::new(
+            self.derive_chain_range(chain, 0..ChildNumber::HARDENED_FLAG)?,
+            gap_limit,
+            is_used,
+        ))
+    }
+
+    /// Derive a child key, using a precomputed fingerprint of this key.
+    pub(crate) fn derive_child_with_fingerprint(
+        &self,
+        child_number: ChildNumber,
+        parent_fingerprint: KeyFingerprint,
+    ) -> Result<Self> {
         let depth = self.attrs.depth.checked_add(1).ok_or(Error::Depth)?;
-        let (tweak, chain_code) = self
+        let (mut tweak, mut chain_code) = self
             .public_key
             .derive_tweak(&self.attrs.chain_code, child_number)?;
 
-        // We should technically loop here if the tweak is zero or overflows
-        // the order of the underlying elliptic curve group, incrementing the
+        // If the tweak is zero or overflows the order of the underlying
+        // elliptic curve group, BIP32 specifies proceeding with the next
         // index, however per "Child key derivation (CKD) functions":
         // https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki#child-key-derivation-ckd-functions
         //
         // > "Note: this has probability lower than 1 in 2^127."
         //
-        // ...so instead, we simply return an error if this were ever to happen,
-        // as the chances of it happening are vanishingly small.
-        let public_key = self.public_key.derive_child(tweak)?;
+        // ...so instead, we return `Error::ChildKey` if this were ever to
+        // happen, and leave skipping to `derive_child_skip_invalid`.
+        //
+        // SLIP-10 keys instead retry with a tweak derived from the invalid one.
+        let public_key = loop {
+            match self.public_key.derive_child(tweak) {
+                Err(Error::Crypto) if K::RETRY_INVALID_KEYS => {
+                    (tweak, chain_code) = hmac_split(
+                        &self.attrs.chain_code,
+                        &[&[1], &chain_code, &child_number.to_bytes()],
+                    )?;
+                }
+                Err(Error::Crypto) => return Err(Error::ChildKey),
+                result => break result?,
+            }
+        };
 
         let attrs = ExtendedKeyAttrs {
-            parent_fingerprint: self.public_key.fingerprint(),
+            parent_fingerprint,
             child_number,
             chain_code,
             depth,
@@ -124,6 +232,46 @@ where
     }
 }
 
+/// Serialized as a Base58 string with the `xpub` prefix.
+///
+/// Keys with any other prefix (e.g. `tpub` or `zpub`) are rejected when
+/// deserializing, since they would be serialized with a different prefix.
+#[cfg(feature = "serde")]
+impl<K> Serialize for ExtendedPublicKey<K>
+where
+    K: PublicKey,
+{
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.collect_str(&self.to_extended_key(Prefix::XPUB))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, K> Deserialize<'de> for ExtendedPublicKey<K>
+where
+    K: PublicKey,
+{
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let extended_key = String::deserialize(deserializer)?
+            .parse::<ExtendedKey>()
+            .map_err(de::Error::custom)?;
+
+        if extended_key.prefix != Prefix::XPUB {
+            return Err(de::Error::custom(Error::Version(
+                extended_key.prefix.version(),
+            )));
+        }
+
+        extended_key.try_into().map_err(de::Error::custom)
+    }
+}
+
 impl<K> TryFrom<ExtendedKey> for ExtendedPublicKey<K>
 where
     K: PublicKey,
@@ -134,7 +282,8 @@ where
     fn try_from(extended_key: ExtendedKey) -> Result<ExtendedPublicKey<K>> {
         if extended_key.prefix.is_public() {
             Ok(ExtendedPublicKey {
-                public_key: PublicKey::from_bytes(extended_key.key_bytes)?,
+                public_key: PublicKey::from_bytes(extended_key.key_bytes)
+                    .map_err(|_| Error::PublicKey)?,
                 attrs: extended_key.attrs.clone(),
             })
         } else if extended_key.prefix.is_private() {
@@ -149,7 +298,7 @@ where
                 attrs: private_key.attrs().clone(),
             })
         } else {
-            Err(Error::Crypto)
+            Err(Error::Version(extended_key.prefix.version()))
         }
     }
 
@@ -157,11 +306,12 @@ where
     fn try_from(extended_key: ExtendedKey) -> Result<ExtendedPublicKey<K>> {
         if extended_key.prefix.is_public() {
             Ok(ExtendedPublicKey {
-                public_key: PublicKey::from_bytes(extended_key.key_bytes)?,
+                public_key: PublicKey::from_bytes(extended_key.key_bytes)
+                    .map_err(|_| Error::PublicKey)?,
                 attrs: extended_key.attrs.clone(),
             })
         } else {
-            Err(Error::Crypto)
+            Err(Error::Version(extended_key.prefix.version()))
         }
     }
 }
@@ -175,9 +325,9 @@ mod tests {
     const CHAIN_CODE: [u8; 32] =
         hex!("873DFF81C02F525623FD1FE5167EAC3A55A049DE3D314BB42EE227FFED37D508");
     #[cfg(feature = "secp256k1")]
-    const XPUB_BASE58: &'static str = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhe\
+    const XPUB_BASE58: &str = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhe\
                                        PY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
-    const XPRV_BASE58: &'static str = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPP\
+    const XPRV_BASE58: &str = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPP\
                                        qjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
     const PUB_KEY_BYTES: PublicKeyBytes =
         hex!("0339A36013301597DAEF41FBE593A02CC513D0B55527EC2DF1050E2E8FF49C85C2");
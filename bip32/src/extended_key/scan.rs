@@ -0,0 +1,260 @@
+//! Iterators over the child keys of extended public keys.
+
+use crate::{ChildNumber, ExtendedPublicKey, KeyFingerprint, PublicKey, Result};
+use core::{iter::FusedIterator, ops::Range};
+
+/// Iterator over the unhardened child keys of an [`ExtendedPublicKey`] for a
+/// range of indices.
+///
+/// Created by [`ExtendedPublicKey::derive_range`] and
+/// [`ExtendedPublicKey::derive_chain_range`].
+#[derive(Clone, Debug)]
+pub struct DeriveRange<'a, K: PublicKey> {
+    /// Parent key.
+    parent: Parent<'a, K>,
+
+    /// Fingerprint of the parent key.
+    parent_fingerprint: KeyFingerprint,
+
+    /// Remaining indices.
+    indices: Range<u32>,
+}
+
+impl<'a, K> DeriveRange<'a, K>
+where
+    K: PublicKey,
+{
+    /// Create a new iterator over the children of the given key.
+    pub(crate) fn new(parent: &'a ExtendedPublicKey<K>, indices: Range<u32>) -> Self {
+        Self {
+            parent_fingerprint: parent.fingerprint(),
+            parent: Parent::Borrowed(parent),
+            indices,
+        }
+    }
+
+    /// Create a new iterator over the children of the given chain key, which
+    /// is cached for the lifetime of the iterator.
+    pub(crate) fn new_chain(chain: ExtendedPublicKey<K>, indices: Range<u32>) -> Self {
+        Self {
+            parent_fingerprint: chain.fingerprint(),
+            parent: Parent::Owned(chain),
+            indices,
+        }
+    }
+
+    /// Get the parent key of the keys in this range.
+    pub fn parent(&self) -> &ExtendedPublicKey<K> {
+        match &self.parent {
+            Parent::Borrowed(parent) => parent,
+            Parent::Owned(parent) => parent,
+        }
+    }
+
+    /// Derive the child key with the given index.
+    fn derive(&self, index: u32) -> Result<ExtendedPublicKey<K>> {
+        self.parent()
+            .derive_child_with_fingerprint(ChildNumber::new(index, false)?, self.parent_fingerprint)
+    }
+}
+
+impl<K> Iterator for DeriveRange<'_, K>
+where
+    K: PublicKey,
+{
+    type Item = Result<ExtendedPublicKey<K>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.indices.next().map(|index| self.derive(index))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indices.size_hint()
+    }
+}
+
+impl<K> DoubleEndedIterator for DeriveRange<'_, K>
+where
+    K: PublicKey,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.indices.next_back().map(|index| self.derive(index))
+    }
+}
+
+impl<K> ExactSizeIterator for DeriveRange<'_, K> where K: PublicKey {}
+
+impl<K> FusedIterator for DeriveRange<'_, K> where K: PublicKey {}
+
+/// Parent key of a [`DeriveRange`]: either the key it was created from, or a
+/// chain key derived from it.
+#[derive(Clone, Debug)]
+enum Parent<'a, K: PublicKey> {
+    /// Borrowed key.
+    Borrowed(&'a ExtendedPublicKey<K>),
+
+    /// Derived chain key.
+    Owned(ExtendedPublicKey<K>),
+}
+
+/// Iterator over the used child keys of an [`ExtendedPublicKey`], which
+/// stops after a run of unused keys as long as the gap limit.
+///
+/// Created by [`ExtendedPublicKey::gap_limit_scan`] and
+/// [`ExtendedPublicKey::gap_limit_scan_chain`].
+pub struct GapLimitScan<'a, K: PublicKey, F> {
+    /// Child keys which have yet to be scanned.
+    children: DeriveRange<'a, K>,
+
+    /// Maximum number of consecutive unused keys.
+    gap_limit: u32,
+
+    /// Number of consecutive unused keys found so far.
+    gap: u32,
+
+    /// Predicate which checks whether a key is used.
+    is_used: F,
+}
+
+impl<'a, K, F> GapLimitScan<'a, K, F>
+where
+    K: PublicKey,
+    F: FnMut(&ExtendedPublicKey<K>) -> bool,
+{
+    /// Create a new scan over the given child keys.
+    pub(crate) fn new(children: DeriveRange<'a, K>, gap_limit: u32, is_used: F) -> Self {
+        Self {
+            children,
+            gap_limit,
+            gap: 0,
+            is_used,
+        }
+    }
+
+    /// Get the number of consecutive unused keys found so far.
+    pub fn gap(&self) -> u32 {
+        self.gap
+    }
+}
+
+impl<K, F> Iterator for GapLimitScan<'_, K, F>
+where
+    K: PublicKey,
+    F: FnMut(&ExtendedPublicKey<K>) -> bool,
+{
+    type Item = Result<ExtendedPublicKey<K>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.gap < self.gap_limit {
+            let child = match self.children.next()? {
+                Ok(child) => child,
+                Err(e) => {
+                    // Stop scanning after an error
+                    self.gap = self.gap_limit;
+                    return Some(Err(e));
+                }
+            };
+
+            if (self.is_used)(&child) {
+                self.gap = 0;
+                return Some(Ok(child));
+            }
+
+            self.gap += 1;
+        }
+
+        None
+    }
+}
+
+impl<K, F> FusedIterator for GapLimitScan<'_, K, F>
+where
+    K: PublicKey,
+    F: FnMut(&ExtendedPublicKey<K>) -> bool,
+{
+}
+
+#[cfg(all(test, feature = "secp256k1"))]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crate::{ChildNumber, Error, XPub};
+
+    /// BIP32 test vector 1: chain m
+    const XPUB: &str = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
+
+    #[test]
+    fn derive_range() {
+        let xpub = XPUB.parse::<XPub>().unwrap();
+        let children = xpub.derive_range(5..10);
+        assert_eq!(children.len(), 5);
+
+        for (index, child) in (5..10).zip(children) {
+            assert_eq!(
+                child.unwrap(),
+                xpub.derive_child(ChildNumber(index)).unwrap()
+            );
+        }
+
+        let last = xpub.derive_range(0..10).next_back().unwrap().unwrap();
+        assert_eq!(last.attrs().child_number, ChildNumber(9));
+    }
+
+    #[test]
+    fn derive_range_hardened() {
+        let xpub = XPUB.parse::<XPub>().unwrap();
+        let mut children =
+            xpub.derive_range((ChildNumber::HARDENED_FLAG - 1)..(ChildNumber::HARDENED_FLAG + 1));
+
+        assert!(children.next().unwrap().is_ok());
+        assert_eq!(children.next().unwrap(), Err(Error::ChildNumber));
+        assert!(children.next().is_none());
+    }
+
+    #[test]
+    fn gap_limit_scan() {
+        let xpub = XPUB.parse::<XPub>().unwrap();
+        let used = [0, 3, 8, 14];
+
+        let found = xpub
+            .gap_limit_scan(5, |child| used.contains(&child.attrs().child_number.0))
+            .map(|child| child.unwrap().attrs().child_number.0);
+
+        // Index 14 is after a gap of 5 unused keys (9-13)
+        assert!(found.eq([0, 3, 8]));
+    }
+
+    #[test]
+    fn derive_chain_range() {
+        let xpub = XPUB.parse::<XPub>().unwrap();
+        let chain = xpub.derive_child(ChildNumber(1)).unwrap();
+        let children = xpub.derive_chain_range(1, 0..5).unwrap();
+        assert_eq!(children.parent(), &chain);
+
+        for (index, child) in (0..5).zip(children) {
+            assert_eq!(
+                child.unwrap(),
+                chain.derive_child(ChildNumber(index)).unwrap()
+            );
+        }
+
+        assert_eq!(
+            xpub.derive_chain_range(ChildNumber::HARDENED_FLAG, 0..5)
+                .err(),
+            Some(Error::ChildNumber)
+        );
+    }
+
+    #[test]
+    fn gap_limit_scan_chain() {
+        let xpub = XPUB.parse::<XPub>().unwrap();
+        let chain = xpub.derive_child(ChildNumber(0)).unwrap();
+        let used = [chain.derive_child(ChildNumber(2)).unwrap()];
+
+        let found = xpub
+            .gap_limit_scan_chain(0, 3, |child| used.contains(child))
+            .unwrap()
+            .map(|child| child.unwrap());
+
+        assert!(found.eq(used.clone()));
+    }
+}
@@ -8,6 +8,7 @@
     rust_2018_idioms,
     unused_qualifications
 )]
+#![cfg_attr(test, allow(clippy::unwrap_used))]
 
 //! ## Backends
 //! This crate provides a generic implementation of BIP32 which can be used
@@ -19,10 +20,42 @@
 //!   crate, with [`XPrv`] and [`XPub`] type aliases.
 //! - `secp256k1-ffi`: support for Bitcoin Core's [libsecp256k1 C library],
 //!   as wrapped by the [`secp256k1` Rust crate].
+//! - `nistp256`: [SLIP-10] derivation of NIST P-256 keys using the pure Rust
+//!   `p256` crate (re-exported as `nistp256`).
+//! - `ed25519`: [SLIP-10] derivation of Ed25519 keys (hardened only), using
+//!   the types in the `ed25519` module.
 //!
-//! ## Limitations and further work
-//! - Only 24-word BIP39 mnemonics are supported
-//! - BIP43, BIP44, BIP49, BIP84 not yet properly supported
+//! Electrum v2 seed phrases (standard and segwit) can be imported with
+//! [`ElectrumMnemonic`] under the `bip39` feature, yielding a [`Seed`] and
+//! the root derivation path of the wallet.
+//!
+//! Mnemonic phrases and BIP39 passwords can be retrieved and supplied as
+//! `secrecy::SecretString` with the `secrecy` feature.
+//!
+//! Child mnemonics, WIF keys, extended keys, and hex secrets can be derived
+//! from an [`XPrv`] as specified in BIP85 with the `bip85` feature.
+//!
+//! Master secrets can be split into Shamir's secret-sharing backups as
+//! specified in SLIP-39, and recovered from them, with the `slip39` feature
+//! (see the [`slip39`] module).
+//!
+//! Output descriptors (BIP380-386) can be parsed and derived with the
+//! `descriptor` feature.
+//!
+//! Taproot output keys (BIP341) and the corresponding key path signing keys
+//! can be computed from secp256k1 extended keys with the `taproot` feature
+//! (see the [`taproot`] module).
+//!
+//! Partially signed Bitcoin transactions (BIP174 and BIP370) can be parsed
+//! and signed with the `psbt` feature.
+//!
+//! Bitcoin, Cosmos, and Ethereum addresses can be computed from secp256k1
+//! extended public keys with the `address` feature (see the [`address`]
+//! module).
+//!
+//! Derivation paths, child numbers, prefixes, and extended public keys can be
+//! (de)serialized as strings with the `serde` feature. Extended private keys
+//! are only serializable when wrapped in [`SerializableExtendedPrivateKey`].
 //!
 //! # Usage
 //! The following is an end-to-end example of how to generate a random BIP39
@@ -45,11 +78,11 @@
 //! # fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! # #[cfg(all(feature = "bip39", feature = "secp256k1"))]
 //! # {
-//! use bip32::{Mnemonic, Prefix, XPrv};
+//! use bip32::{Mnemonic, Prefix, WordCount, XPrv};
 //! use rand_core::OsRng;
 //!
-//! // Generate random Mnemonic using the default language (English)
-//! let mnemonic = Mnemonic::random(&mut OsRng, Default::default());
+//! // Generate random 24-word Mnemonic using the default language (English)
+//! let mnemonic = Mnemonic::random(&mut OsRng, WordCount::Words24, Default::default());
 //!
 //! // Derive a BIP39 seed value using the given password
 //! let seed = mnemonic.to_seed("password");
@@ -92,6 +125,7 @@
 //! ```
 //!
 //! [bip32]: https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki
+//! [SLIP-10]: https://github.com/satoshilabs/slips/blob/master/slip-0010.md
 //! [libsecp256k1 C library]: https://github.com/bitcoin-core/secp256k1
 //! [`secp256k1` Rust crate]: https://github.com/rust-bitcoin/rust-secp256k1/
 
@@ -108,18 +142,40 @@ mod prefix;
 mod private_key;
 mod public_key;
 
+#[cfg(feature = "alloc")]
+mod account_path;
+#[cfg(any(feature = "address", feature = "descriptor"))]
+mod bech32;
 #[cfg(feature = "alloc")]
 mod derivation_path;
 
+#[cfg(feature = "bip85")]
+mod bip85;
+#[cfg(feature = "descriptor")]
+mod descriptor;
 #[cfg(feature = "mnemonic")]
 mod mnemonic;
+#[cfg(feature = "psbt")]
+mod psbt;
+
+#[cfg(feature = "address")]
+pub mod address;
+#[cfg(feature = "ed25519")]
+pub mod ed25519;
+#[cfg(feature = "slip39")]
+pub mod slip39;
+#[cfg(feature = "taproot")]
+pub mod taproot;
 
 pub use crate::{
     child_number::ChildNumber,
     error::{Error, Result},
     extended_key::{
-        attrs::ExtendedKeyAttrs, private_key::ExtendedPrivateKey, public_key::ExtendedPublicKey,
         ExtendedKey,
+        attrs::ExtendedKeyAttrs,
+        private_key::ExtendedPrivateKey,
+        public_key::ExtendedPublicKey,
+        scan::{DeriveRange, GapLimitScan},
     },
     prefix::Prefix,
     private_key::{PrivateKey, PrivateKeyBytes},
@@ -127,10 +183,23 @@ pub use crate::{
 };
 
 #[cfg(feature = "alloc")]
-pub use crate::derivation_path::DerivationPath;
+pub use crate::{
+    account_path::{Bip44Path, Bip49Path, Bip84Path, Bip86Path, Purpose},
+    derivation_path::DerivationPath,
+};
+
+#[cfg(feature = "descriptor")]
+pub use crate::descriptor::{
+    Descriptor, DescriptorAddress, DescriptorKeys, DescriptorPublicKey, KeyOrigin, Multi, Wildcard,
+};
+
+#[cfg(feature = "psbt")]
+pub use crate::psbt::Psbt;
 
 #[cfg(feature = "bip39")]
-pub use crate::mnemonic::{Language, Phrase as Mnemonic, Seed};
+pub use crate::mnemonic::{
+    ElectrumMnemonic, ElectrumSeedType, Language, Phrase as Mnemonic, Seed, WordCount,
+};
 
 #[cfg(feature = "secp256k1")]
 pub use {
@@ -138,6 +207,15 @@ pub use {
     k256 as secp256k1,
 };
 
+#[cfg(feature = "serde")]
+pub use crate::extended_key::private_key::SerializableExtendedPrivateKey;
+
+#[cfg(feature = "nistp256")]
+pub use p256 as nistp256;
+
+#[cfg(feature = "secrecy")]
+pub use secrecy;
+
 /// Chain code: extension for both private and public keys which provides an
 /// additional 256-bits of entropy.
 pub type ChainCode = [u8; KEY_SIZE];
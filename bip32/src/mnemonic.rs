@@ -6,11 +6,17 @@
 mod bits;
 mod language;
 mod phrase;
+mod word_count;
 
+#[cfg(feature = "bip39")]
+mod electrum;
 #[cfg(feature = "bip39")]
 pub(crate) mod seed;
 
-pub use self::{language::Language, phrase::Phrase};
+pub use self::{language::Language, phrase::Phrase, word_count::WordCount};
 
 #[cfg(feature = "bip39")]
-pub use self::seed::Seed;
+pub use self::{
+    electrum::{ElectrumMnemonic, ElectrumSeedType},
+    seed::Seed,
+};
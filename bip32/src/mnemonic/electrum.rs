@@ -0,0 +1,217 @@
+//! Electrum v2 seed phrases.
+//!
+//! Unlike BIP39, Electrum seed phrases have no checksum: their type is
+//! encoded as a prefix of the HMAC of the normalized phrase. The phrase is
+//! stretched into a BIP32 seed using PBKDF2 with an `electrum` salt.
+//!
+//! Old (pre-2.0) Electrum seeds, which aren't BIP32-based, and two-factor
+//! seeds, which require a third-party cosigner, aren't supported.
+
+use super::seed::Seed;
+use crate::{ChildNumber, DerivationPath, Error, Prefix};
+use alloc::string::String;
+use hmac::{Hmac, KeyInit, Mac};
+use sha2::Sha512;
+use unicode_normalization::{UnicodeNormalization, char::canonical_combining_class};
+use zeroize::Zeroizing;
+
+/// HMAC key used to compute the version of a seed phrase.
+const VERSION_KEY: &[u8] = b"Seed version";
+
+/// Salt prefix used to derive the seed.
+const SALT_PREFIX: &str = "electrum";
+
+/// Number of PBKDF2 rounds to perform when deriving the seed.
+const PBKDF2_ROUNDS: u32 = 2048;
+
+/// Ranges of CJK characters, between which whitespace is removed.
+const CJK_RANGES: &[(u32, u32)] = &[
+    (0x4e00, 0x9fff),   // CJK Unified Ideographs
+    (0x3400, 0x4dbf),   // CJK Unified Ideographs Extension A
+    (0x20000, 0x2a6df), // CJK Unified Ideographs Extension B
+    (0x2a700, 0x2b73f), // CJK Unified Ideographs Extension C
+    (0x2b740, 0x2b81f), // CJK Unified Ideographs Extension D
+    (0xf900, 0xfaff),   // CJK Compatibility Ideographs
+    (0x2f800, 0x2fa1d), // CJK Compatibility Ideographs Supplement
+    (0x3190, 0x319f),   // Kanbun
+    (0x2e80, 0x2eff),   // CJK Radicals Supplement
+    (0x2f00, 0x2fdf),   // CJK Radicals
+    (0x31c0, 0x31ef),   // CJK Strokes
+    (0x2ff0, 0x2fff),   // Ideographic Description Characters
+    (0xe0100, 0xe01ef), // Variation Selectors Supplement
+    (0x3100, 0x312f),   // Bopomofo
+    (0x31a0, 0x31bf),   // Bopomofo Extended
+    (0xff00, 0xffef),   // Halfwidth and Fullwidth Forms
+    (0x3040, 0x309f),   // Hiragana
+    (0x30a0, 0x30ff),   // Katakana
+    (0x31f0, 0x31ff),   // Katakana Phonetic Extensions
+    (0x1b000, 0x1b0ff), // Kana Supplement
+    (0xac00, 0xd7af),   // Hangul Syllables
+    (0x1100, 0x11ff),   // Hangul Jamo
+    (0xa960, 0xa97f),   // Hangul Jamo Extended A
+    (0xd7b0, 0xd7ff),   // Hangul Jamo Extended B
+    (0x3130, 0x318f),   // Hangul Compatibility Jamo
+    (0xa4d0, 0xa4ff),   // Lisu
+    (0x16f00, 0x16f9f), // Miao
+    (0xa000, 0xa48f),   // Yi Syllables
+    (0xa490, 0xa4cf),   // Yi Radicals
+];
+
+/// Types of Electrum v2 seed phrases.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ElectrumSeedType {
+    /// Standard wallets, with P2PKH addresses derived from the root key.
+    Standard,
+
+    /// Segwit wallets, with P2WPKH addresses derived from `m/0'`.
+    Segwit,
+}
+
+impl ElectrumSeedType {
+    /// Get the type of a normalized seed phrase from its version prefix.
+    fn detect(phrase: &str) -> Option<Self> {
+        let mut hmac = Hmac::<Sha512>::new_from_slice(VERSION_KEY).ok()?;
+        hmac.update(phrase.as_bytes());
+        let version = hmac.finalize().into_bytes();
+
+        // Version prefixes are hex strings: "01" for standard wallets and
+        // "100" for segwit wallets
+        if version[0] == 0x01 {
+            Some(ElectrumSeedType::Standard)
+        } else if version[0] == 0x10 && version[1] >> 4 == 0 {
+            Some(ElectrumSeedType::Segwit)
+        } else {
+            None
+        }
+    }
+
+    /// Get the derivation path of the account key, from which receiving
+    /// (`0/i`) and change (`1/i`) addresses are derived.
+    pub fn derivation_path(self) -> DerivationPath {
+        let mut path = DerivationPath::default();
+
+        if self == ElectrumSeedType::Segwit {
+            path.push(ChildNumber::new(0, true).expect("valid child number"));
+        }
+
+        path
+    }
+
+    /// Get the prefix Electrum uses to serialize extended private keys of
+    /// this type: `xprv` for standard wallets, and `zprv` for segwit wallets.
+    pub fn prefix(self) -> Prefix {
+        match self {
+            ElectrumSeedType::Standard => Prefix::XPRV,
+            ElectrumSeedType::Segwit => Prefix::ZPRV,
+        }
+    }
+}
+
+/// Electrum v2 seed phrase.
+#[derive(Clone)]
+pub struct ElectrumMnemonic {
+    /// Normalized seed phrase.
+    phrase: Zeroizing<String>,
+
+    /// Type of the seed phrase.
+    seed_type: ElectrumSeedType,
+}
+
+impl ElectrumMnemonic {
+    /// Parse an Electrum v2 seed phrase, detecting its type.
+    ///
+    /// Returns [`Error::Electrum`] if the phrase isn't a standard or segwit
+    /// seed phrase.
+    pub fn new<S>(phrase: S) -> Result<Self, Error>
+    where
+        S: AsRef<str>,
+    {
+        let phrase = normalize(phrase.as_ref());
+        let seed_type = ElectrumSeedType::detect(&phrase).ok_or(Error::Electrum)?;
+        Ok(Self { phrase, seed_type })
+    }
+
+    /// Get the normalized seed phrase.
+    pub fn phrase(&self) -> &str {
+        &self.phrase
+    }
+
+    /// Get the type of this seed phrase.
+    pub fn seed_type(&self) -> ElectrumSeedType {
+        self.seed_type
+    }
+
+    /// Get the derivation path of the account key.
+    pub fn derivation_path(&self) -> DerivationPath {
+        self.seed_type.derivation_path()
+    }
+
+    /// Convert this seed phrase into a BIP32 seed, using the given
+    /// passphrase (the "seed extension" in Electrum).
+    pub fn to_seed(&self, passphrase: &str) -> Seed {
+        let mut salt = Zeroizing::new(String::from(SALT_PREFIX));
+        salt.push_str(&normalize(passphrase));
+
+        let mut seed = [0u8; Seed::SIZE];
+        pbkdf2::pbkdf2_hmac::<Sha512>(
+            self.phrase.as_bytes(),
+            salt.as_bytes(),
+            PBKDF2_ROUNDS,
+            &mut seed,
+        );
+        Seed(seed)
+    }
+}
+
+/// Normalize a seed phrase or passphrase as Electrum does: NFKD, lowercase,
+/// with accents removed, words separated by single spaces, and no spaces
+/// between CJK characters.
+fn normalize(s: &str) -> Zeroizing<String> {
+    let mut stripped = Zeroizing::new(String::with_capacity(s.len()));
+
+    for c in s.nfkd().flat_map(char::to_lowercase) {
+        if canonical_combining_class(c) == 0 {
+            stripped.push(c);
+        }
+    }
+
+    let mut normalized = Zeroizing::new(String::with_capacity(stripped.len()));
+    let mut words = stripped.split_whitespace().peekable();
+
+    while let Some(word) = words.next() {
+        normalized.push_str(word);
+
+        if let Some(next) = words.peek() {
+            let last_is_cjk = word.chars().last().is_some_and(is_cjk);
+            let next_is_cjk = next.chars().next().is_some_and(is_cjk);
+
+            if !(last_is_cjk && next_is_cjk) {
+                normalized.push(' ');
+            }
+        }
+    }
+
+    normalized
+}
+
+/// Is the given character a CJK character?
+fn is_cjk(c: char) -> bool {
+    CJK_RANGES
+        .iter()
+        .any(|&(start, end)| (start..=end).contains(&u32::from(c)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize;
+
+    #[test]
+    fn normalization() {
+        assert_eq!(*normalize("  Wild\tFATHER \n tree "), "wild father tree");
+        assert_eq!(*normalize("caf\u{e9} na\u{ef}ve"), "cafe naive");
+        assert_eq!(
+            *normalize("\u{4e00} \u{4e01}  a \u{4e02}"),
+            "\u{4e00}\u{4e01} a \u{4e02}"
+        );
+    }
+}
@@ -5,24 +5,195 @@
 //!
 //! Adapted from the `bip39` crate
 
-use super::bits::{Bits, Bits11};
+use super::{
+    bits::{Bits, Bits11},
+    phrase::{Phrase, normalize},
+};
+use crate::Error;
 use alloc::{collections::BTreeMap, vec::Vec};
+use core::ops::Bound;
 
 /// Supported languages.
 ///
-/// Presently only English is specified by the BIP39 standard
-#[derive(Copy, Clone, Debug, Default)]
+/// English is always available. The other wordlists specified by BIP39 are
+/// available under per-language crate features (e.g. `japanese`), or all at
+/// once with the `all-languages` feature.
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq, PartialOrd, Ord)]
+#[non_exhaustive]
 pub enum Language {
-    /// English is presently the only supported language
+    /// English
     #[default]
     English,
+
+    /// Chinese (Simplified)
+    #[cfg(feature = "chinese-simplified")]
+    ChineseSimplified,
+
+    /// Chinese (Traditional)
+    #[cfg(feature = "chinese-traditional")]
+    ChineseTraditional,
+
+    /// Czech
+    #[cfg(feature = "czech")]
+    Czech,
+
+    /// French
+    #[cfg(feature = "french")]
+    French,
+
+    /// Italian
+    #[cfg(feature = "italian")]
+    Italian,
+
+    /// Japanese
+    #[cfg(feature = "japanese")]
+    Japanese,
+
+    /// Korean
+    #[cfg(feature = "korean")]
+    Korean,
+
+    /// Portuguese
+    #[cfg(feature = "portuguese")]
+    Portuguese,
+
+    /// Spanish
+    #[cfg(feature = "spanish")]
+    Spanish,
 }
 
 impl Language {
+    /// All languages enabled in this build.
+    pub const ALL: &'static [Language] = &[
+        Language::English,
+        #[cfg(feature = "chinese-simplified")]
+        Language::ChineseSimplified,
+        #[cfg(feature = "chinese-traditional")]
+        Language::ChineseTraditional,
+        #[cfg(feature = "czech")]
+        Language::Czech,
+        #[cfg(feature = "french")]
+        Language::French,
+        #[cfg(feature = "italian")]
+        Language::Italian,
+        #[cfg(feature = "japanese")]
+        Language::Japanese,
+        #[cfg(feature = "korean")]
+        Language::Korean,
+        #[cfg(feature = "portuguese")]
+        Language::Portuguese,
+        #[cfg(feature = "spanish")]
+        Language::Spanish,
+    ];
+
+    /// Detect the language of the given mnemonic phrase.
+    ///
+    /// Some words appear in more than one wordlist, so a language is only
+    /// returned if the phrase is valid (including its checksum) in exactly
+    /// one of the enabled languages.
+    pub fn detect<S>(phrase: S) -> Result<Self, Error>
+    where
+        S: AsRef<str>,
+    {
+        let phrase = normalize(phrase.as_ref());
+        let mut detected = None;
+
+        for &language in Language::ALL {
+            if Phrase::new(phrase.as_str(), language).is_ok() {
+                if detected.is_some() {
+                    return Err(Error::Bip39);
+                }
+
+                detected = Some(language);
+            }
+        }
+
+        detected.ok_or(Error::Bip39)
+    }
+
+    /// Get the separator placed between words in phrases in this language.
+    ///
+    /// This is an ideographic space for Japanese, and an ASCII space for all
+    /// other languages.
+    pub fn separator(self) -> &'static str {
+        match self {
+            #[cfg(feature = "japanese")]
+            Language::Japanese => "\u{3000}",
+            _ => " ",
+        }
+    }
+
+    /// Get the words in this language's wordlist which start with the given
+    /// prefix, in lexicographic order.
+    pub fn completions(self, prefix: &str) -> Vec<&'static str> {
+        self.wordmap()
+            .words_with_prefix(&normalize(prefix))
+            .collect()
+    }
+
+    /// Complete a word from a prefix which unambiguously identifies it, e.g.
+    /// `aban` for `abandon` (in English, the first 4 letters of each word are
+    /// unique).
+    ///
+    /// Returns the word itself if it's in the wordlist, even if it's also a
+    /// prefix of other words (e.g. `act`), and `None` if the prefix matches
+    /// no words or more than one word.
+    pub fn complete_word(self, prefix: &str) -> Option<&'static str> {
+        let prefix = normalize(prefix);
+        let mut words = self.wordmap().words_with_prefix(&prefix);
+        let first = words.next()?;
+
+        if first == prefix.as_str() {
+            return Some(first);
+        }
+
+        match words.next() {
+            Some(_) => None,
+            None => Some(first),
+        }
+    }
+
+    /// Suggest words from this language's wordlist which are within the
+    /// given edit (Levenshtein) distance of a possibly misspelled word,
+    /// closest first.
+    pub fn suggestions(self, word: &str, max_distance: usize) -> Vec<&'static str> {
+        let word = normalize(word);
+
+        let mut suggestions: Vec<_> = self
+            .wordlist()
+            .iter()
+            .filter_map(|candidate| {
+                let distance = edit_distance(&word, candidate);
+                (distance <= max_distance).then_some((distance, candidate))
+            })
+            .collect();
+
+        suggestions.sort_by_key(|&(distance, _)| distance);
+        suggestions.into_iter().map(|(_, word)| word).collect()
+    }
+
     /// Get the word list for this language
     pub(crate) fn wordlist(&self) -> &'static WordList {
         match *self {
             Language::English => &lazy::WORDLIST_ENGLISH,
+            #[cfg(feature = "chinese-simplified")]
+            Language::ChineseSimplified => &lazy::WORDLIST_CHINESE_SIMPLIFIED,
+            #[cfg(feature = "chinese-traditional")]
+            Language::ChineseTraditional => &lazy::WORDLIST_CHINESE_TRADITIONAL,
+            #[cfg(feature = "czech")]
+            Language::Czech => &lazy::WORDLIST_CZECH,
+            #[cfg(feature = "french")]
+            Language::French => &lazy::WORDLIST_FRENCH,
+            #[cfg(feature = "italian")]
+            Language::Italian => &lazy::WORDLIST_ITALIAN,
+            #[cfg(feature = "japanese")]
+            Language::Japanese => &lazy::WORDLIST_JAPANESE,
+            #[cfg(feature = "korean")]
+            Language::Korean => &lazy::WORDLIST_KOREAN,
+            #[cfg(feature = "portuguese")]
+            Language::Portuguese => &lazy::WORDLIST_PORTUGUESE,
+            #[cfg(feature = "spanish")]
+            Language::Spanish => &lazy::WORDLIST_SPANISH,
         }
     }
 
@@ -30,6 +201,24 @@ impl Language {
     pub(crate) fn wordmap(&self) -> &'static WordMap {
         match *self {
             Language::English => &lazy::WORDMAP_ENGLISH,
+            #[cfg(feature = "chinese-simplified")]
+            Language::ChineseSimplified => &lazy::WORDMAP_CHINESE_SIMPLIFIED,
+            #[cfg(feature = "chinese-traditional")]
+            Language::ChineseTraditional => &lazy::WORDMAP_CHINESE_TRADITIONAL,
+            #[cfg(feature = "czech")]
+            Language::Czech => &lazy::WORDMAP_CZECH,
+            #[cfg(feature = "french")]
+            Language::French => &lazy::WORDMAP_FRENCH,
+            #[cfg(feature = "italian")]
+            Language::Italian => &lazy::WORDMAP_ITALIAN,
+            #[cfg(feature = "japanese")]
+            Language::Japanese => &lazy::WORDMAP_JAPANESE,
+            #[cfg(feature = "korean")]
+            Language::Korean => &lazy::WORDMAP_KOREAN,
+            #[cfg(feature = "portuguese")]
+            Language::Portuguese => &lazy::WORDMAP_PORTUGUESE,
+            #[cfg(feature = "spanish")]
+            Language::Spanish => &lazy::WORDMAP_SPANISH,
         }
     }
 }
@@ -46,15 +235,51 @@ impl WordMap {
     pub fn get_bits(&self, word: &str) -> Option<Bits11> {
         self.inner.get(word).cloned()
     }
+
+    pub fn words_with_prefix<'a>(
+        &'a self,
+        prefix: &'a str,
+    ) -> impl Iterator<Item = &'static str> + 'a {
+        self.inner
+            .range::<str, _>((Bound::Included(prefix), Bound::Unbounded))
+            .map(|(word, _)| *word)
+            .take_while(move |word| word.starts_with(prefix))
+    }
 }
 
 impl WordList {
     pub fn get_word(&self, bits: Bits11) -> &'static str {
         self.inner[bits.bits() as usize]
     }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.inner.iter().copied()
+    }
+}
+
+/// Compute the Levenshtein distance between two strings, in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, &b_char) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a_char != b_char);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    row[b.len()]
 }
 
 // TODO(tarcieri): use `const fn` instead of `Lazy`
+//
+// NOTE: the wordlists in `langs/` are stored in Unicode NFKD form, so that
+// words in normalized phrases can be looked up directly.
 mod lazy {
     use super::{Bits11, WordList, WordMap};
     use alloc::vec::Vec;
@@ -81,8 +306,85 @@ mod lazy {
         WordMap { inner }
     }
 
+    /// Define the lazily-generated word list and word map for a language.
+    macro_rules! wordlist {
+        ($feature:literal, $wordlist:ident, $wordmap:ident, $file:literal) => {
+            #[cfg(feature = $feature)]
+            pub(crate) static $wordlist: Lazy<WordList> =
+                Lazy::new(|| gen_wordlist(include_str!($file)));
+
+            #[cfg(feature = $feature)]
+            pub(crate) static $wordmap: Lazy<WordMap> = Lazy::new(|| gen_wordmap(&$wordlist));
+        };
+    }
+
     pub(crate) static WORDLIST_ENGLISH: Lazy<WordList> =
         Lazy::new(|| gen_wordlist(include_str!("langs/english.txt")));
 
     pub(crate) static WORDMAP_ENGLISH: Lazy<WordMap> = Lazy::new(|| gen_wordmap(&WORDLIST_ENGLISH));
+
+    wordlist!(
+        "chinese-simplified",
+        WORDLIST_CHINESE_SIMPLIFIED,
+        WORDMAP_CHINESE_SIMPLIFIED,
+        "langs/chinese_simplified.txt"
+    );
+    wordlist!(
+        "chinese-traditional",
+        WORDLIST_CHINESE_TRADITIONAL,
+        WORDMAP_CHINESE_TRADITIONAL,
+        "langs/chinese_traditional.txt"
+    );
+    wordlist!("czech", WORDLIST_CZECH, WORDMAP_CZECH, "langs/czech.txt");
+    wordlist!(
+        "french",
+        WORDLIST_FRENCH,
+        WORDMAP_FRENCH,
+        "langs/french.txt"
+    );
+    wordlist!(
+        "italian",
+        WORDLIST_ITALIAN,
+        WORDMAP_ITALIAN,
+        "langs/italian.txt"
+    );
+    wordlist!(
+        "japanese",
+        WORDLIST_JAPANESE,
+        WORDMAP_JAPANESE,
+        "langs/japanese.txt"
+    );
+    wordlist!(
+        "korean",
+        WORDLIST_KOREAN,
+        WORDMAP_KOREAN,
+        "langs/korean.txt"
+    );
+    wordlist!(
+        "portuguese",
+        WORDLIST_PORTUGUESE,
+        WORDMAP_PORTUGUESE,
+        "langs/portuguese.txt"
+    );
+    wordlist!(
+        "spanish",
+        WORDLIST_SPANISH,
+        WORDMAP_SPANISH,
+        "langs/spanish.txt"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::edit_distance;
+
+    #[test]
+    fn edit_distances() {
+        assert_eq!(edit_distance("abandon", "abandon"), 0);
+        assert_eq!(edit_distance("abandom", "abandon"), 1);
+        assert_eq!(edit_distance("abadon", "abandon"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "act"), 3);
+        assert_eq!(edit_distance("\u{e9}t\u{e9}", "ete"), 2);
+    }
 }
@@ -1,24 +1,31 @@
 //! BIP39 mnemonic phrases
 
 use super::{
-    bits::{BitWriter, IterExt},
+    bits::{BitWriter, Bits, Bits11, IterExt},
     language::Language,
+    word_count::WordCount,
 };
 use crate::{Error, KEY_SIZE};
-use alloc::{format, string::String};
+use alloc::{format, string::String, vec, vec::Vec};
 use rand_core::{CryptoRng, RngCore};
 use sha2::{Digest, Sha256};
+use subtle::{Choice, ConstantTimeEq};
+use unicode_normalization::UnicodeNormalization;
 use zeroize::{Zeroize, Zeroizing};
 
 #[cfg(feature = "bip39")]
 use {super::seed::Seed, sha2::Sha512};
 
+#[cfg(feature = "secrecy")]
+use secrecy::{ExposeSecret, SecretString};
+
 /// Number of PBKDF2 rounds to perform when deriving the seed
 #[cfg(feature = "bip39")]
 const PBKDF2_ROUNDS: u32 = 2048;
 
-/// Source entropy for a BIP39 mnemonic phrase
-pub type Entropy = [u8; KEY_SIZE];
+/// Source entropy for a BIP39 mnemonic phrase, sized for the longest
+/// supported phrase.
+type Entropy = [u8; KEY_SIZE];
 
 /// BIP39 mnemonic phrases: sequences of words representing cryptographic keys.
 #[derive(Clone)]
@@ -26,7 +33,10 @@ pub struct Phrase {
     /// Language
     language: Language,
 
-    /// Source entropy for this phrase
+    /// Number of words in the phrase
+    word_count: WordCount,
+
+    /// Source entropy for this phrase, padded with zeroes
     entropy: Entropy,
 
     /// Mnemonic phrase
@@ -34,37 +44,61 @@ pub struct Phrase {
 }
 
 impl Phrase {
-    /// Create a random BIP39 mnemonic phrase.
-    pub fn random(mut rng: impl RngCore + CryptoRng, language: Language) -> Self {
-        let mut entropy = Entropy::default();
-        rng.fill_bytes(&mut entropy);
-        Self::from_entropy(entropy, language)
+    /// Create a random BIP39 mnemonic phrase with the given number of words.
+    pub fn random(
+        mut rng: impl RngCore + CryptoRng,
+        word_count: WordCount,
+        language: Language,
+    ) -> Self {
+        let mut entropy = Zeroizing::new(Entropy::default());
+        let entropy = &mut entropy[..word_count.entropy_size()];
+        rng.fill_bytes(entropy);
+        Self::from_entropy_with_word_count(entropy, word_count, language)
+    }
+
+    /// Create a new BIP39 mnemonic phrase from the given entropy.
+    ///
+    /// The entropy must be 16, 20, 24, 28, or 32 bytes, producing a phrase of
+    /// 12, 15, 18, 21, or 24 words respectively.
+    pub fn from_entropy(entropy: &[u8], language: Language) -> Result<Self, Error> {
+        let word_count = WordCount::from_entropy_size(entropy.len())?;
+        Ok(Self::from_entropy_with_word_count(
+            entropy, word_count, language,
+        ))
     }
 
-    /// Create a new BIP39 mnemonic phrase from the given entropy
-    pub fn from_entropy(entropy: Entropy, language: Language) -> Self {
+    /// Create a new BIP39 mnemonic phrase from entropy whose size is
+    /// `word_count.entropy_size()`.
+    fn from_entropy_with_word_count(
+        entropy: &[u8],
+        word_count: WordCount,
+        language: Language,
+    ) -> Self {
         let wordlist = language.wordlist();
-        let checksum_byte = Sha256::digest(entropy.as_slice()).as_slice()[0];
+        let checksum_byte = Sha256::digest(entropy).as_slice()[0];
 
         // First, create a byte iterator for the given entropy and the first byte of the
         // hash of the entropy that will serve as the checksum (up to 8 bits for biggest
         // entropy source).
         //
         // Then we transform that into a bits iterator that returns 11 bits at a
-        // time (as u16), which we can map to the words on the `wordlist`.
-        //
-        // Given the entropy is of correct size, this ought to give us the correct word
-        // count.
+        // time (as u16), which we can map to the words on the `wordlist`. Any
+        // checksum bits which don't fill a whole word are discarded.
         let phrase = entropy
             .iter()
             .chain(Some(&checksum_byte))
             .bits()
+            .take(word_count.words())
             .map(|bits| wordlist.get_word(bits))
-            .join(" ");
+            .join(language.separator());
+
+        let mut padded_entropy = Entropy::default();
+        padded_entropy[..entropy.len()].copy_from_slice(entropy);
 
         Phrase {
             language,
-            entropy,
+            word_count,
+            entropy: padded_entropy,
             phrase,
         }
     }
@@ -72,51 +106,161 @@ impl Phrase {
     /// Create a new BIP39 mnemonic phrase from the given string.
     ///
     /// The phrase supplied will be checked for word length and validated
-    /// according to the checksum specified in BIP0039.
+    /// according to the checksum specified in BIP0039. Phrases of 12, 15, 18,
+    /// 21, or 24 words are supported.
     ///
-    /// To use the default language, English, (the only one supported by this
-    /// library and also the only one standardized for BIP39) you can supply
-    /// `Default::default()` as the language.
+    /// The phrase is normalized to Unicode NFKD before it's parsed. Words may
+    /// be separated by any amount of whitespace, including (for Japanese)
+    /// ideographic spaces, and leading or trailing whitespace is ignored.
+    ///
+    /// To use the default language, English, you can supply
+    /// `Default::default()` as the language. See [`Language::detect`] to
+    /// determine the language of a phrase.
     pub fn new<S>(phrase: S, language: Language) -> Result<Self, Error>
     where
         S: AsRef<str>,
     {
-        let phrase = phrase.as_ref();
+        let phrase = normalize(phrase.as_ref());
         let wordmap = language.wordmap();
+        let mut indices = Zeroizing::new(Vec::with_capacity(24));
 
-        // Preallocate enough space for the longest possible word list
-        let mut bits = BitWriter::with_capacity(264);
+        for (index, word) in phrase.split_whitespace().enumerate() {
+            let bits = wordmap.get_bits(word).ok_or(Error::UnknownWord { index })?;
+            indices.push(u16::from(bits));
+        }
 
-        for word in phrase.split(' ') {
-            bits.push(wordmap.get_bits(word).ok_or(Error::Bip39)?);
+        Self::from_indices(&indices, language)
+    }
+
+    /// Create a new BIP39 mnemonic phrase from a phrase supplied as a
+    /// [`SecretString`].
+    ///
+    /// See [`Phrase::new`].
+    #[cfg(feature = "secrecy")]
+    pub fn new_secret(phrase: &SecretString, language: Language) -> Result<Self, Error> {
+        Self::new(phrase.expose_secret(), language)
+    }
+
+    /// Recover a BIP39 mnemonic phrase with a single missing or incorrect
+    /// word, by trying every word of the wordlist in its place and keeping
+    /// the phrases with a valid checksum:
+    ///
+    /// - if the phrase is one word short, the missing word is tried at every
+    ///   position;
+    /// - if a word isn't in the wordlist, only that word is replaced;
+    /// - otherwise, each word is replaced in turn.
+    ///
+    /// As the checksum is only 4 to 8 bits long, several candidates are
+    /// usually returned, which need to be checked e.g. against the addresses
+    /// of the wallet. A phrase which is already valid is returned as the only
+    /// candidate.
+    ///
+    /// Returns an error if more than one word is unknown, or if the number
+    /// of words isn't valid, even with an additional word.
+    pub fn recover<S>(phrase: S, language: Language) -> Result<Vec<Self>, Error>
+    where
+        S: AsRef<str>,
+    {
+        let phrase = normalize(phrase.as_ref());
+        let wordmap = language.wordmap();
+        let mut indices = Zeroizing::new(Vec::with_capacity(24));
+        let mut unknown = None;
+
+        for (index, word) in phrase.split_whitespace().enumerate() {
+            match wordmap.get_bits(word) {
+                Some(bits) => indices.push(u16::from(bits)),
+                None if unknown.is_none() => {
+                    unknown = Some(index);
+                    indices.push(0);
+                }
+                None => return Err(Error::UnknownWord { index }),
+            }
         }
 
-        let mut entropy = Zeroizing::new(bits.into_bytes());
+        let missing = WordCount::try_from(indices.len() + 1).is_ok();
 
-        if entropy.len() != KEY_SIZE + 1 {
-            return Err(Error::Bip39);
+        if !missing {
+            WordCount::try_from(indices.len())?;
         }
 
-        let actual_checksum = entropy[KEY_SIZE];
+        let positions = match unknown {
+            Some(index) if missing => return Err(Error::UnknownWord { index }),
+            Some(index) => index..(index + 1),
+            None if missing => 0..(indices.len() + 1),
+            None => {
+                if let Ok(phrase) = Self::from_indices(&indices, language) {
+                    return Ok(vec![phrase]);
+                }
+
+                0..indices.len()
+            }
+        };
 
-        // Truncate to get rid of the byte containing the checksum
-        entropy.truncate(KEY_SIZE);
+        let mut candidates = Vec::new();
 
-        let expected_checksum = Sha256::digest(&entropy).as_slice()[0];
+        for position in positions {
+            let mut candidate = indices.clone();
+
+            if missing {
+                candidate.insert(position, 0);
+            }
+
+            for word in 0..(1 << Bits11::SIZE) {
+                candidate[position] = word;
+
+                if let Ok(phrase) = Self::from_indices(&candidate, language) {
+                    // The same phrase can be obtained by inserting a word
+                    // before or after an identical one
+                    if !candidates.contains(&phrase) {
+                        candidates.push(phrase);
+                    }
+                }
+            }
+        }
+
+        Ok(candidates)
+    }
+
+    /// Create a new BIP39 mnemonic phrase from the indices of its words in
+    /// the wordlist, validating the checksum.
+    fn from_indices(indices: &[u16], language: Language) -> Result<Self, Error> {
+        let word_count = WordCount::try_from(indices.len())?;
+
+        // Preallocate enough space for the longest possible word list
+        let mut bits = BitWriter::with_capacity(264);
+
+        for &index in indices {
+            bits.push(Bits11::from(index));
+        }
+
+        let entropy_size = word_count.entropy_size();
+        let entropy = Zeroizing::new(bits.into_bytes());
+
+        // The checksum occupies the high bits of the byte following the entropy
+        let checksum_shift = 8 - word_count.checksum_bits();
+        let actual_checksum = entropy[entropy_size] >> checksum_shift;
+        let expected_checksum =
+            Sha256::digest(&entropy[..entropy_size]).as_slice()[0] >> checksum_shift;
 
         if actual_checksum != expected_checksum {
-            return Err(Error::Bip39);
+            return Err(Error::MnemonicChecksum);
         }
 
-        Ok(Self::from_entropy(
-            entropy.as_slice().try_into().map_err(|_| Error::Bip39)?,
+        Ok(Self::from_entropy_with_word_count(
+            &entropy[..entropy_size],
+            word_count,
             language,
         ))
     }
 
     /// Get source entropy for this phrase.
-    pub fn entropy(&self) -> &Entropy {
-        &self.entropy
+    pub fn entropy(&self) -> &[u8] {
+        &self.entropy[..self.word_count.entropy_size()]
+    }
+
+    /// Get the number of words in this phrase.
+    pub fn word_count(&self) -> WordCount {
+        self.word_count
     }
 
     /// Get the mnemonic phrase as a string reference.
@@ -124,24 +268,55 @@ impl Phrase {
         &self.phrase
     }
 
+    /// Get a copy of the mnemonic phrase as a [`SecretString`].
+    #[cfg(feature = "secrecy")]
+    pub fn phrase_secret(&self) -> SecretString {
+        SecretString::from(self.phrase.as_str())
+    }
+
     /// Language this phrase's wordlist is for
     pub fn language(&self) -> Language {
         self.language
     }
 
     /// Convert this mnemonic phrase into the BIP39 seed value.
+    ///
+    /// Both the phrase and the password are normalized to Unicode NFKD.
     #[cfg(feature = "bip39")]
     pub fn to_seed(&self, password: &str) -> Seed {
-        let salt = Zeroizing::new(format!("mnemonic{}", password));
+        let phrase = normalize(&self.phrase);
+        let salt = normalize(&Zeroizing::new(format!("mnemonic{}", password)));
         let mut seed = [0u8; Seed::SIZE];
-        pbkdf2::pbkdf2_hmac::<Sha512>(
-            self.phrase.as_bytes(),
-            salt.as_bytes(),
-            PBKDF2_ROUNDS,
-            &mut seed,
-        );
+        pbkdf2::pbkdf2_hmac::<Sha512>(phrase.as_bytes(), salt.as_bytes(), PBKDF2_ROUNDS, &mut seed);
         Seed(seed)
     }
+
+    /// Convert this mnemonic phrase into the BIP39 seed value, using a
+    /// password supplied as a [`SecretString`].
+    ///
+    /// See [`Phrase::to_seed`].
+    #[cfg(feature = "secrecy")]
+    pub fn to_seed_secret(&self, password: &SecretString) -> Seed {
+        self.to_seed(password.expose_secret())
+    }
+}
+
+impl ConstantTimeEq for Phrase {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.entropy.ct_eq(&other.entropy)
+            & self.word_count.words().ct_eq(&other.word_count.words())
+            & Choice::from(u8::from(self.language == other.language))
+    }
+}
+
+/// NOTE: uses [`ConstantTimeEq`] internally
+impl Eq for Phrase {}
+
+/// NOTE: uses [`ConstantTimeEq`] internally
+impl PartialEq for Phrase {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
 }
 
 impl Drop for Phrase {
@@ -150,3 +325,8 @@ impl Drop for Phrase {
         self.entropy.zeroize();
     }
 }
+
+/// Normalize the given string to Unicode NFKD form.
+pub(crate) fn normalize(s: &str) -> Zeroizing<String> {
+    Zeroizing::new(s.nfkd().collect())
+}
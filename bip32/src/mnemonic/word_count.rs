@@ -0,0 +1,79 @@
+//! BIP39 mnemonic phrase lengths
+
+use crate::Error;
+
+/// Number of words in a BIP39 mnemonic phrase.
+///
+/// Each word encodes 11 bits: every 3 words carry 32 bits of entropy plus a
+/// 1-bit checksum.
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum WordCount {
+    /// 12 words: 128 bits of entropy
+    Words12,
+
+    /// 15 words: 160 bits of entropy
+    Words15,
+
+    /// 18 words: 192 bits of entropy
+    Words18,
+
+    /// 21 words: 224 bits of entropy
+    Words21,
+
+    /// 24 words: 256 bits of entropy
+    #[default]
+    Words24,
+}
+
+impl WordCount {
+    /// Get the number of words in the phrase.
+    pub fn words(self) -> usize {
+        match self {
+            WordCount::Words12 => 12,
+            WordCount::Words15 => 15,
+            WordCount::Words18 => 18,
+            WordCount::Words21 => 21,
+            WordCount::Words24 => 24,
+        }
+    }
+
+    /// Get the size of the source entropy in bytes.
+    pub fn entropy_size(self) -> usize {
+        self.words() * 4 / 3
+    }
+
+    /// Get the size of the checksum in bits.
+    pub fn checksum_bits(self) -> usize {
+        self.words() / 3
+    }
+
+    /// Get the word count for source entropy of the given size in bytes.
+    pub fn from_entropy_size(size: usize) -> Result<Self, Error> {
+        if size % 4 != 0 {
+            return Err(Error::Bip39);
+        }
+
+        Self::try_from(size * 3 / 4)
+    }
+}
+
+impl TryFrom<usize> for WordCount {
+    type Error = Error;
+
+    fn try_from(words: usize) -> Result<Self, Error> {
+        match words {
+            12 => Ok(WordCount::Words12),
+            15 => Ok(WordCount::Words15),
+            18 => Ok(WordCount::Words18),
+            21 => Ok(WordCount::Words21),
+            24 => Ok(WordCount::Words24),
+            _ => Err(Error::Bip39),
+        }
+    }
+}
+
+impl From<WordCount> for usize {
+    fn from(word_count: WordCount) -> usize {
+        word_count.words()
+    }
+}
@@ -3,9 +3,40 @@
 use crate::{Error, ExtendedKey, Result, Version};
 use core::{
     fmt::{self, Debug, Display},
-    str,
+    str::{self, FromStr},
 };
 
+#[cfg(feature = "serde")]
+use {
+    alloc::string::String,
+    serde::{Deserialize, Serialize, de, ser},
+};
+
+#[cfg(feature = "std")]
+use {
+    alloc::vec::Vec,
+    std::sync::{PoisonError, RwLock},
+};
+
+/// Pairs of private and public prefixes which are always recognized.
+const KNOWN_PREFIXES: &[(Prefix, Prefix)] = &[
+    (Prefix::XPRV, Prefix::XPUB),
+    (Prefix::YPRV, Prefix::YPUB),
+    (Prefix::ZPRV, Prefix::ZPUB),
+    (Prefix::YPRV_MULTISIG, Prefix::YPUB_MULTISIG),
+    (Prefix::ZPRV_MULTISIG, Prefix::ZPUB_MULTISIG),
+    (Prefix::TPRV, Prefix::TPUB),
+    (Prefix::UPRV, Prefix::UPUB),
+    (Prefix::VPRV, Prefix::VPUB),
+    (Prefix::UPRV_MULTISIG, Prefix::UPUB_MULTISIG),
+    (Prefix::VPRV_MULTISIG, Prefix::VPUB_MULTISIG),
+];
+
+/// Pairs of private and public prefixes registered at runtime with
+/// [`Prefix::register`].
+#[cfg(feature = "std")]
+static REGISTERED_PREFIXES: RwLock<Vec<(Prefix, Prefix)>> = RwLock::new(Vec::new());
+
 /// BIP32 extended key prefixes a.k.a. "versions" (e.g. `xpub`, `xprv`)
 ///
 /// The BIP32 spec describes these as "versions" and gives examples for
@@ -49,6 +80,42 @@ impl Prefix {
     /// `zpub` prefix
     pub const ZPUB: Self = Self::from_parts_unchecked("zpub", 0x04b24746);
 
+    /// `Yprv` prefix (SLIP-132: P2WSH nested in P2SH multisig)
+    pub const YPRV_MULTISIG: Self = Self::from_parts_unchecked("Yprv", 0x0295b005);
+
+    /// `Ypub` prefix (SLIP-132: P2WSH nested in P2SH multisig)
+    pub const YPUB_MULTISIG: Self = Self::from_parts_unchecked("Ypub", 0x0295b43f);
+
+    /// `Zprv` prefix (SLIP-132: P2WSH multisig)
+    pub const ZPRV_MULTISIG: Self = Self::from_parts_unchecked("Zprv", 0x02aa7a99);
+
+    /// `Zpub` prefix (SLIP-132: P2WSH multisig)
+    pub const ZPUB_MULTISIG: Self = Self::from_parts_unchecked("Zpub", 0x02aa7ed3);
+
+    /// `uprv` prefix (SLIP-132: testnet P2WPKH nested in P2SH)
+    pub const UPRV: Self = Self::from_parts_unchecked("uprv", 0x044a4e28);
+
+    /// `upub` prefix (SLIP-132: testnet P2WPKH nested in P2SH)
+    pub const UPUB: Self = Self::from_parts_unchecked("upub", 0x044a5262);
+
+    /// `Uprv` prefix (SLIP-132: testnet P2WSH nested in P2SH multisig)
+    pub const UPRV_MULTISIG: Self = Self::from_parts_unchecked("Uprv", 0x024285b5);
+
+    /// `Upub` prefix (SLIP-132: testnet P2WSH nested in P2SH multisig)
+    pub const UPUB_MULTISIG: Self = Self::from_parts_unchecked("Upub", 0x024289ef);
+
+    /// `vprv` prefix (SLIP-132: testnet P2WPKH)
+    pub const VPRV: Self = Self::from_parts_unchecked("vprv", 0x045f18bc);
+
+    /// `vpub` prefix (SLIP-132: testnet P2WPKH)
+    pub const VPUB: Self = Self::from_parts_unchecked("vpub", 0x045f1cf6);
+
+    /// `Vprv` prefix (SLIP-132: testnet P2WSH multisig)
+    pub const VPRV_MULTISIG: Self = Self::from_parts_unchecked("Vprv", 0x02575048);
+
+    /// `Vpub` prefix (SLIP-132: testnet P2WSH multisig)
+    pub const VPUB_MULTISIG: Self = Self::from_parts_unchecked("Vpub", 0x02575483);
+
     /// Create a new prefix from the given 4-character string and version number.
     /// The main intended use case for this function is [`Prefix`] constants
     /// such as [`Prefix::XPRV`].
@@ -77,11 +144,101 @@ impl Prefix {
         Self::from_version(Version::from_be_bytes(bytes))
     }
 
+    /// Register a pair of private and public prefixes for an additional
+    /// network, e.g. Litecoin's `Ltpv` and `Ltub`.
+    ///
+    /// Registered prefixes are recognized when parsing extended keys, and
+    /// are considered private or public keys regardless of their suffix.
+    ///
+    /// Returns [`Error::Decode`] if either prefix doesn't match its version
+    /// number, or [`Error::PrefixConflict`] if either version number is
+    /// already in use by a different prefix.
+    #[cfg(feature = "std")]
+    pub fn register(private: Prefix, public: Prefix) -> Result<()> {
+        for prefix in [private, public] {
+            if Self::encode_version(prefix.version)? != prefix {
+                return Err(Error::Decode);
+            }
+        }
+
+        if private.version == public.version {
+            return Err(Error::PrefixConflict);
+        }
+
+        let mut registered = REGISTERED_PREFIXES
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let pairs = KNOWN_PREFIXES.iter().chain(registered.iter());
+        for (known_private, known_public) in pairs {
+            if (known_private, known_public) == (&private, &public) {
+                // Already registered
+                return Ok(());
+            }
+
+            for known in [known_private, known_public] {
+                if known.version == private.version || known.version == public.version {
+                    return Err(Error::PrefixConflict);
+                }
+            }
+        }
+
+        registered.push((private, public));
+        Ok(())
+    }
+
     /// Parse a [`Prefix`] from a 32-bit integer "version", e.g.:
     ///
     /// - 0x0488B21E => `xpub`
     /// - 0x0488ADE4 => `xprv`
     fn from_version(version: Version) -> Result<Self> {
+        match Self::lookup(version) {
+            Some((prefix, _)) => Ok(prefix),
+            None => Self::encode_version(version),
+        }
+    }
+
+    /// Look up a known or registered prefix by its version number, along
+    /// with whether or not it is public.
+    pub(crate) fn lookup(version: Version) -> Option<(Self, bool)> {
+        Self::find(|prefix| prefix.version == version)
+    }
+
+    /// Find the first known or registered prefix matching the given
+    /// predicate, along with whether or not it is public.
+    ///
+    /// Known prefixes are checked first, so the registry is only locked if
+    /// none of them match.
+    fn find(predicate: impl Fn(&Prefix) -> bool) -> Option<(Self, bool)> {
+        let find = |pairs: &[(Prefix, Prefix)]| {
+            pairs.iter().find_map(|(private, public)| {
+                if predicate(private) {
+                    Some((*private, false))
+                } else if predicate(public) {
+                    Some((*public, true))
+                } else {
+                    None
+                }
+            })
+        };
+
+        if let Some(found) = find(KNOWN_PREFIXES) {
+            return Some(found);
+        }
+
+        #[cfg(feature = "std")]
+        return find(
+            &REGISTERED_PREFIXES
+                .read()
+                .unwrap_or_else(PoisonError::into_inner),
+        );
+
+        #[cfg(not(feature = "std"))]
+        None
+    }
+
+    /// Compute the prefix for a version number by Base58 encoding it.
+    fn encode_version(version: Version) -> Result<Self> {
         let mut bytes = [0u8; ExtendedKey::BYTE_SIZE];
         bytes[..4].copy_from_slice(&version.to_be_bytes());
 
@@ -99,13 +256,25 @@ impl Prefix {
     }
 
     /// Is this a public key?
+    ///
+    /// Prefixes which are neither known nor registered are public if they
+    /// end in `pub`.
     pub fn is_public(self) -> bool {
-        &self.chars[1..] == b"pub"
+        match Self::lookup(self.version) {
+            Some((_, public)) => public,
+            None => &self.chars[1..] == b"pub",
+        }
     }
 
     /// Is this a private key?
+    ///
+    /// Prefixes which are neither known nor registered are private if they
+    /// end in `prv`.
     pub fn is_private(self) -> bool {
-        &self.chars[1..] == b"prv"
+        match Self::lookup(self.version) {
+            Some((_, public)) => !public,
+            None => &self.chars[1..] == b"prv",
+        }
     }
 
     /// Get the [`Version`] number.
@@ -121,7 +290,7 @@ impl Prefix {
     /// Validate that the given prefix string is well-formed.
     // TODO(tarcieri): validate the string ends with `prv` or `pub`?
     pub(crate) const fn validate_str(s: &str) -> Result<&str> {
-        if s.as_bytes().len() != Self::LENGTH {
+        if s.len() != Self::LENGTH {
             return Err(Error::Decode);
         }
 
@@ -180,6 +349,18 @@ impl TryFrom<Version> for Prefix {
     }
 }
 
+/// Parse a known or registered prefix from its string representation,
+/// e.g. `xpub`.
+impl FromStr for Prefix {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Prefix> {
+        Self::find(|prefix| prefix.as_str() == s)
+            .map(|(prefix, _)| prefix)
+            .ok_or(Error::Decode)
+    }
+}
+
 impl TryFrom<&[u8]> for Prefix {
     type Error = Error;
 
@@ -188,6 +369,30 @@ impl TryFrom<&[u8]> for Prefix {
     }
 }
 
+/// Serialized as a string, e.g. `xpub`.
+#[cfg(feature = "serde")]
+impl Serialize for Prefix {
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Only known or registered prefixes can be deserialized.
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Prefix {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
 /// Debugging formatting helper for [`Version`] with a `Debug` impl that
 /// outputs hexadecimal instead of base 10.
 struct DebugVersion(Version);
@@ -224,4 +429,74 @@ mod tests {
         assert_eq!(Prefix::ZPUB, Prefix::try_from(0x04b24746).unwrap());
         assert_eq!(Prefix::ZPUB.as_str(), "zpub");
     }
+
+    #[test]
+    fn from_str() {
+        assert_eq!("xpub".parse::<Prefix>().unwrap(), Prefix::XPUB);
+        assert_eq!("Zprv".parse::<Prefix>().unwrap(), Prefix::ZPRV_MULTISIG);
+        assert!("abcd".parse::<Prefix>().is_err());
+    }
+
+    #[test]
+    fn slip132_constants() {
+        for (private, public) in super::KNOWN_PREFIXES {
+            assert_eq!(*private, Prefix::encode_version(private.version()).unwrap());
+            assert_eq!(*public, Prefix::encode_version(public.version()).unwrap());
+            assert!(private.is_private() && !private.is_public());
+            assert!(public.is_public() && !public.is_private());
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn register() {
+        use crate::{ChildNumber, Error, ExtendedKey, ExtendedKeyAttrs};
+        use alloc::string::ToString;
+
+        let ltpv = Prefix::from_parts_unchecked("Ltpv", 0x019d9cfe);
+        let ltub = Prefix::from_parts_unchecked("Ltub", 0x019da462);
+        assert!(!ltpv.is_private());
+
+        Prefix::register(ltpv, ltub).unwrap();
+        Prefix::register(ltpv, ltub).unwrap();
+        assert!(ltpv.is_private());
+        assert!(ltub.is_public());
+        assert_eq!(Prefix::try_from(0x019da462).unwrap(), ltub);
+
+        let xkey = ExtendedKey {
+            prefix: ltub,
+            attrs: ExtendedKeyAttrs {
+                depth: 0,
+                parent_fingerprint: [0; 4],
+                child_number: ChildNumber(0),
+                chain_code: [1; 32],
+            },
+            key_bytes: [2; 33],
+        };
+
+        let parsed = xkey.to_string().parse::<ExtendedKey>().unwrap();
+        assert!(parsed.to_string().starts_with("Ltub"));
+        assert_eq!(parsed.prefix, ltub);
+        assert!(parsed.prefix.is_public());
+
+        // Dogecoin prefixes
+        let dgpv = Prefix::from_parts_unchecked("dgpv", 0x02fac398);
+        let dgub = Prefix::from_parts_unchecked("dgub", 0x02facafd);
+
+        // Version numbers which don't match their prefix
+        let invalid = Prefix::from_parts_unchecked("dgpv", 0x02facafd);
+        assert_eq!(Prefix::register(invalid, ltub), Err(Error::Decode));
+
+        // Version numbers which are already in use
+        assert_eq!(
+            Prefix::register(dgpv, Prefix::XPUB),
+            Err(Error::PrefixConflict)
+        );
+        assert_eq!(Prefix::register(dgpv, ltub), Err(Error::PrefixConflict));
+        assert_eq!(Prefix::register(dgpv, dgpv), Err(Error::PrefixConflict));
+
+        Prefix::register(dgpv, dgub).unwrap();
+        assert!(dgpv.is_private());
+        assert!(dgub.is_public());
+    }
 }
@@ -14,6 +14,16 @@ pub trait PrivateKey: Sized {
     /// Public key type which corresponds to this private key.
     type PublicKey: PublicKey;
 
+    /// HMAC-SHA-512 key used to derive the master key from a seed.
+    ///
+    /// This is `"Bitcoin seed"` as specified in BIP32. SLIP-10 specifies a
+    /// different key for each curve.
+    const SEED_KEY: &'static [u8] = b"Bitcoin seed";
+
+    /// Retry derivations which produce an invalid key as specified in SLIP-10,
+    /// rather than returning an error.
+    const RETRY_INVALID_KEYS: bool = false;
+
     /// Initialize this key from bytes.
     fn from_bytes(bytes: &PrivateKeyBytes) -> Result<Self>;
 
@@ -42,28 +52,35 @@ pub trait PrivateKey: Sized {
         chain_code: &ChainCode,
         child_number: ChildNumber,
     ) -> Result<(PrivateKeyBytes, ChainCode)> {
-        let mut hmac = HmacSha512::new_from_slice(chain_code).map_err(|_| Error::Crypto)?;
-
+        // Note that at this point we are only asserting that the tweak has the expected size.
+        // Checking if it actually fits the curve scalar happens in `derive_child()`.
         if child_number.is_hardened() {
-            hmac.update(&[0]);
-            hmac.update(&self.to_bytes());
+            hmac_split(
+                chain_code,
+                &[&[0], &self.to_bytes(), &child_number.to_bytes()],
+            )
         } else {
-            hmac.update(&self.public_key().to_bytes());
+            hmac_split(
+                chain_code,
+                &[&self.public_key().to_bytes(), &child_number.to_bytes()],
+            )
         }
+    }
+}
 
-        hmac.update(&child_number.to_bytes());
-
-        let result = hmac.finalize().into_bytes();
-        let (tweak_bytes, chain_code_bytes) = result.split_at(KEY_SIZE);
-
-        // Note that at this point we are only asserting that `tweak_bytes` have the expected size.
-        // Checking if it actually fits the curve scalar happens in `derive_child()`.
-        let tweak = tweak_bytes.try_into()?;
-
-        let chain_code = chain_code_bytes.try_into()?;
+/// Compute `HMAC-SHA-512(key, data)` and split the result into its left half
+/// ("I sub L", i.e. a key or tweak) and right half ("I sub R", i.e. a chain
+/// code).
+pub(crate) fn hmac_split(key: &[u8], data: &[&[u8]]) -> Result<(PrivateKeyBytes, ChainCode)> {
+    let mut hmac = HmacSha512::new_from_slice(key).map_err(|_| Error::Crypto)?;
 
-        Ok((tweak, chain_code))
+    for bytes in data {
+        hmac.update(bytes);
     }
+
+    let result = hmac.finalize().into_bytes();
+    let (left, right) = result.split_at(KEY_SIZE);
+    Ok((left.try_into()?, right.try_into()?))
 }
 
 #[cfg(feature = "secp256k1")]
@@ -132,6 +149,64 @@ impl From<&XPrv> for k256::ecdsa::SigningKey {
     }
 }
 
+#[cfg(feature = "nistp256")]
+impl PrivateKey for p256::SecretKey {
+    type PublicKey = p256::PublicKey;
+
+    const SEED_KEY: &'static [u8] = b"Nist256p1 seed";
+    const RETRY_INVALID_KEYS: bool = true;
+
+    fn from_bytes(bytes: &PrivateKeyBytes) -> Result<Self> {
+        Ok(p256::SecretKey::from_slice(bytes)?)
+    }
+
+    fn to_bytes(&self) -> PrivateKeyBytes {
+        p256::SecretKey::to_bytes(self).into()
+    }
+
+    fn derive_child(&self, other: PrivateKeyBytes) -> Result<Self> {
+        let child_scalar =
+            Option::<p256::NonZeroScalar>::from(p256::NonZeroScalar::from_repr(other.into()))
+                .ok_or(Error::Crypto)?;
+
+        let derived_scalar = self.to_nonzero_scalar().as_ref() + child_scalar.as_ref();
+
+        Option::<p256::NonZeroScalar>::from(p256::NonZeroScalar::new(derived_scalar))
+            .map(Into::into)
+            .ok_or(Error::Crypto)
+    }
+
+    fn public_key(&self) -> Self::PublicKey {
+        p256::SecretKey::public_key(self)
+    }
+}
+
+#[cfg(feature = "nistp256")]
+impl PrivateKey for p256::ecdsa::SigningKey {
+    type PublicKey = p256::ecdsa::VerifyingKey;
+
+    const SEED_KEY: &'static [u8] = b"Nist256p1 seed";
+    const RETRY_INVALID_KEYS: bool = true;
+
+    fn from_bytes(bytes: &PrivateKeyBytes) -> Result<Self> {
+        Ok(p256::ecdsa::SigningKey::from_slice(bytes)?)
+    }
+
+    fn to_bytes(&self) -> PrivateKeyBytes {
+        p256::ecdsa::SigningKey::to_bytes(self).into()
+    }
+
+    fn derive_child(&self, other: PrivateKeyBytes) -> Result<Self> {
+        p256::SecretKey::from(self)
+            .derive_child(other)
+            .map(Into::into)
+    }
+
+    fn public_key(&self) -> Self::PublicKey {
+        *self.verifying_key()
+    }
+}
+
 #[cfg(feature = "secp256k1-ffi")]
 impl PrivateKey for secp256k1_ffi::SecretKey {
     type PublicKey = secp256k1_ffi::PublicKey;
@@ -171,7 +246,7 @@ mod tests {
         );
 
         let path = "m/0/2147483647'/1/2147483646'/2";
-        let xprv = XPrv::derive_from_path(&seed, &path.parse().unwrap()).unwrap();
+        let xprv = XPrv::derive_from_path(seed, &path.parse().unwrap()).unwrap();
 
         assert_eq!(
             xprv,
@@ -0,0 +1,950 @@
+//! Partially Signed Bitcoin Transactions (PSBTs) as specified in BIP174
+//! (version 0) and BIP370 (version 2).
+//!
+//! This is a minimal implementation intended for signing: PSBTs are kept as
+//! raw key-value maps so they can be re-serialized without losing any fields,
+//! and only the fields needed to compute signatures are interpreted.
+
+mod encoding;
+mod map;
+mod sighash;
+mod transaction;
+
+use self::{
+    encoding::Reader,
+    map::Map,
+    sighash::{SIGHASH_ALL, SIGHASH_DEFAULT},
+    transaction::{Transaction, TxIn, TxOut},
+};
+use crate::{ChildNumber, Error, KEY_SIZE, KeyFingerprint, PublicKey, Result, XPrv, taproot};
+use alloc::vec::Vec;
+use core::{
+    fmt::{self, Display},
+    str::{self, FromStr},
+};
+use k256::{ecdsa::signature::hazmat::PrehashSigner, schnorr};
+use rand_core::CryptoRngCore;
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+use subtle_encoding::base64;
+
+/// Magic bytes at the start of every PSBT.
+const MAGIC: &[u8; 5] = b"psbt\xff";
+
+/// Global map: unsigned transaction (version 0 only).
+const GLOBAL_UNSIGNED_TX: u8 = 0x00;
+
+/// Global map: transaction version (version 2 only).
+const GLOBAL_TX_VERSION: u8 = 0x02;
+
+/// Global map: lock time if no input requires one (version 2 only).
+const GLOBAL_FALLBACK_LOCKTIME: u8 = 0x03;
+
+/// Global map: number of inputs (version 2 only).
+const GLOBAL_INPUT_COUNT: u8 = 0x04;
+
+/// Global map: number of outputs (version 2 only).
+const GLOBAL_OUTPUT_COUNT: u8 = 0x05;
+
+/// Global map: transaction modifiable flags (version 2 only).
+const GLOBAL_TX_MODIFIABLE: u8 = 0x06;
+
+/// Global map: PSBT version number.
+const GLOBAL_VERSION: u8 = 0xfb;
+
+/// Input map: transaction containing the output being spent.
+const IN_NON_WITNESS_UTXO: u8 = 0x00;
+
+/// Input map: output being spent.
+const IN_WITNESS_UTXO: u8 = 0x01;
+
+/// Input map: ECDSA signature, keyed by public key.
+const IN_PARTIAL_SIG: u8 = 0x02;
+
+/// Input map: sighash type to sign with.
+const IN_SIGHASH_TYPE: u8 = 0x03;
+
+/// Input map: P2SH redeem script.
+const IN_REDEEM_SCRIPT: u8 = 0x04;
+
+/// Input map: P2WSH witness script.
+const IN_WITNESS_SCRIPT: u8 = 0x05;
+
+/// Input map: key origin of a public key.
+const IN_BIP32_DERIVATION: u8 = 0x06;
+
+/// Input map: ID of the transaction being spent (version 2 only).
+const IN_PREVIOUS_TXID: u8 = 0x0e;
+
+/// Input map: index of the output being spent (version 2 only).
+const IN_OUTPUT_INDEX: u8 = 0x0f;
+
+/// Input map: sequence number (version 2 only).
+const IN_SEQUENCE: u8 = 0x10;
+
+/// Input map: minimum time-based lock time (version 2 only).
+const IN_REQUIRED_TIME_LOCKTIME: u8 = 0x11;
+
+/// Input map: minimum height-based lock time (version 2 only).
+const IN_REQUIRED_HEIGHT_LOCKTIME: u8 = 0x12;
+
+/// Input map: Schnorr signature for key path spending.
+const IN_TAP_KEY_SIG: u8 = 0x13;
+
+/// Input map: Schnorr signature for script path spending, keyed by x-only
+/// public key and leaf hash.
+const IN_TAP_SCRIPT_SIG: u8 = 0x14;
+
+/// Input map: leaf hashes and key origin of an x-only public key.
+const IN_TAP_BIP32_DERIVATION: u8 = 0x16;
+
+/// Input map: Taproot internal key.
+const IN_TAP_INTERNAL_KEY: u8 = 0x17;
+
+/// Input map: Taproot script tree Merkle root.
+const IN_TAP_MERKLE_ROOT: u8 = 0x18;
+
+/// Output map: amount (version 2 only).
+const OUT_AMOUNT: u8 = 0x03;
+
+/// Output map: output script (version 2 only).
+const OUT_SCRIPT: u8 = 0x04;
+
+/// Partially Signed Bitcoin Transaction (PSBT).
+///
+/// Supports parsing and serializing version 0 (BIP174) and version 2
+/// (BIP370) PSBTs, as either bytes or Base64 strings, and signing their
+/// inputs with keys derived from a master [`XPrv`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Psbt {
+    /// Global map.
+    global: Map,
+
+    /// Input maps.
+    inputs: Vec<Map>,
+
+    /// Output maps.
+    outputs: Vec<Map>,
+}
+
+impl Psbt {
+    /// Parse a PSBT from bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut reader = Reader::new(bytes);
+
+        if reader.read_bytes(MAGIC.len())? != MAGIC {
+            return Err(Error::Psbt);
+        }
+
+        let global = Map::decode(&mut reader)?;
+
+        let (input_count, output_count) = match version(&global)? {
+            0 => {
+                let tx = unsigned_tx(&global)?;
+                (tx.inputs.len() as u64, tx.outputs.len() as u64)
+            }
+            _ => (
+                compact_size(&global, GLOBAL_INPUT_COUNT)?.ok_or(Error::Psbt)?,
+                compact_size(&global, GLOBAL_OUTPUT_COUNT)?.ok_or(Error::Psbt)?,
+            ),
+        };
+
+        let mut psbt = Psbt {
+            global,
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+        };
+
+        for _ in 0..input_count {
+            psbt.inputs.push(Map::decode(&mut reader)?);
+        }
+
+        for _ in 0..output_count {
+            psbt.outputs.push(Map::decode(&mut reader)?);
+        }
+
+        if !reader.is_empty() {
+            return Err(Error::Psbt);
+        }
+
+        psbt.validate()?;
+        Ok(psbt)
+    }
+
+    /// Serialize this PSBT as bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::from(*MAGIC);
+        self.global.encode(&mut bytes);
+
+        for map in self.inputs.iter().chain(&self.outputs) {
+            map.encode(&mut bytes);
+        }
+
+        bytes
+    }
+
+    /// Get the PSBT version number: either 0 or 2.
+    pub fn version(&self) -> u32 {
+        version(&self.global).expect("PSBT version validated")
+    }
+
+    /// Get the number of inputs.
+    pub fn input_count(&self) -> usize {
+        self.inputs.len()
+    }
+
+    /// Get the number of outputs.
+    pub fn output_count(&self) -> usize {
+        self.outputs.len()
+    }
+
+    /// Sign all inputs with keys derived from the given master key,
+    /// returning the number of signatures added.
+    ///
+    /// Keys are located using the key origins of inputs' public keys: keys
+    /// whose origin fingerprint matches the fingerprint of `master` are
+    /// derived along their origin derivation path and, if the derived public
+    /// key matches, used to sign.
+    ///
+    /// ECDSA signatures are added for legacy and SegWit v0 inputs, and
+    /// Schnorr signatures for Taproot inputs (key path spending if the key is
+    /// the internal key, and script path spending for any leaf hashes listed
+    /// with the key). The `rng` is used for Schnorr auxiliary randomness.
+    pub fn sign(&mut self, master: &XPrv, rng: &mut impl CryptoRngCore) -> Result<usize> {
+        let fingerprint = master.public_key().public_key().fingerprint();
+        let tx = self.unsigned_tx()?;
+
+        let spent_outputs = self
+            .inputs
+            .iter()
+            .zip(&tx.inputs)
+            .map(|(input, txin)| spent_output(input, txin))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut count = 0;
+
+        for index in 0..self.inputs.len() {
+            let signatures =
+                self.sign_input(index, &tx, &spent_outputs, master, fingerprint, rng)?;
+
+            count += signatures.len();
+
+            for (key, value) in signatures {
+                self.inputs[index].insert(key, value);
+            }
+        }
+
+        Ok(count)
+    }
+
+    /// Compute signatures for the input with the given index, returning the
+    /// keys and values of the fields to add to the input's map.
+    fn sign_input(
+        &self,
+        index: usize,
+        tx: &Transaction,
+        spent_outputs: &[Option<TxOut>],
+        master: &XPrv,
+        fingerprint: KeyFingerprint,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        let input = &self.inputs[index];
+        let sighash_type = u32_field(input, IN_SIGHASH_TYPE)?;
+        let mut signatures = Vec::new();
+
+        let script_pubkey = match &spent_outputs[index] {
+            Some(output) => output.script_pubkey.as_slice(),
+            None => &[],
+        };
+
+        if is_p2tr(script_pubkey) {
+            let sighash_type = sighash_type.unwrap_or(SIGHASH_DEFAULT);
+
+            for (x_only_key, value) in input.iter_type(IN_TAP_BIP32_DERIVATION) {
+                let mut reader = Reader::new(value);
+                let leaf_count = reader.read_compact_size()?;
+                let leaf_hashes = reader.read_bytes(
+                    usize::try_from(leaf_count)
+                        .ok()
+                        .and_then(|n| n.checked_mul(KEY_SIZE))
+                        .ok_or(Error::Psbt)?,
+                )?;
+
+                let Some(xprv) = derive_key(master, fingerprint, reader.remaining())? else {
+                    continue;
+                };
+
+                let secret_key = xprv.private_key().as_nonzero_scalar();
+                let public_key = k256::PublicKey::from_secret_scalar(secret_key);
+
+                if x_only_key != taproot::x_only(&public_key) {
+                    continue;
+                }
+
+                let merkle_root = input
+                    .get(IN_TAP_MERKLE_ROOT)
+                    .map(<&[u8; KEY_SIZE]>::try_from)
+                    .transpose()?;
+
+                // Key path spending: only sign if the tweaked key is the output key
+                if input.get(IN_TAP_INTERNAL_KEY) == Some(x_only_key)
+                    && taproot::x_only(&taproot::output_key(&public_key, merkle_root)?)
+                        == script_pubkey[2..]
+                {
+                    let signing_key = schnorr::SigningKey::from(taproot::tweak_secret_key(
+                        secret_key,
+                        merkle_root,
+                    )?);
+                    let sighash = sighash::taproot(tx, index, spent_outputs, sighash_type, None)?;
+                    let signature = schnorr_sign(&signing_key, &sighash, sighash_type, rng)?;
+                    signatures.push((Vec::from([IN_TAP_KEY_SIG]), signature));
+                }
+
+                let signing_key = schnorr::SigningKey::from(*secret_key);
+
+                for leaf_hash in leaf_hashes.chunks_exact(KEY_SIZE) {
+                    let leaf_hash = leaf_hash.try_into()?;
+                    let sighash =
+                        sighash::taproot(tx, index, spent_outputs, sighash_type, Some(leaf_hash))?;
+
+                    let mut key = Vec::from([IN_TAP_SCRIPT_SIG]);
+                    key.extend_from_slice(x_only_key);
+                    key.extend_from_slice(leaf_hash);
+
+                    let signature = schnorr_sign(&signing_key, &sighash, sighash_type, rng)?;
+                    signatures.push((key, signature));
+                }
+            }
+        } else {
+            let sighash_type = sighash_type.unwrap_or(SIGHASH_ALL);
+
+            for (public_key, value) in input.iter_type(IN_BIP32_DERIVATION) {
+                let Some(xprv) = derive_key(master, fingerprint, value)? else {
+                    continue;
+                };
+
+                if public_key != xprv.public_key().to_bytes() {
+                    continue;
+                }
+
+                let output = spent_outputs[index].as_ref().ok_or(Error::Psbt)?;
+                let sighash = ecdsa_sighash(input, tx, index, output, sighash_type)?;
+                let signature: k256::ecdsa::Signature =
+                    xprv.private_key().sign_prehash(&sighash)?;
+
+                let mut key = Vec::from([IN_PARTIAL_SIG]);
+                key.extend_from_slice(public_key);
+
+                let mut value = signature.normalize_s().to_der().as_bytes().to_vec();
+                value.push(sighash_type as u8);
+                signatures.push((key, value));
+            }
+        }
+
+        Ok(signatures)
+    }
+
+    /// Get the unsigned transaction.
+    fn unsigned_tx(&self) -> Result<Transaction> {
+        if version(&self.global)? == 0 {
+            return unsigned_tx(&self.global);
+        }
+
+        let mut tx = Transaction {
+            version: u32_field(&self.global, GLOBAL_TX_VERSION)?.ok_or(Error::Psbt)?,
+            lock_time: self.lock_time()?,
+            ..Default::default()
+        };
+
+        for input in &self.inputs {
+            tx.inputs.push(TxIn {
+                txid: input.get(IN_PREVIOUS_TXID).ok_or(Error::Psbt)?.try_into()?,
+                vout: u32_field(input, IN_OUTPUT_INDEX)?.ok_or(Error::Psbt)?,
+                script_sig: Vec::new(),
+                sequence: u32_field(input, IN_SEQUENCE)?.unwrap_or(u32::MAX),
+            });
+        }
+
+        for output in &self.outputs {
+            let amount = output.get(OUT_AMOUNT).ok_or(Error::Psbt)?;
+
+            tx.outputs.push(TxOut {
+                value: u64::from_le_bytes(amount.try_into()?),
+                script_pubkey: output.get(OUT_SCRIPT).ok_or(Error::Psbt)?.into(),
+            });
+        }
+
+        Ok(tx)
+    }
+
+    /// Determine the lock time of a version 2 PSBT as specified in BIP370.
+    fn lock_time(&self) -> Result<u32> {
+        let mut any_required = false;
+        let mut height_supported = true;
+        let mut time_supported = true;
+        let mut height = 0;
+        let mut time = 0;
+
+        for input in &self.inputs {
+            let required_height = u32_field(input, IN_REQUIRED_HEIGHT_LOCKTIME)?;
+            let required_time = u32_field(input, IN_REQUIRED_TIME_LOCKTIME)?;
+
+            if required_height.is_none() && required_time.is_none() {
+                continue;
+            }
+
+            any_required = true;
+
+            match required_height {
+                Some(h) => height = height.max(h),
+                None => height_supported = false,
+            }
+
+            match required_time {
+                Some(t) => time = time.max(t),
+                None => time_supported = false,
+            }
+        }
+
+        if !any_required {
+            Ok(u32_field(&self.global, GLOBAL_FALLBACK_LOCKTIME)?.unwrap_or(0))
+        } else if height_supported {
+            Ok(height)
+        } else if time_supported {
+            Ok(time)
+        } else {
+            Err(Error::Psbt)
+        }
+    }
+
+    /// Ensure the fields required by this PSBT's version are present, and the
+    /// fields of other versions are absent.
+    fn validate(&self) -> Result<()> {
+        let v2_global = [
+            GLOBAL_TX_VERSION,
+            GLOBAL_FALLBACK_LOCKTIME,
+            GLOBAL_INPUT_COUNT,
+            GLOBAL_OUTPUT_COUNT,
+            GLOBAL_TX_MODIFIABLE,
+        ];
+
+        let v2_input = [
+            IN_PREVIOUS_TXID,
+            IN_OUTPUT_INDEX,
+            IN_SEQUENCE,
+            IN_REQUIRED_TIME_LOCKTIME,
+            IN_REQUIRED_HEIGHT_LOCKTIME,
+        ];
+
+        let v2_output = [OUT_AMOUNT, OUT_SCRIPT];
+
+        if version(&self.global)? == 0 {
+            let tx = unsigned_tx(&self.global)?;
+
+            let invalid = tx.inputs.iter().any(|input| !input.script_sig.is_empty())
+                || has_any(&self.global, &v2_global)
+                || self.inputs.iter().any(|map| has_any(map, &v2_input))
+                || self.outputs.iter().any(|map| has_any(map, &v2_output));
+
+            if invalid {
+                return Err(Error::Psbt);
+            }
+        } else if self.global.get(GLOBAL_UNSIGNED_TX).is_some() {
+            return Err(Error::Psbt);
+        }
+
+        // Ensure all fields required to sign have valid encodings
+        self.unsigned_tx()?;
+        Ok(())
+    }
+}
+
+impl Display for Psbt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let encoded = base64::encode(self.to_bytes());
+        f.write_str(str::from_utf8(&encoded).map_err(|_| fmt::Error)?)
+    }
+}
+
+impl FromStr for Psbt {
+    type Err = Error;
+
+    /// Parse a Base64-encoded PSBT.
+    fn from_str(s: &str) -> Result<Self> {
+        let bytes = base64::decode(s).map_err(|_| Error::Psbt)?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+/// Get the PSBT version number from the global map.
+fn version(global: &Map) -> Result<u32> {
+    match u32_field(global, GLOBAL_VERSION)?.unwrap_or(0) {
+        version @ (0 | 2) => Ok(version),
+        _ => Err(Error::Psbt),
+    }
+}
+
+/// Parse the unsigned transaction from the global map of a version 0 PSBT.
+fn unsigned_tx(global: &Map) -> Result<Transaction> {
+    let tx = global.get(GLOBAL_UNSIGNED_TX).ok_or(Error::Psbt)?;
+    Transaction::decode(tx, false)
+}
+
+/// Get the output spent by an input, if known.
+fn spent_output(input: &Map, txin: &TxIn) -> Result<Option<TxOut>> {
+    if let Some(value) = input.get(IN_WITNESS_UTXO) {
+        let mut reader = Reader::new(value);
+        let output = TxOut::decode(&mut reader)?;
+
+        if !reader.is_empty() {
+            return Err(Error::Psbt);
+        }
+
+        return Ok(Some(output));
+    }
+
+    let Some(value) = input.get(IN_NON_WITNESS_UTXO) else {
+        return Ok(None);
+    };
+
+    let prev_tx = Transaction::decode(value, true)?;
+
+    if prev_tx.txid() != txin.txid {
+        return Err(Error::Psbt);
+    }
+
+    let vout = usize::try_from(txin.vout).map_err(|_| Error::Psbt)?;
+    prev_tx
+        .outputs
+        .get(vout)
+        .cloned()
+        .map(Some)
+        .ok_or(Error::Psbt)
+}
+
+/// Compute the ECDSA signature hash of a legacy or SegWit v0 input.
+fn ecdsa_sighash(
+    input: &Map,
+    tx: &Transaction,
+    index: usize,
+    spent_output: &TxOut,
+    sighash_type: u32,
+) -> Result<[u8; 32]> {
+    let mut script = spent_output.script_pubkey.as_slice();
+
+    if is_p2sh(script) {
+        let redeem_script = input.get(IN_REDEEM_SCRIPT).ok_or(Error::Psbt)?;
+
+        if hash160(redeem_script) != script[2..22] {
+            return Err(Error::Psbt);
+        }
+
+        script = redeem_script;
+    }
+
+    if is_p2wpkh(script) {
+        let mut script_code = Vec::from([0x76, 0xa9, 0x14]); // OP_DUP OP_HASH160 <20 bytes>
+        script_code.extend_from_slice(&script[2..]);
+        script_code.extend_from_slice(&[0x88, 0xac]); // OP_EQUALVERIFY OP_CHECKSIG
+        sighash::segwit_v0(tx, index, &script_code, spent_output.value, sighash_type)
+    } else if is_p2wsh(script) {
+        let witness_script = input.get(IN_WITNESS_SCRIPT).ok_or(Error::Psbt)?;
+
+        if Sha256::digest(witness_script).as_slice() != &script[2..] {
+            return Err(Error::Psbt);
+        }
+
+        sighash::segwit_v0(tx, index, witness_script, spent_output.value, sighash_type)
+    } else if is_witness_program(script) {
+        // Unknown witness version
+        Err(Error::Psbt)
+    } else {
+        // Legacy inputs must include the full transaction being spent
+        if input.get(IN_NON_WITNESS_UTXO).is_none() {
+            return Err(Error::Psbt);
+        }
+
+        sighash::legacy(tx, index, script, sighash_type)
+    }
+}
+
+/// Derive the key with the given serialized key origin, if its fingerprint
+/// matches the master key's.
+fn derive_key(master: &XPrv, fingerprint: KeyFingerprint, origin: &[u8]) -> Result<Option<XPrv>> {
+    if origin.len() < 4 || origin.len() % 4 != 0 {
+        return Err(Error::Psbt);
+    }
+
+    if origin[..4] != fingerprint {
+        return Ok(None);
+    }
+
+    let mut xprv = master.clone();
+
+    for child_number in origin[4..].chunks_exact(4) {
+        let child_number = ChildNumber(u32::from_le_bytes(child_number.try_into()?));
+        xprv = xprv.derive_child(child_number)?;
+    }
+
+    Ok(Some(xprv))
+}
+
+/// Compute a Schnorr signature, appending the sighash type unless it's
+/// `SIGHASH_DEFAULT`.
+fn schnorr_sign(
+    signing_key: &schnorr::SigningKey,
+    sighash: &[u8; 32],
+    sighash_type: u32,
+    rng: &mut impl CryptoRngCore,
+) -> Result<Vec<u8>> {
+    let mut aux_rand = [0u8; 32];
+    rng.fill_bytes(&mut aux_rand);
+
+    let mut signature = Vec::from(signing_key.sign_raw(sighash, &aux_rand)?.to_bytes());
+
+    if sighash_type != SIGHASH_DEFAULT {
+        signature.push(sighash_type as u8);
+    }
+
+    Ok(signature)
+}
+
+/// Get a little endian `u32` field.
+fn u32_field(map: &Map, key_type: u8) -> Result<Option<u32>> {
+    map.get(key_type)
+        .map(|value| Ok(u32::from_le_bytes(value.try_into()?)))
+        .transpose()
+}
+
+/// Get a CompactSize field.
+fn compact_size(map: &Map, key_type: u8) -> Result<Option<u64>> {
+    map.get(key_type)
+        .map(|value| {
+            let mut reader = Reader::new(value);
+            let n = reader.read_compact_size()?;
+
+            if reader.is_empty() {
+                Ok(n)
+            } else {
+                Err(Error::Psbt)
+            }
+        })
+        .transpose()
+}
+
+/// Does the map have any fields of the given types (with no key data)?
+fn has_any(map: &Map, key_types: &[u8]) -> bool {
+    key_types.iter().any(|t| map.get(*t).is_some())
+}
+
+/// Compute `RIPEMD160(SHA256(data))`.
+fn hash160(data: &[u8]) -> [u8; 20] {
+    Ripemd160::digest(Sha256::digest(data)).into()
+}
+
+/// Is this a P2SH output script?
+fn is_p2sh(script: &[u8]) -> bool {
+    // OP_HASH160 <20 bytes> OP_EQUAL
+    script.len() == 23 && script[0] == 0xa9 && script[1] == 0x14 && script[22] == 0x87
+}
+
+/// Is this a SegWit output script of any version?
+fn is_witness_program(script: &[u8]) -> bool {
+    // OP_0 or OP_1..OP_16, followed by a 2 to 40-byte push
+    (4..=42).contains(&script.len())
+        && (script[0] == 0x00 || (0x51..=0x60).contains(&script[0]))
+        && usize::from(script[1]) == script.len() - 2
+}
+
+/// Is this a P2WPKH output script?
+fn is_p2wpkh(script: &[u8]) -> bool {
+    script.len() == 22 && script[0] == 0x00 && script[1] == 0x14
+}
+
+/// Is this a P2WSH output script?
+fn is_p2wsh(script: &[u8]) -> bool {
+    script.len() == 34 && script[0] == 0x00 && script[1] == 0x20
+}
+
+/// Is this a P2TR output script?
+fn is_p2tr(script: &[u8]) -> bool {
+    script.len() == 34 && script[0] == 0x51 && script[1] == 0x20
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        GLOBAL_INPUT_COUNT, GLOBAL_OUTPUT_COUNT, GLOBAL_TX_VERSION, GLOBAL_UNSIGNED_TX,
+        GLOBAL_VERSION, IN_BIP32_DERIVATION, IN_OUTPUT_INDEX, IN_PARTIAL_SIG, IN_PREVIOUS_TXID,
+        IN_SEQUENCE, IN_TAP_BIP32_DERIVATION, IN_TAP_INTERNAL_KEY, IN_TAP_KEY_SIG, IN_WITNESS_UTXO,
+        Map, OUT_AMOUNT, OUT_SCRIPT, Psbt, SIGHASH_ALL, SIGHASH_DEFAULT, Transaction, TxIn, TxOut,
+        ecdsa_sighash, hash160, sighash, taproot,
+    };
+    use crate::{DerivationPath, XPrv};
+    use alloc::vec::Vec;
+    use hex_literal::hex;
+    use k256::{
+        ecdsa::{Signature, SigningKey, signature::hazmat::PrehashSigner},
+        schnorr,
+    };
+    use rand_core::OsRng;
+
+    /// BIP32 test vector 1 seed.
+    const SEED: [u8; 16] = hex!("000102030405060708090a0b0c0d0e0f");
+
+    /// Unsigned transaction from the BIP143 native P2WPKH example.
+    const BIP143_TX: [u8; 160] = hex!(
+        "0100000002fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f0000000000"
+        "eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ff"
+        "ffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d"
+        "000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac11000000"
+    );
+
+    /// Sign with the deterministic (RFC6979) nonces used by Bitcoin Core, and
+    /// compare against the signatures in the BIP143 example.
+    #[test]
+    fn bip143_signatures() {
+        let tx = Transaction::decode(&BIP143_TX, false).unwrap();
+
+        // Input 0: legacy P2PK
+        let signing_key = SigningKey::from_slice(&hex!(
+            "bbc27228ddcb9209d7fd6f36b02f7dfa6252af40bb2f1cbc7a557da8027ff866"
+        ))
+        .unwrap();
+
+        let script_pubkey =
+            hex!("2103c9f4836b9a4f77fc0d81f7bcb01b7f1b35916864b9476c241ce9fc198bd25432ac");
+        let sighash = sighash::legacy(&tx, 0, &script_pubkey, SIGHASH_ALL).unwrap();
+        let signature: Signature = signing_key.sign_prehash(&sighash).unwrap();
+
+        assert_eq!(
+            signature.normalize_s().to_der().as_bytes(),
+            hex!(
+                "30450221008b9d1dc26ba6a9cb62127b02742fa9d754cd3bebf337f7a55d114c8e5cdd30be0220"
+                "40529b194ba3f9281a99f2b1c0a19c0489bc22ede944ccf4ecbab4cc618ef3ed"
+            )
+        );
+
+        // Input 1: P2WPKH
+        let signing_key = SigningKey::from_slice(&hex!(
+            "619c335025c7f4012e556c2a58b2506e30b8511b53ade95ea316fd8c3286feb9"
+        ))
+        .unwrap();
+
+        let spent_output = TxOut {
+            value: 600_000_000,
+            script_pubkey: hex!("00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1").into(),
+        };
+
+        let sighash = ecdsa_sighash(&Map::default(), &tx, 1, &spent_output, SIGHASH_ALL).unwrap();
+        let signature: Signature = signing_key.sign_prehash(&sighash).unwrap();
+
+        assert_eq!(
+            signature.normalize_s().to_der().as_bytes(),
+            hex!(
+                "304402203609e17b84f6a7d30c80bfa610b5b4542f32a8a0d5447a12fb1366d7f01cc44a0220"
+                "573a954c4518331561406f90300e8f3358f51928d43c212a8caed02de67eebee"
+            )
+        );
+    }
+
+    /// Derive a key from [`SEED`], returning it along with its serialized
+    /// key origin.
+    fn derive(path: &str) -> (XPrv, Vec<u8>) {
+        let path = path.parse::<DerivationPath>().unwrap();
+        let xprv = XPrv::derive_from_path(SEED, &path).unwrap();
+
+        let mut origin = Vec::from(XPrv::new(SEED).unwrap().public_key().fingerprint());
+        for child_number in path.iter() {
+            origin.extend_from_slice(&child_number.0.to_le_bytes());
+        }
+
+        (xprv, origin)
+    }
+
+    /// Create a transaction with a P2WPKH input, a P2TR input, and an input
+    /// with a key from another wallet, and maps with their signing fields.
+    fn test_transaction() -> (Transaction, Vec<Map>) {
+        let (p2wpkh_key, p2wpkh_origin) = derive("m/84'/0'/0'/0/0");
+        let (p2tr_key, p2tr_origin) = derive("m/86'/0'/0'/0/0");
+
+        let p2wpkh_public_key = p2wpkh_key.public_key().to_bytes();
+        let p2tr_public_key =
+            k256::PublicKey::from_secret_scalar(p2tr_key.private_key().as_nonzero_scalar());
+        let p2tr_internal_key = taproot::x_only(&p2tr_public_key);
+
+        let mut p2wpkh_script = Vec::from([0x00, 0x14]);
+        p2wpkh_script.extend_from_slice(&hash160(&p2wpkh_public_key));
+
+        let mut p2tr_script = Vec::from([0x51, 0x20]);
+        p2tr_script.extend_from_slice(&taproot::x_only(
+            &taproot::output_key(&p2tr_public_key, None).unwrap(),
+        ));
+
+        let spent_outputs = [
+            TxOut {
+                value: 50_000,
+                script_pubkey: p2wpkh_script,
+            },
+            TxOut {
+                value: 70_000,
+                script_pubkey: p2tr_script,
+            },
+            TxOut {
+                value: 90_000,
+                script_pubkey: hex!("0014751e76e8199196d454941c45d1b3a323f1433bd6").into(),
+            },
+        ];
+
+        let tx = Transaction {
+            version: 2,
+            inputs: (0..3)
+                .map(|vout| TxIn {
+                    txid: [0x42; 32],
+                    vout,
+                    script_sig: Vec::new(),
+                    sequence: 0xffff_fffd,
+                })
+                .collect(),
+            outputs: Vec::from([TxOut {
+                value: 200_000,
+                script_pubkey: hex!("0014751e76e8199196d454941c45d1b3a323f1433bd6").into(),
+            }]),
+            lock_time: 0,
+        };
+
+        let mut inputs = Vec::new();
+        for output in &spent_outputs {
+            let mut witness_utxo = Vec::new();
+            output.encode(&mut witness_utxo);
+
+            let mut input = Map::default();
+            input.insert(Vec::from([IN_WITNESS_UTXO]), witness_utxo);
+            inputs.push(input);
+        }
+
+        let mut key = Vec::from([IN_BIP32_DERIVATION]);
+        key.extend_from_slice(&p2wpkh_public_key);
+        inputs[0].insert(key, p2wpkh_origin);
+
+        let mut key = Vec::from([IN_TAP_BIP32_DERIVATION]);
+        key.extend_from_slice(&p2tr_internal_key);
+        let mut value = Vec::from([0x00]); // no leaf hashes
+        value.extend_from_slice(&p2tr_origin);
+        inputs[1].insert(key, value);
+        inputs[1].insert(Vec::from([IN_TAP_INTERNAL_KEY]), p2tr_internal_key.into());
+
+        let mut key = Vec::from([IN_BIP32_DERIVATION]);
+        key.extend_from_slice(&hex!(
+            "0330d54fd0dd420a6e5f8d3624f5f3482cae350f79d5f0753bf5beef9c2d91af3c"
+        ));
+        inputs[2].insert(key, hex!("d34db33f00000000").into());
+
+        (tx, inputs)
+    }
+
+    /// Check the signatures added to a signed PSBT, returning the ECDSA
+    /// signature of the P2WPKH input.
+    fn check_signatures(psbt: &Psbt, tx: &Transaction) -> Vec<u8> {
+        let spent_outputs = psbt
+            .inputs
+            .iter()
+            .zip(&tx.inputs)
+            .map(|(input, txin)| super::spent_output(input, txin).unwrap())
+            .collect::<Vec<_>>();
+
+        // P2WPKH input
+        let (_, ecdsa_signature) = psbt.inputs[0].iter_type(IN_PARTIAL_SIG).next().unwrap();
+        let (ecdsa_signature, sighash_type) = ecdsa_signature.split_at(ecdsa_signature.len() - 1);
+        assert_eq!(sighash_type, [SIGHASH_ALL as u8]);
+
+        let (p2wpkh_key, _) = derive("m/84'/0'/0'/0/0");
+        let sighash = ecdsa_sighash(
+            &psbt.inputs[0],
+            tx,
+            0,
+            spent_outputs[0].as_ref().unwrap(),
+            SIGHASH_ALL,
+        )
+        .unwrap();
+        let signature: Signature = p2wpkh_key.private_key().sign_prehash(&sighash).unwrap();
+        assert_eq!(ecdsa_signature, signature.normalize_s().to_der().as_bytes());
+
+        // P2TR input
+        let schnorr_signature = psbt.inputs[1].get(IN_TAP_KEY_SIG).unwrap();
+        let output_key = schnorr::VerifyingKey::from_bytes(
+            &spent_outputs[1].as_ref().unwrap().script_pubkey[2..],
+        )
+        .unwrap();
+        let sighash = sighash::taproot(tx, 1, &spent_outputs, SIGHASH_DEFAULT, None).unwrap();
+        output_key
+            .verify_raw(
+                &sighash,
+                &schnorr::Signature::try_from(schnorr_signature).unwrap(),
+            )
+            .unwrap();
+
+        // Input with a key from another wallet
+        assert!(psbt.inputs[2].iter_type(IN_PARTIAL_SIG).next().is_none());
+
+        ecdsa_signature.into()
+    }
+
+    #[test]
+    fn sign() {
+        let (tx, inputs) = test_transaction();
+        let master = XPrv::new(SEED).unwrap();
+
+        // Version 0
+        let mut unsigned_tx = Vec::new();
+        tx.encode(&mut unsigned_tx);
+
+        let mut global = Map::default();
+        global.insert(Vec::from([GLOBAL_UNSIGNED_TX]), unsigned_tx);
+
+        let mut psbt_v0 = Psbt {
+            global,
+            inputs: inputs.clone(),
+            outputs: Vec::from([Map::default()]),
+        };
+
+        psbt_v0.validate().unwrap();
+        assert_eq!(psbt_v0.sign(&master, &mut OsRng).unwrap(), 2);
+        let signature_v0 = check_signatures(&psbt_v0, &tx);
+
+        // Version 2
+        let mut global = Map::default();
+        global.insert(Vec::from([GLOBAL_TX_VERSION]), 2u32.to_le_bytes().into());
+        global.insert(Vec::from([GLOBAL_INPUT_COUNT]), Vec::from([3]));
+        global.insert(Vec::from([GLOBAL_OUTPUT_COUNT]), Vec::from([1]));
+        global.insert(Vec::from([GLOBAL_VERSION]), 2u32.to_le_bytes().into());
+
+        let mut inputs = inputs;
+        for (input, txin) in inputs.iter_mut().zip(&tx.inputs) {
+            input.insert(Vec::from([IN_PREVIOUS_TXID]), txin.txid.into());
+            input.insert(Vec::from([IN_OUTPUT_INDEX]), txin.vout.to_le_bytes().into());
+            input.insert(Vec::from([IN_SEQUENCE]), txin.sequence.to_le_bytes().into());
+        }
+
+        let mut output = Map::default();
+        output.insert(
+            Vec::from([OUT_AMOUNT]),
+            tx.outputs[0].value.to_le_bytes().into(),
+        );
+        output.insert(Vec::from([OUT_SCRIPT]), tx.outputs[0].script_pubkey.clone());
+
+        let mut psbt_v2 = Psbt {
+            global,
+            inputs,
+            outputs: Vec::from([output]),
+        };
+
+        psbt_v2.validate().unwrap();
+        assert_eq!(psbt_v2.unsigned_tx().unwrap(), tx);
+        assert_eq!(psbt_v2.sign(&master, &mut OsRng).unwrap(), 2);
+        assert_eq!(check_signatures(&psbt_v2, &tx), signature_v0);
+
+        // Signed PSBTs round trip
+        assert_eq!(Psbt::from_bytes(&psbt_v2.to_bytes()).unwrap(), psbt_v2);
+    }
+}
@@ -0,0 +1,111 @@
+//! Bitcoin consensus encoding primitives.
+
+use crate::{Error, Result};
+use alloc::vec::Vec;
+
+/// Reader for consensus-encoded data.
+pub(super) struct Reader<'a> {
+    /// Remaining bytes.
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    /// Create a new reader for the given bytes.
+    pub(super) fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    /// Have all bytes been read?
+    pub(super) fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Get the remaining bytes, consuming the reader.
+    pub(super) fn remaining(self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Peek at the next byte without consuming it.
+    pub(super) fn peek(&self) -> Option<u8> {
+        self.bytes.first().copied()
+    }
+
+    /// Read the given number of bytes.
+    pub(super) fn read_bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        if len > self.bytes.len() {
+            return Err(Error::Psbt);
+        }
+
+        let (bytes, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(bytes)
+    }
+
+    /// Read a fixed-size array of bytes.
+    pub(super) fn read_array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        Ok(self.read_bytes(N)?.try_into()?)
+    }
+
+    /// Read a single byte.
+    pub(super) fn read_u8(&mut self) -> Result<u8> {
+        Ok(self.read_array::<1>()?[0])
+    }
+
+    /// Read a little endian `u32`.
+    pub(super) fn read_u32(&mut self) -> Result<u32> {
+        self.read_array().map(u32::from_le_bytes)
+    }
+
+    /// Read a little endian `u64`.
+    pub(super) fn read_u64(&mut self) -> Result<u64> {
+        self.read_array().map(u64::from_le_bytes)
+    }
+
+    /// Read a variable length integer ("CompactSize").
+    pub(super) fn read_compact_size(&mut self) -> Result<u64> {
+        let (value, min) = match self.read_u8()? {
+            0xfd => (u64::from(u16::from_le_bytes(self.read_array()?)), 0xfd),
+            0xfe => (u64::from(self.read_u32()?), 0x1_0000),
+            0xff => (self.read_u64()?, 0x1_0000_0000),
+            n => return Ok(u64::from(n)),
+        };
+
+        // Reject non-canonical encodings
+        if value < min {
+            return Err(Error::Psbt);
+        }
+
+        Ok(value)
+    }
+
+    /// Read a length-prefixed byte string.
+    pub(super) fn read_var_bytes(&mut self) -> Result<&'a [u8]> {
+        let len = self.read_compact_size()?;
+        self.read_bytes(usize::try_from(len).map_err(|_| Error::Psbt)?)
+    }
+}
+
+/// Write a variable length integer ("CompactSize").
+pub(super) fn write_compact_size(out: &mut Vec<u8>, value: u64) {
+    match value {
+        0..=0xfc => out.push(value as u8),
+        0xfd..=0xffff => {
+            out.push(0xfd);
+            out.extend_from_slice(&(value as u16).to_le_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            out.push(0xfe);
+            out.extend_from_slice(&(value as u32).to_le_bytes());
+        }
+        _ => {
+            out.push(0xff);
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+}
+
+/// Write a length-prefixed byte string.
+pub(super) fn write_var_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    write_compact_size(out, bytes.len() as u64);
+    out.extend_from_slice(bytes);
+}
@@ -0,0 +1,78 @@
+//! PSBT key-value maps.
+
+use super::encoding::{Reader, write_var_bytes};
+use crate::{Error, Result};
+use alloc::vec::Vec;
+
+/// Key-value map, i.e. the global map or the map of an input or output.
+///
+/// Entries are kept in the order they were parsed or inserted in so PSBTs
+/// are re-serialized exactly as they were received, including fields which
+/// aren't otherwise understood by this crate.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(super) struct Map {
+    /// Raw keys (including the key type) and values.
+    entries: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl Map {
+    /// Parse a map, consuming its terminating `0x00` separator.
+    pub(super) fn decode(reader: &mut Reader<'_>) -> Result<Self> {
+        let mut map = Map::default();
+
+        loop {
+            let key = reader.read_var_bytes()?;
+
+            if key.is_empty() {
+                return Ok(map);
+            }
+
+            let value = reader.read_var_bytes()?;
+
+            if map.get_raw(key).is_some() {
+                return Err(Error::Psbt);
+            }
+
+            map.entries.push((key.into(), value.into()));
+        }
+    }
+
+    /// Serialize this map, including its terminating `0x00` separator.
+    pub(super) fn encode(&self, out: &mut Vec<u8>) {
+        for (key, value) in &self.entries {
+            write_var_bytes(out, key);
+            write_var_bytes(out, value);
+        }
+
+        out.push(0x00);
+    }
+
+    /// Get the value of a field whose key is only its type.
+    pub(super) fn get(&self, key_type: u8) -> Option<&[u8]> {
+        self.get_raw(&[key_type])
+    }
+
+    /// Get the value for the given raw key.
+    pub(super) fn get_raw(&self, key: &[u8]) -> Option<&[u8]> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_slice())
+    }
+
+    /// Iterate over the key data and values of all fields of a given type.
+    pub(super) fn iter_type(&self, key_type: u8) -> impl Iterator<Item = (&[u8], &[u8])> {
+        self.entries
+            .iter()
+            .filter(move |(key, _)| key.first() == Some(&key_type))
+            .map(|(key, value)| (&key[1..], value.as_slice()))
+    }
+
+    /// Insert a field, replacing any existing field with the same key.
+    pub(super) fn insert(&mut self, key: Vec<u8>, value: Vec<u8>) {
+        match self.entries.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, v)) => *v = value,
+            None => self.entries.push((key, value)),
+        }
+    }
+}
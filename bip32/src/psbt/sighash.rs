@@ -0,0 +1,489 @@
+//! Signature hashes for legacy (pre-SegWit), SegWit v0 (BIP143), and
+//! Taproot (BIP341) inputs.
+
+use super::{
+    encoding::write_var_bytes,
+    transaction::{Transaction, TxOut, sha256d},
+};
+use crate::{Error, Result, taproot::tagged_hash};
+use alloc::vec::Vec;
+use sha2::{Digest, Sha256};
+
+/// Taproot-only sighash type which signs everything, like `SIGHASH_ALL`.
+pub(super) const SIGHASH_DEFAULT: u32 = 0x00;
+
+/// Sign all inputs and outputs.
+pub(super) const SIGHASH_ALL: u32 = 0x01;
+
+/// Sign all inputs, but no outputs.
+const SIGHASH_NONE: u32 = 0x02;
+
+/// Sign all inputs, and the output with the same index as the input.
+const SIGHASH_SINGLE: u32 = 0x03;
+
+/// Only sign the input being signed (combined with one of the above).
+const SIGHASH_ANYONECANPAY: u32 = 0x80;
+
+/// Compute the signature hash of a legacy (pre-SegWit) input.
+///
+/// Returns an error for `SIGHASH_SINGLE` without a corresponding output,
+/// rather than signing the constant `1` as consensus rules do.
+pub(super) fn legacy(
+    tx: &Transaction,
+    index: usize,
+    script_code: &[u8],
+    sighash_type: u32,
+) -> Result<[u8; 32]> {
+    let base_type = sighash_type & 0x1f;
+    let anyone_can_pay = sighash_type & SIGHASH_ANYONECANPAY != 0;
+
+    if index >= tx.inputs.len() || (base_type == SIGHASH_SINGLE && index >= tx.outputs.len()) {
+        return Err(Error::Psbt);
+    }
+
+    let mut tx_copy = Transaction {
+        version: tx.version,
+        inputs: Vec::new(),
+        outputs: Vec::new(),
+        lock_time: tx.lock_time,
+    };
+
+    for (i, input) in tx.inputs.iter().enumerate() {
+        if anyone_can_pay && i != index {
+            continue;
+        }
+
+        let mut input = input.clone();
+
+        if i == index {
+            input.script_sig = script_code.into();
+        } else {
+            input.script_sig.clear();
+
+            if base_type == SIGHASH_NONE || base_type == SIGHASH_SINGLE {
+                input.sequence = 0;
+            }
+        }
+
+        tx_copy.inputs.push(input);
+    }
+
+    match base_type {
+        SIGHASH_NONE => (),
+        SIGHASH_SINGLE => {
+            for _ in 0..index {
+                tx_copy.outputs.push(TxOut {
+                    value: u64::MAX,
+                    script_pubkey: Vec::new(),
+                });
+            }
+
+            tx_copy.outputs.push(tx.outputs[index].clone());
+        }
+        _ => tx_copy.outputs.clone_from(&tx.outputs),
+    }
+
+    let mut preimage = Vec::new();
+    tx_copy.encode(&mut preimage);
+    preimage.extend_from_slice(&sighash_type.to_le_bytes());
+    Ok(sha256d(&preimage))
+}
+
+/// Compute the signature hash of a SegWit v0 input as specified in BIP143.
+pub(super) fn segwit_v0(
+    tx: &Transaction,
+    index: usize,
+    script_code: &[u8],
+    value: u64,
+    sighash_type: u32,
+) -> Result<[u8; 32]> {
+    let base_type = sighash_type & 0x1f;
+    let anyone_can_pay = sighash_type & SIGHASH_ANYONECANPAY != 0;
+    let input = tx.inputs.get(index).ok_or(Error::Psbt)?;
+
+    let mut hash_prevouts = [0u8; 32];
+    let mut hash_sequence = [0u8; 32];
+    let mut hash_outputs = [0u8; 32];
+
+    if !anyone_can_pay {
+        hash_prevouts = sha256d(&prevouts(tx));
+
+        if base_type != SIGHASH_SINGLE && base_type != SIGHASH_NONE {
+            hash_sequence = sha256d(&sequences(tx));
+        }
+    }
+
+    if base_type != SIGHASH_SINGLE && base_type != SIGHASH_NONE {
+        hash_outputs = sha256d(&outputs(&tx.outputs));
+    } else if base_type == SIGHASH_SINGLE && index < tx.outputs.len() {
+        hash_outputs = sha256d(&outputs(&tx.outputs[index..=index]));
+    }
+
+    let mut preimage = Vec::new();
+    preimage.extend_from_slice(&tx.version.to_le_bytes());
+    preimage.extend_from_slice(&hash_prevouts);
+    preimage.extend_from_slice(&hash_sequence);
+    input.encode_outpoint(&mut preimage);
+    write_var_bytes(&mut preimage, script_code);
+    preimage.extend_from_slice(&value.to_le_bytes());
+    preimage.extend_from_slice(&input.sequence.to_le_bytes());
+    preimage.extend_from_slice(&hash_outputs);
+    preimage.extend_from_slice(&tx.lock_time.to_le_bytes());
+    preimage.extend_from_slice(&sighash_type.to_le_bytes());
+    Ok(sha256d(&preimage))
+}
+
+/// Compute the signature hash of a Taproot input as specified in BIP341,
+/// for key path spending or, if a leaf hash is given, script path spending.
+///
+/// The outputs spent by all inputs must be known, unless the sighash type
+/// is `SIGHASH_ANYONECANPAY`.
+pub(super) fn taproot(
+    tx: &Transaction,
+    index: usize,
+    spent_outputs: &[Option<TxOut>],
+    sighash_type: u32,
+    leaf_hash: Option<&[u8; 32]>,
+) -> Result<[u8; 32]> {
+    if !matches!(sighash_type, 0x00..=0x03 | 0x81..=0x83) {
+        return Err(Error::Psbt);
+    }
+
+    let base_type = sighash_type & 0x03;
+    let anyone_can_pay = sighash_type & SIGHASH_ANYONECANPAY != 0;
+    let input = tx.inputs.get(index).ok_or(Error::Psbt)?;
+
+    let mut msg = Vec::new();
+    msg.push(0x00); // epoch
+    msg.push(sighash_type as u8);
+    msg.extend_from_slice(&tx.version.to_le_bytes());
+    msg.extend_from_slice(&tx.lock_time.to_le_bytes());
+
+    if !anyone_can_pay {
+        let spent_outputs = spent_outputs
+            .iter()
+            .map(|output| output.as_ref().ok_or(Error::Psbt))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut amounts = Vec::new();
+        let mut script_pubkeys = Vec::new();
+
+        for output in spent_outputs {
+            amounts.extend_from_slice(&output.value.to_le_bytes());
+            write_var_bytes(&mut script_pubkeys, &output.script_pubkey);
+        }
+
+        msg.extend_from_slice(&Sha256::digest(prevouts(tx)));
+        msg.extend_from_slice(&Sha256::digest(amounts));
+        msg.extend_from_slice(&Sha256::digest(script_pubkeys));
+        msg.extend_from_slice(&Sha256::digest(sequences(tx)));
+    }
+
+    if base_type != SIGHASH_NONE && base_type != SIGHASH_SINGLE {
+        msg.extend_from_slice(&Sha256::digest(outputs(&tx.outputs)));
+    }
+
+    // Annexes are not supported
+    let ext_flag = if leaf_hash.is_some() { 1 } else { 0 };
+    msg.push(ext_flag * 2);
+
+    if anyone_can_pay {
+        let spent_output = spent_outputs
+            .get(index)
+            .and_then(Option::as_ref)
+            .ok_or(Error::Psbt)?;
+
+        input.encode_outpoint(&mut msg);
+        spent_output.encode(&mut msg);
+        msg.extend_from_slice(&input.sequence.to_le_bytes());
+    } else {
+        msg.extend_from_slice(&(index as u32).to_le_bytes());
+    }
+
+    if base_type == SIGHASH_SINGLE {
+        let output = tx.outputs.get(index).ok_or(Error::Psbt)?;
+        msg.extend_from_slice(&Sha256::digest(outputs(core::slice::from_ref(output))));
+    }
+
+    if let Some(leaf_hash) = leaf_hash {
+        msg.extend_from_slice(leaf_hash);
+        msg.push(0x00); // key version
+        msg.extend_from_slice(&u32::MAX.to_le_bytes()); // no OP_CODESEPARATOR
+    }
+
+    Ok(tagged_hash(b"TapSighash", &[&msg]))
+}
+
+/// Serialize the outpoints of all inputs.
+fn prevouts(tx: &Transaction) -> Vec<u8> {
+    let mut bytes = Vec::new();
+
+    for input in &tx.inputs {
+        input.encode_outpoint(&mut bytes);
+    }
+
+    bytes
+}
+
+/// Serialize the sequence numbers of all inputs.
+fn sequences(tx: &Transaction) -> Vec<u8> {
+    tx.inputs
+        .iter()
+        .flat_map(|input| input.sequence.to_le_bytes())
+        .collect()
+}
+
+/// Serialize the given outputs.
+fn outputs(outputs: &[TxOut]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+
+    for output in outputs {
+        output.encode(&mut bytes);
+    }
+
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SIGHASH_ALL, segwit_v0, taproot};
+    use crate::psbt::{
+        encoding::Reader,
+        transaction::{Transaction, TxOut},
+    };
+    use alloc::vec::Vec;
+    use hex_literal::hex;
+
+    /// BIP143 test vector: native P2WPKH
+    #[test]
+    fn bip143_p2wpkh() {
+        let tx = Transaction::decode(
+            &hex!(
+                "0100000002fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f000000"
+                "0000eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a01000000"
+                "00ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093"
+                "510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac11000000"
+            ),
+            true,
+        )
+        .unwrap();
+
+        let script_code = hex!("76a9141d0f172a0ecb48aee1be1f2687d2963ae33f71a188ac");
+        assert_eq!(
+            segwit_v0(&tx, 1, &script_code, 600_000_000, SIGHASH_ALL).unwrap(),
+            hex!("c37af31116d1b27caf68aae9e3ac82f1477929014d5b917657d0eb49478cb670")
+        );
+    }
+
+    /// BIP143 test vector: P2SH-P2WPKH
+    #[test]
+    fn bip143_p2sh_p2wpkh() {
+        let tx = Transaction::decode(
+            &hex!(
+                "0100000001db6b1b20aa0fd7b23880be2ecbd4a98130974cf4748fb66092ac4d3ceb1a5477010000"
+                "0000feffffff02b8b4eb0b000000001976a914a457b684d7f0d539a46a45bbc043f35b59d0d96388ac00"
+                "08af2f000000001976a914fd270b1ee6abcaea97fea7ad0402e8bd8ad6d77c88ac92040000"
+            ),
+            true,
+        )
+        .unwrap();
+
+        let script_code = hex!("76a91479091972186c449eb1ded22b78e40d009bdf008988ac");
+        assert_eq!(
+            segwit_v0(&tx, 0, &script_code, 1_000_000_000, SIGHASH_ALL).unwrap(),
+            hex!("64f3b0f4dd2bb3aa1ce8566d220cc74dda9df97d8490cc81d89d735c92e59fb6")
+        );
+    }
+
+    /// BIP143 test vector: P2SH-P2WSH with all sighash types
+    #[test]
+    fn bip143_p2sh_p2wsh() {
+        let tx = Transaction::decode(
+            &hex!(
+                "010000000136641869ca081e70f394c6948e8af409e18b619df2ed74aa106c1ca29787b96e0100000000"
+                "ffffffff0200e9a435000000001976a914389ffce9cd9ae88dcc0631e88a821ffdbe9bfe2688acc0832f"
+                "05000000001976a9147480a33f950689af511e6e84c138dbbd3c3ee41588ac00000000"
+            ),
+            true,
+        )
+        .unwrap();
+
+        let witness_script = hex!(
+            "56210307b8ae49ac90a048e9b53357a2354b3334e9c8bee813ecb98e99a7e07e8c3ba32103b28f0c28"
+            "bfab54554ae8c658ac5c3e0ce6e79ad336331f78c428dd43eea8449b21034b8113d703413d57761b8b"
+            "9781957b8c0ac1dfe69f492580ca4195f50376ba4a21033400f6afecb833092a9a21cfdf1ed1376e58"
+            "c5d1f47de74683123987e967a8f42103a6d48b1131e94ba04d9737d61acdaa1322008af9602b3b1486"
+            "2c07a1789aac162102d8b661b0b3302ee2f162b09e07a55ad5dfbe673a9f01d9f0c19617681024306b"
+            "56ae"
+        );
+
+        for (sighash_type, expected) in [
+            (
+                0x01,
+                hex!("185c0be5263dce5b4bb50a047973c1b6272bfbd0103a89444597dc40b248ee7c"),
+            ),
+            (
+                0x02,
+                hex!("e9733bc60ea13c95c6527066bb975a2ff29a925e80aa14c213f686cbae5d2f36"),
+            ),
+            (
+                0x03,
+                hex!("1e1f1c303dc025bd664acb72e583e933fae4cff9148bf78c157d1e8f78530aea"),
+            ),
+            (
+                0x81,
+                hex!("2a67f03e63a6a422125878b40b82da593be8d4efaafe88ee528af6e5a9955c6e"),
+            ),
+            (
+                0x82,
+                hex!("781ba15f3779d5542ce8ecb5c18716733a5ee42a6f51488ec96154934e2c890a"),
+            ),
+            (
+                0x83,
+                hex!("511e8e52ed574121fc1b654970395502128263f62662e076dc6baf05c2e6a99b"),
+            ),
+        ] {
+            assert_eq!(
+                segwit_v0(&tx, 0, &witness_script, 987_654_321, sighash_type).unwrap(),
+                expected,
+                "sighash type {sighash_type:#04x}"
+            );
+        }
+    }
+
+    /// Taproot signature hash test vector.
+    struct TaprootVector {
+        tx: &'static [u8],
+        spent_outputs: &'static [u8],
+        index: usize,
+        sighash_type: u32,
+        leaf_hash: Option<[u8; 32]>,
+        sighash: [u8; 32],
+    }
+
+    /// Taproot test vectors from the Bitcoin Core test framework.
+    const TAPROOT_VECTORS: &[TaprootVector] = &[
+        TaprootVector {
+            tx: &hex!(
+                "020000000164eb050a5e3da0c2a65e4786f26d753b7bc69691fabccafb11f7acef36641f1846010000003101b2b404392a22000000000017a9147f2bde86fe78bf68a0544a4f290e12f0b7e0a08c87580200000000000017a91425d11723074ecfb96a0a83c3956bfaf362ae0c908758020000000000001600147e20f938993641de67bb0cdd71682aa34c4d29ad5802000000000000160014c64984dc8761acfa99418bd6bedc79b9287d652d72000000"
+            ),
+            spent_outputs: &hex!(
+                "01365724000000000023542156b39dab4f8f3508e0432cfb41fab110170acaa2d4c42539cb90a4dc7c093bc500"
+            ),
+            index: 0,
+            sighash_type: 0x00,
+            leaf_hash: None,
+            sighash: hex!("33ca0ebfb4a945eeee9569fc0f5040221275f88690b7f8592ada88ce3bdf6703"),
+        },
+        TaprootVector {
+            tx: &hex!(
+                "0200000002fff49be59befe7566050737910f6ccdc5e749c7f8860ddc140386463d88c5ad0f3000000002cf68eb4a3d67f9d4c079249f7e4f27b8854815cb1ed13842d4fbf395f9e217fd605ee24090100000065235d9203f458520000000000160014b6d48333bb13b4c644e57c43a9a26df3a44b785e58020000000000001976a914eea9461a9e1e3f765d3af3e726162e0229fe3eb688ac58020000000000001976a9143a8869c9f2b5ea1d4ff3aeeb6a8fb2fffb1ad5fe88ac0ad7125c"
+            ),
+            spent_outputs: &hex!(
+                "02591f220000000000225120f25ad35583ea31998d968871d7de1abd2a52f6fe4178b54ea158274806ff4ece48fb310000000000225120f25ad35583ea31998d968871d7de1abd2a52f6fe4178b54ea158274806ff4ece"
+            ),
+            index: 1,
+            sighash_type: 0x01,
+            leaf_hash: None,
+            sighash: hex!("626ab955d58c9a8a600a0c580549d06dc7da4e802eb2a531f62a588e430967a8"),
+        },
+        TaprootVector {
+            tx: &hex!(
+                "0200000001350005f65aa830ced2079df348e2d8c2bdb4f10e2dde6a161d8a07b40d1ad87dae000000001611d0d603d9dc0e000000000017a914459b6d7d6bbb4d8837b4bf7e9a4556f952da2f5c8758020000000000001976a9141dd70e1299ffc2d5b51f6f87de9dfe9398c33cbb88ac58020000000000001976a9141dd70e1299ffc2d5b51f6f87de9dfe9398c33cbb88aca71c1f4f"
+            ),
+            spent_outputs: &hex!(
+                "01c4811000000000002251201bf9297d0a2968ae6693aadd0fa514717afefd218087a239afb7418e2d22e65c"
+            ),
+            index: 0,
+            sighash_type: 0x81,
+            leaf_hash: None,
+            sighash: hex!("dfa9437f9c9a1d1f9af271f79f2f5482f287cdb0d2e03fa92c8a9b216cc6061c"),
+        },
+        TaprootVector {
+            tx: &hex!(
+                "020000000185bed1a6da2bffbd60ec681a1bfb71c5111d6395b99b3f8b2bf90167111bcb18f5010000007c83ace802ded24a00000000001600142c4698f9f7a773866879755aa78c516fb332af8e5802000000000000160014d38639dfbac4259323b98a472405db0c461b31fa61073747"
+            ),
+            spent_outputs: &hex!(
+                "0144c84d0000000000225120e3f2107989c88e67296ab2faca930efa2e3a5bd3ff0904835a11c9e807458621"
+            ),
+            index: 0,
+            sighash_type: 0x02,
+            leaf_hash: None,
+            sighash: hex!("3129de36a5d05fff97ffca31eb75fcccbbbc27b3147a7a36a9e4b45d8b625067"),
+        },
+        TaprootVector {
+            tx: &hex!(
+                "eb93dbb901028c8515589dac980b6e7f8e4088b77ed866ca0d6d210a7218b6fd0f6b22dd6d7300000000eb4740a9047efc0e0000000000160014913da2128d8fcf292b3691db0e187414aa1783825802000000000000160014913da2128d8fcf292b3691db0e187414aa178382580200000000000017a9143dd27f01c6f7ef9bb9159937b17f17065ed01a0c875802000000000000160014d7630e19df70ada9905ede1722b800c0005f246641000000"
+            ),
+            spent_outputs: &hex!(
+                "013fed110000000000225120eb536ae8c33580290630fc495046e998086a64f8f33b93b07967d9029b265c55"
+            ),
+            index: 0,
+            sighash_type: 0x82,
+            leaf_hash: None,
+            sighash: hex!("2441e8b0e063a2083ee790f14f2045022f07258ddde5ee01de543c9e789d80ae"),
+        },
+        TaprootVector {
+            tx: &hex!(
+                "02000000017836b409a5fed32211407e44b971591f2032053f14701fb5b3a30c0ff382f2cc9c0100000061ac55f60288fb5600000000001976a9144ea02f6f182b082fb6ce47e36bbde390b6a41b5088ac58020000000000001976a9144ea02f6f182b082fb6ce47e36bbde390b6a41b5088ace4000000"
+            ),
+            spent_outputs: &hex!(
+                "01efa558000000000022512007071ea3dc7e331b0687d0193d1e6d6ed10e645ef36f10ef8831d5e522ac9e80"
+            ),
+            index: 0,
+            sighash_type: 0x03,
+            leaf_hash: None,
+            sighash: hex!("30239345177cadd0e3ea413d49803580abb6cb27971b481b7788a78d35117a88"),
+        },
+        TaprootVector {
+            tx: &hex!(
+                "0100000001aa6deae89d5e0aaca58714fc76ef6f3c8284224888089232d4e663843ed3ab3eae010000008b6657a60450cb4c0000000000160014a3d42b5413ef0c0701c4702f3cd7d4df222c147058020000000000001976a91430b4ed8723a4ee8992aa2c8814cfe5c3ad0ab9d988ac5802000000000000160014365b1166a6ed0a5e8e9dff17a6d00bbb43454bc758020000000000001976a914bc98c51a84fe7fad5dc380eb8b39586eff47241688ac4f313247"
+            ),
+            spent_outputs: &hex!(
+                "0107af4e00000000002251202c36d243dfc06cb56a248e62df27ecba7417307511a81ae61aa41c597a929c69"
+            ),
+            index: 0,
+            sighash_type: 0x83,
+            leaf_hash: None,
+            sighash: hex!("bf9c83f26c6dd16449e4921f813f551c4218e86f2ec906ca8611175b41b566df"),
+        },
+        TaprootVector {
+            tx: &hex!(
+                "020000000189fc651483f9296b906455dd939813bf086b1bbe7c77635e157c8e14ae29062195010000004445b5c7044561320000000000160014331414dbdada7fb578f700f38fb69995fc9b5ab958020000000000001976a914268db0a8104cc6d8afd91233cc8b3d1ace8ac3ef88ac580200000000000017a914ec00dcb368d6a693e11986d265f659d2f59e8be2875802000000000000160014c715799a49a0bae3956df9c17cb4440a673ac0df6f010000"
+            ),
+            spent_outputs: &hex!(
+                "011bec34000000000022512028055142ea437db73382e991861446040b61dd2185c4891d7daf6893d79f7182"
+            ),
+            index: 0,
+            sighash_type: 0x01,
+            leaf_hash: Some(hex!(
+                "15a2530514e399f8b5cf0b3d3112cf5b289eaa3e308ba2071b58392fdc6da68a"
+            )),
+            sighash: hex!("d66de5274a60400c7b08c86ba6b7f198f40660079edf53aca89d2a9501317f2e"),
+        },
+    ];
+
+    #[test]
+    fn bip341_sighashes() {
+        for vector in TAPROOT_VECTORS {
+            let tx = Transaction::decode(vector.tx, true).unwrap();
+
+            let mut reader = Reader::new(vector.spent_outputs);
+            let spent_outputs = (0..reader.read_compact_size().unwrap())
+                .map(|_| Some(TxOut::decode(&mut reader).unwrap()))
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                taproot(
+                    &tx,
+                    vector.index,
+                    &spent_outputs,
+                    vector.sighash_type,
+                    vector.leaf_hash.as_ref()
+                )
+                .unwrap(),
+                vector.sighash
+            );
+        }
+    }
+}
@@ -0,0 +1,165 @@
+//! Bitcoin transactions.
+
+use super::encoding::{Reader, write_compact_size, write_var_bytes};
+use crate::{Error, Result};
+use alloc::vec::Vec;
+use sha2::{Digest, Sha256};
+
+/// Transaction ID, in the byte order used when serializing transactions.
+pub(super) type Txid = [u8; 32];
+
+/// Bitcoin transaction.
+///
+/// Witnesses are skipped when parsing, as they're never needed for signing.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(super) struct Transaction {
+    /// Transaction version.
+    pub version: u32,
+
+    /// Transaction inputs.
+    pub inputs: Vec<TxIn>,
+
+    /// Transaction outputs.
+    pub outputs: Vec<TxOut>,
+
+    /// Lock time.
+    pub lock_time: u32,
+}
+
+impl Transaction {
+    /// Parse a transaction, optionally in the extended serialization format
+    /// with witnesses specified in BIP144.
+    pub(super) fn decode(bytes: &[u8], allow_witness: bool) -> Result<Self> {
+        let mut reader = Reader::new(bytes);
+        let version = reader.read_u32()?;
+
+        // Segregated witness marker and flag
+        let has_witness = allow_witness && reader.peek() == Some(0x00);
+        if has_witness && reader.read_array()? != [0x00, 0x01] {
+            return Err(Error::Psbt);
+        }
+
+        let mut inputs = Vec::new();
+        for _ in 0..reader.read_compact_size()? {
+            inputs.push(TxIn {
+                txid: reader.read_array()?,
+                vout: reader.read_u32()?,
+                script_sig: reader.read_var_bytes()?.into(),
+                sequence: reader.read_u32()?,
+            });
+        }
+
+        let mut outputs = Vec::new();
+        for _ in 0..reader.read_compact_size()? {
+            outputs.push(TxOut::decode(&mut reader)?);
+        }
+
+        if has_witness {
+            for _ in 0..inputs.len() {
+                for _ in 0..reader.read_compact_size()? {
+                    reader.read_var_bytes()?;
+                }
+            }
+        }
+
+        let lock_time = reader.read_u32()?;
+
+        if !reader.is_empty() {
+            return Err(Error::Psbt);
+        }
+
+        Ok(Self {
+            version,
+            inputs,
+            outputs,
+            lock_time,
+        })
+    }
+
+    /// Serialize this transaction without witnesses.
+    pub(super) fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.version.to_le_bytes());
+        write_compact_size(out, self.inputs.len() as u64);
+
+        for input in &self.inputs {
+            input.encode(out);
+        }
+
+        write_compact_size(out, self.outputs.len() as u64);
+
+        for output in &self.outputs {
+            output.encode(out);
+        }
+
+        out.extend_from_slice(&self.lock_time.to_le_bytes());
+    }
+
+    /// Compute the ID of this transaction.
+    pub(super) fn txid(&self) -> Txid {
+        let mut bytes = Vec::new();
+        self.encode(&mut bytes);
+        sha256d(&bytes)
+    }
+}
+
+/// Transaction input.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(super) struct TxIn {
+    /// ID of the transaction containing the output being spent.
+    pub txid: Txid,
+
+    /// Index of the output being spent.
+    pub vout: u32,
+
+    /// Signature script.
+    pub script_sig: Vec<u8>,
+
+    /// Sequence number.
+    pub sequence: u32,
+}
+
+impl TxIn {
+    /// Serialize the outpoint (i.e. `txid` and `vout`) of this input.
+    pub(super) fn encode_outpoint(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.txid);
+        out.extend_from_slice(&self.vout.to_le_bytes());
+    }
+
+    /// Serialize this input.
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.encode_outpoint(out);
+        write_var_bytes(out, &self.script_sig);
+        out.extend_from_slice(&self.sequence.to_le_bytes());
+    }
+}
+
+/// Transaction output.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(super) struct TxOut {
+    /// Amount in satoshis.
+    pub value: u64,
+
+    /// Locking script.
+    pub script_pubkey: Vec<u8>,
+}
+
+impl TxOut {
+    /// Parse a transaction output.
+    pub(super) fn decode(reader: &mut Reader<'_>) -> Result<Self> {
+        Ok(Self {
+            value: reader.read_u64()?,
+            script_pubkey: reader.read_var_bytes()?.into(),
+        })
+    }
+
+    /// Serialize this transaction output.
+    pub(super) fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.value.to_le_bytes());
+        write_var_bytes(out, &self.script_pubkey);
+    }
+}
+
+/// Compute `SHA256(SHA256(data))`.
+pub(super) fn sha256d(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(Sha256::digest(data)).into()
+}
@@ -1,9 +1,9 @@
 //! Trait for deriving child keys on a given type.
 
 use crate::{
-    ChainCode, ChildNumber, Error, HmacSha512, KeyFingerprint, PrivateKeyBytes, Result, KEY_SIZE,
+    ChainCode, ChildNumber, Error, KEY_SIZE, KeyFingerprint, PrivateKeyBytes, Result,
+    private_key::hmac_split,
 };
-use hmac::{KeyInit, Mac};
 use ripemd::Ripemd160;
 use sha2::{Digest, Sha256};
 
@@ -20,6 +20,10 @@ pub type PublicKeyBytes = [u8; KEY_SIZE + 1];
 
 /// Trait for key types which can be derived using BIP32.
 pub trait PublicKey: Sized {
+    /// Retry derivations which produce an invalid key as specified in SLIP-10,
+    /// rather than returning an error.
+    const RETRY_INVALID_KEYS: bool = false;
+
     /// Initialize this key from bytes.
     fn from_bytes(bytes: PublicKeyBytes) -> Result<Self>;
 
@@ -58,21 +62,9 @@ pub trait PublicKey: Sized {
             return Err(Error::ChildNumber);
         }
 
-        let mut hmac = HmacSha512::new_from_slice(chain_code).map_err(|_| Error::Crypto)?;
-
-        hmac.update(&self.to_bytes());
-        hmac.update(&child_number.to_bytes());
-
-        let result = hmac.finalize().into_bytes();
-        let (tweak_bytes, chain_code_bytes) = result.split_at(KEY_SIZE);
-
-        // Note that at this point we are only asserting that `tweak_bytes` have the expected size.
+        // Note that at this point we are only asserting that the tweak has the expected size.
         // Checking if it actually fits the curve scalar happens in `derive_child()`.
-        let tweak = tweak_bytes.try_into()?;
-
-        let chain_code = chain_code_bytes.try_into()?;
-
-        Ok((tweak, chain_code))
+        hmac_split(chain_code, &[&self.to_bytes(), &child_number.to_bytes()])
     }
 }
 
@@ -133,6 +125,57 @@ impl From<&XPub> for k256::ecdsa::VerifyingKey {
     }
 }
 
+#[cfg(feature = "nistp256")]
+impl PublicKey for p256::PublicKey {
+    const RETRY_INVALID_KEYS: bool = true;
+
+    fn from_bytes(bytes: PublicKeyBytes) -> Result<Self> {
+        Ok(p256::PublicKey::from_sec1_bytes(&bytes)?)
+    }
+
+    fn to_bytes(&self) -> PublicKeyBytes {
+        use p256::elliptic_curve::sec1::ToEncodedPoint;
+
+        self.to_encoded_point(true)
+            .as_bytes()
+            .try_into()
+            .expect("malformed public key")
+    }
+
+    fn derive_child(&self, other: PrivateKeyBytes) -> Result<Self> {
+        use p256::elliptic_curve::group::prime::PrimeCurveAffine;
+
+        let child_scalar =
+            Option::<p256::NonZeroScalar>::from(p256::NonZeroScalar::from_repr(other.into()))
+                .ok_or(Error::Crypto)?;
+
+        let child_point = self.to_projective() + (p256::AffinePoint::generator() * *child_scalar);
+        Self::from_affine(child_point.into()).map_err(|_| Error::Crypto)
+    }
+}
+
+#[cfg(feature = "nistp256")]
+impl PublicKey for p256::ecdsa::VerifyingKey {
+    const RETRY_INVALID_KEYS: bool = true;
+
+    fn from_bytes(bytes: PublicKeyBytes) -> Result<Self> {
+        Ok(p256::ecdsa::VerifyingKey::from_sec1_bytes(&bytes)?)
+    }
+
+    fn to_bytes(&self) -> PublicKeyBytes {
+        p256::CompressedPoint::from(self)
+            .as_slice()
+            .try_into()
+            .expect("malformed key")
+    }
+
+    fn derive_child(&self, other: PrivateKeyBytes) -> Result<Self> {
+        p256::PublicKey::from(self)
+            .derive_child(other)
+            .map(Into::into)
+    }
+}
+
 #[cfg(feature = "secp256k1-ffi")]
 impl PublicKey for secp256k1_ffi::PublicKey {
     fn from_bytes(bytes: PublicKeyBytes) -> Result<Self> {
@@ -167,7 +210,7 @@ mod tests {
     #[test]
     fn secp256k1_ffi_xprv_derivation() {
         let path = "m/0/2147483647'/1/2147483646'/2";
-        let xprv = XPrv::derive_from_path(&SEED, &path.parse().unwrap()).unwrap();
+        let xprv = XPrv::derive_from_path(SEED, &path.parse().unwrap()).unwrap();
 
         assert_eq!(
             xprv.public_key(),
@@ -178,7 +221,7 @@ mod tests {
     #[test]
     fn secp256k1_ffi_xpub_derivation() {
         let path = "m/0/2147483647'/1/2147483646'";
-        let xprv = XPrv::derive_from_path(&SEED, &path.parse().unwrap()).unwrap();
+        let xprv = XPrv::derive_from_path(SEED, &path.parse().unwrap()).unwrap();
         let xpub = xprv.public_key().derive_child(2.into()).unwrap();
 
         assert_eq!(
@@ -0,0 +1,453 @@
+//! Shamir's secret-sharing backups of master secrets as specified in SLIP-39.
+//!
+//! A master secret is encrypted with a passphrase and split into groups of
+//! shares, each of which is encoded as a mnemonic. The master secret can be
+//! recovered from a threshold number of groups, using a threshold number of
+//! member shares from each of them.
+//!
+//! The recovered [`MasterSecret`] is used as the seed of the root [`XPrv`]:
+//!
+//! ```
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # #[cfg(feature = "secp256k1")]
+//! # {
+//! use bip32::{slip39, XPrv};
+//! use rand_core::OsRng;
+//!
+//! let master_secret = [0x42; 16];
+//!
+//! // Split the master secret into one group of 3 shares, any 2 of which can
+//! // be used to recover it
+//! let groups = [slip39::Group { member_threshold: 2, member_count: 3 }];
+//! let mnemonics = slip39::split(&mut OsRng, &master_secret, "passphrase", 1, &groups, 0)?;
+//!
+//! let recovered = slip39::combine(&mnemonics[0][1..], "passphrase")?;
+//! assert_eq!(recovered.as_bytes(), master_secret);
+//!
+//! let root_xprv = XPrv::new(&recovered)?;
+//! # }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`XPrv`]: crate::XPrv
+
+mod shamir;
+mod share;
+
+use self::share::Share;
+use crate::{Error, Result};
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use rand_core::{CryptoRng, RngCore};
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+/// Number of rounds of the Feistel cipher used to encrypt master secrets.
+const ROUND_COUNT: u8 = 4;
+
+/// Number of PBKDF2 iterations in each round with an iteration exponent of
+/// zero.
+const BASE_ITERATION_COUNT: u32 = 2500;
+
+/// Maximum iteration exponent.
+const MAX_ITERATION_EXPONENT: u8 = 15;
+
+/// Salt prefix for backups without the extendable backup flag.
+const SALT_PREFIX: &[u8] = b"shamir";
+
+/// Parameters of a group of shares.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Group {
+    /// Number of member shares required to recover the group's share.
+    pub member_threshold: u8,
+
+    /// Total number of member shares in the group.
+    pub member_count: u8,
+}
+
+/// Master secret recovered from SLIP-39 shares, to be used as the seed of a
+/// root extended private key.
+#[derive(Clone)]
+pub struct MasterSecret(Zeroizing<Vec<u8>>);
+
+impl MasterSecret {
+    /// Get the inner secret byte slice.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for MasterSecret {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+/// Split a master secret into groups of SLIP-39 share mnemonics, encrypting
+/// it with the given passphrase.
+///
+/// The master secret can be recovered from `group_threshold` of the
+/// `groups`, using `member_threshold` of each group's shares. Encryption
+/// uses `10000 << iteration_exponent` PBKDF2 iterations.
+///
+/// The returned mnemonics are ordered by group, then by member.
+pub fn split(
+    mut rng: impl RngCore + CryptoRng,
+    master_secret: &[u8],
+    passphrase: &str,
+    group_threshold: u8,
+    groups: &[Group],
+    iteration_exponent: u8,
+) -> Result<Vec<Vec<Zeroizing<String>>>> {
+    if master_secret.len() < share::MIN_VALUE_SIZE || master_secret.len() % 2 != 0 {
+        return Err(Error::SeedLength);
+    }
+
+    if iteration_exponent > MAX_ITERATION_EXPONENT {
+        return Err(Error::Slip39);
+    }
+
+    let group_count = u8::try_from(groups.len()).map_err(|_| Error::Slip39)?;
+
+    if groups
+        .iter()
+        .any(|group| group.member_threshold == 1 && group.member_count > 1)
+    {
+        // A single member share would be enough, so all of them would be
+        // identical
+        return Err(Error::Slip39);
+    }
+
+    let identifier = (rng.next_u32() & 0x7fff) as u16;
+    let extendable = true;
+
+    let encrypted = encrypt(
+        master_secret,
+        passphrase,
+        iteration_exponent,
+        identifier,
+        extendable,
+    )?;
+
+    let group_shares = shamir::split_secret(&mut rng, group_threshold, group_count, &encrypted)?;
+    let mut mnemonics = Vec::with_capacity(groups.len());
+
+    for ((group_index, group_secret), group) in group_shares.iter().zip(groups) {
+        let member_shares = shamir::split_secret(
+            &mut rng,
+            group.member_threshold,
+            group.member_count,
+            group_secret,
+        )?;
+
+        mnemonics.push(
+            member_shares
+                .into_iter()
+                .map(|(member_index, value)| {
+                    Share {
+                        identifier,
+                        extendable,
+                        iteration_exponent,
+                        group_index: *group_index,
+                        group_threshold,
+                        group_count,
+                        member_index,
+                        member_threshold: group.member_threshold,
+                        value,
+                    }
+                    .to_mnemonic()
+                })
+                .collect(),
+        );
+    }
+
+    Ok(mnemonics)
+}
+
+/// Recover a master secret from SLIP-39 share mnemonics, decrypting it with
+/// the given passphrase.
+///
+/// Exactly the threshold number of groups must be present, each with exactly
+/// its threshold number of member shares.
+///
+/// NOTE: an incorrect passphrase can't be detected, and results in a
+/// different master secret.
+pub fn combine<S>(mnemonics: &[S], passphrase: &str) -> Result<MasterSecret>
+where
+    S: AsRef<str>,
+{
+    let shares = mnemonics
+        .iter()
+        .map(|mnemonic| Share::from_mnemonic(mnemonic.as_ref()))
+        .collect::<Result<Vec<_>>>()?;
+
+    let first = shares.first().ok_or(Error::Slip39)?;
+
+    if shares.iter().any(|share| {
+        share.identifier != first.identifier
+            || share.extendable != first.extendable
+            || share.iteration_exponent != first.iteration_exponent
+            || share.group_threshold != first.group_threshold
+            || share.group_count != first.group_count
+            || share.value.len() != first.value.len()
+    }) {
+        return Err(Error::Slip39);
+    }
+
+    // Member thresholds and shares, by group index
+    let mut groups = BTreeMap::<u8, (u8, Vec<shamir::Share>)>::new();
+
+    for share in &shares {
+        let (member_threshold, members) = groups
+            .entry(share.group_index)
+            .or_insert_with(|| (share.member_threshold, Vec::new()));
+
+        if *member_threshold != share.member_threshold {
+            return Err(Error::Slip39);
+        }
+
+        match members
+            .iter()
+            .find(|(index, _)| *index == share.member_index)
+        {
+            Some((_, value)) if *value == share.value => continue,
+            Some(_) => return Err(Error::Slip39),
+            None => members.push((share.member_index, share.value.clone())),
+        }
+    }
+
+    if groups.len() != usize::from(first.group_threshold) {
+        return Err(Error::Slip39);
+    }
+
+    let mut group_shares = Vec::with_capacity(groups.len());
+
+    for (group_index, (member_threshold, members)) in &groups {
+        if members.len() != usize::from(*member_threshold) {
+            return Err(Error::Slip39);
+        }
+
+        group_shares.push((
+            *group_index,
+            shamir::recover_secret(*member_threshold, members)?,
+        ));
+    }
+
+    let encrypted = shamir::recover_secret(first.group_threshold, &group_shares)?;
+
+    decrypt(
+        &encrypted,
+        passphrase,
+        first.iteration_exponent,
+        first.identifier,
+        first.extendable,
+    )
+    .map(MasterSecret)
+}
+
+/// Encrypt a master secret with a passphrase.
+fn encrypt(
+    master_secret: &[u8],
+    passphrase: &str,
+    iteration_exponent: u8,
+    identifier: u16,
+    extendable: bool,
+) -> Result<Zeroizing<Vec<u8>>> {
+    feistel(
+        master_secret,
+        passphrase,
+        iteration_exponent,
+        identifier,
+        extendable,
+        0..ROUND_COUNT,
+    )
+}
+
+/// Decrypt an encrypted master secret with a passphrase.
+fn decrypt(
+    encrypted: &[u8],
+    passphrase: &str,
+    iteration_exponent: u8,
+    identifier: u16,
+    extendable: bool,
+) -> Result<Zeroizing<Vec<u8>>> {
+    feistel(
+        encrypted,
+        passphrase,
+        iteration_exponent,
+        identifier,
+        extendable,
+        (0..ROUND_COUNT).rev(),
+    )
+}
+
+/// Apply the Feistel cipher used to encrypt master secrets, using the given
+/// rounds (in reverse order to decrypt).
+fn feistel(
+    input: &[u8],
+    passphrase: &str,
+    iteration_exponent: u8,
+    identifier: u16,
+    extendable: bool,
+    rounds: impl Iterator<Item = u8>,
+) -> Result<Zeroizing<Vec<u8>>> {
+    if input.len() % 2 != 0 {
+        return Err(Error::Slip39);
+    }
+
+    // Passphrases are restricted to printable ASCII
+    if !passphrase.bytes().all(|byte| (32..=126).contains(&byte)) {
+        return Err(Error::Slip39);
+    }
+
+    let half = input.len() / 2;
+    let mut left = Zeroizing::new(input[..half].to_vec());
+    let mut right = Zeroizing::new(input[half..].to_vec());
+
+    // The salt holds half of the plaintext, so its capacity is reserved up
+    // front to avoid reallocating (and leaving copies) as it's extended
+    let mut salt = Zeroizing::new(Vec::with_capacity(SALT_PREFIX.len() + 2 + half));
+    if !extendable {
+        salt.extend_from_slice(SALT_PREFIX);
+        salt.extend_from_slice(&identifier.to_be_bytes());
+    }
+    let salt_prefix_len = salt.len();
+
+    let mut password = Zeroizing::new(Vec::with_capacity(passphrase.len() + 1));
+    let mut round_output = Zeroizing::new(alloc::vec![0u8; half]);
+
+    for round in rounds {
+        password.clear();
+        password.push(round);
+        password.extend_from_slice(passphrase.as_bytes());
+
+        salt.truncate(salt_prefix_len);
+        salt.extend_from_slice(&right);
+
+        pbkdf2::pbkdf2_hmac::<Sha256>(
+            &password,
+            &salt,
+            BASE_ITERATION_COUNT << iteration_exponent,
+            &mut round_output,
+        );
+
+        for (l, f) in left.iter_mut().zip(round_output.iter()) {
+            *l ^= f;
+        }
+
+        core::mem::swap(&mut left, &mut right);
+    }
+
+    let mut output = Zeroizing::new(Vec::with_capacity(input.len()));
+    output.extend_from_slice(&right);
+    output.extend_from_slice(&left);
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Group, combine, decrypt, encrypt, split};
+    use crate::Error;
+    use alloc::{vec, vec::Vec};
+    use rand_core::OsRng;
+
+    #[test]
+    fn encryption_round_trip() {
+        let master_secret = *b"ABCDEFGHIJKLMNOP";
+
+        for extendable in [false, true] {
+            let encrypted = encrypt(&master_secret, "TREZOR", 0, 0x1234, extendable).unwrap();
+            assert_ne!(*encrypted, master_secret);
+
+            let decrypted = decrypt(&encrypted, "TREZOR", 0, 0x1234, extendable).unwrap();
+            assert_eq!(*decrypted, master_secret);
+        }
+    }
+
+    #[test]
+    fn split_and_combine() {
+        let master_secret = [0x5a; 32];
+        let groups = [
+            Group {
+                member_threshold: 1,
+                member_count: 1,
+            },
+            Group {
+                member_threshold: 2,
+                member_count: 3,
+            },
+            Group {
+                member_threshold: 3,
+                member_count: 5,
+            },
+        ];
+
+        let mnemonics = split(OsRng, &master_secret, "TREZOR", 2, &groups, 0).unwrap();
+        assert_eq!(
+            mnemonics.iter().map(Vec::len).collect::<Vec<_>>(),
+            [1, 3, 5]
+        );
+        assert!(
+            mnemonics
+                .iter()
+                .flatten()
+                .all(|m| m.split(' ').count() == 33)
+        );
+
+        let combinations = [
+            vec![&mnemonics[0][0], &mnemonics[1][0], &mnemonics[1][2]],
+            vec![
+                &mnemonics[2][4],
+                &mnemonics[1][1],
+                &mnemonics[2][0],
+                &mnemonics[1][0],
+                &mnemonics[2][2],
+            ],
+        ];
+
+        for combination in &combinations {
+            let recovered = combine(combination, "TREZOR").unwrap();
+            assert_eq!(recovered.as_bytes(), master_secret);
+        }
+
+        // Duplicate shares don't count towards the threshold
+        let duplicated = [&mnemonics[0][0], &mnemonics[1][0], &mnemonics[1][0]];
+        assert_eq!(combine(&duplicated, "TREZOR").err(), Some(Error::Slip39));
+
+        // Too few groups
+        assert_eq!(
+            combine(&mnemonics[1][..2], "TREZOR").err(),
+            Some(Error::Slip39)
+        );
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        let secret = [0; 16];
+        let group = |member_threshold, member_count| Group {
+            member_threshold,
+            member_count,
+        };
+
+        assert_eq!(
+            split(OsRng, &secret[..15], "", 1, &[group(1, 1)], 0).err(),
+            Some(Error::SeedLength)
+        );
+        assert_eq!(
+            split(OsRng, &secret, "", 1, &[group(1, 2)], 0).err(),
+            Some(Error::Slip39)
+        );
+        assert_eq!(
+            split(OsRng, &secret, "", 2, &[group(1, 1)], 0).err(),
+            Some(Error::Slip39)
+        );
+        assert_eq!(
+            split(OsRng, &secret, "", 1, &[group(1, 1)], 16).err(),
+            Some(Error::Slip39)
+        );
+        assert_eq!(
+            split(OsRng, &secret, "caf\u{e9}", 1, &[group(1, 1)], 0).err(),
+            Some(Error::Slip39)
+        );
+    }
+}
@@ -0,0 +1,208 @@
+//! Shamir's secret sharing over GF(256) as specified in SLIP-39.
+//!
+//! Field arithmetic is performed without table lookups or branches on
+//! secret values, to avoid leaking shares via timing side-channels.
+
+use crate::{Error, Result};
+use alloc::vec::Vec;
+use hmac::{Hmac, KeyInit, Mac};
+use rand_core::{CryptoRng, RngCore};
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+/// Maximum number of shares a secret can be split into.
+pub(super) const MAX_SHARE_COUNT: u8 = 16;
+
+/// Index of the share containing the shared secret.
+const SECRET_INDEX: u8 = 255;
+
+/// Index of the share containing the digest of the shared secret.
+const DIGEST_INDEX: u8 = 254;
+
+/// Size of the digest of the shared secret.
+const DIGEST_SIZE: usize = 4;
+
+/// Share: an index (x-coordinate) and value (y-coordinates).
+pub(super) type Share = (u8, Zeroizing<Vec<u8>>);
+
+/// Multiply two elements of GF(256) with the Rijndael polynomial
+/// `x^8 + x^4 + x^3 + x + 1`.
+fn mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+
+    for _ in 0..8 {
+        // Add `a` if the low bit of `b` is set
+        product ^= a & 0u8.wrapping_sub(b & 1);
+
+        // Multiply `a` by `x`, reducing by the polynomial on overflow
+        let overflow = 0u8.wrapping_sub(a >> 7);
+        a = (a << 1) ^ (0x1b & overflow);
+        b >>= 1;
+    }
+
+    product
+}
+
+/// Compute the multiplicative inverse of an element of GF(256) as `a^254`,
+/// which is zero when `a` is zero.
+fn inv(a: u8) -> u8 {
+    let mut result = 1;
+    let mut square = a;
+
+    // 254 = 2 + 4 + 8 + 16 + 32 + 64 + 128
+    for _ in 0..7 {
+        square = mul(square, square);
+        result = mul(result, square);
+    }
+
+    result
+}
+
+/// Divide two elements of GF(256). The divisor must be non-zero.
+fn div(a: u8, b: u8) -> u8 {
+    mul(a, inv(b))
+}
+
+/// Evaluate the polynomials which pass through the given shares at `x`.
+fn interpolate(shares: &[Share], x: u8) -> Result<Zeroizing<Vec<u8>>> {
+    let len = shares.first().ok_or(Error::Slip39)?.1.len();
+
+    for (i, (index, value)) in shares.iter().enumerate() {
+        if value.len() != len || shares[..i].iter().any(|(other, _)| other == index) {
+            return Err(Error::Slip39);
+        }
+    }
+
+    if let Some((_, value)) = shares.iter().find(|(index, _)| *index == x) {
+        return Ok(value.clone());
+    }
+
+    let mut result = Zeroizing::new(alloc::vec![0u8; len]);
+
+    for (index, value) in shares {
+        // Lagrange basis polynomial for this share, evaluated at `x`
+        // (subtraction is XOR in GF(256))
+        let basis = shares
+            .iter()
+            .filter(|(other, _)| other != index)
+            .fold(1, |basis, (other, _)| {
+                mul(basis, div(x ^ other, index ^ other))
+            });
+
+        for (out, y) in result.iter_mut().zip(value.iter()) {
+            *out ^= mul(basis, *y);
+        }
+    }
+
+    Ok(result)
+}
+
+/// Compute the digest of a shared secret.
+fn digest(random_part: &[u8], secret: &[u8]) -> Result<[u8; DIGEST_SIZE]> {
+    let mut hmac = Hmac::<Sha256>::new_from_slice(random_part)?;
+    hmac.update(secret);
+    Ok(hmac.finalize().into_bytes()[..DIGEST_SIZE].try_into()?)
+}
+
+/// Split a secret into `count` shares, any `threshold` of which can be used
+/// to recover it.
+pub(super) fn split_secret(
+    rng: &mut (impl RngCore + CryptoRng),
+    threshold: u8,
+    count: u8,
+    secret: &[u8],
+) -> Result<Vec<Share>> {
+    if threshold == 0 || threshold > count || count > MAX_SHARE_COUNT {
+        return Err(Error::Slip39);
+    }
+
+    if secret.len() < DIGEST_SIZE {
+        return Err(Error::Slip39);
+    }
+
+    if threshold == 1 {
+        return Ok((0..count)
+            .map(|index| (index, Zeroizing::new(secret.into())))
+            .collect());
+    }
+
+    let random_count = threshold - 2;
+    let mut shares = Vec::new();
+
+    for index in 0..random_count {
+        let mut value = Zeroizing::new(alloc::vec![0u8; secret.len()]);
+        rng.fill_bytes(&mut value);
+        shares.push((index, value));
+    }
+
+    let mut digest_share = Zeroizing::new(alloc::vec![0u8; secret.len()]);
+    rng.fill_bytes(&mut digest_share[DIGEST_SIZE..]);
+    let digest = digest(&digest_share[DIGEST_SIZE..], secret)?;
+    digest_share[..DIGEST_SIZE].copy_from_slice(&digest);
+
+    let mut base_shares = shares.clone();
+    base_shares.push((DIGEST_INDEX, digest_share));
+    base_shares.push((SECRET_INDEX, Zeroizing::new(secret.into())));
+
+    for index in random_count..count {
+        shares.push((index, interpolate(&base_shares, index)?));
+    }
+
+    Ok(shares)
+}
+
+/// Recover a secret from `threshold` shares.
+pub(super) fn recover_secret(threshold: u8, shares: &[Share]) -> Result<Zeroizing<Vec<u8>>> {
+    if threshold == 1 {
+        return shares
+            .first()
+            .map(|(_, value)| value.clone())
+            .ok_or(Error::Slip39);
+    }
+
+    let secret = interpolate(shares, SECRET_INDEX)?;
+    let digest_share = interpolate(shares, DIGEST_INDEX)?;
+
+    if digest_share.len() < DIGEST_SIZE
+        || digest_share[..DIGEST_SIZE] != digest(&digest_share[DIGEST_SIZE..], &secret)?
+    {
+        return Err(Error::Slip39);
+    }
+
+    Ok(secret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{div, inv, mul, recover_secret, split_secret};
+    use rand_core::OsRng;
+
+    #[test]
+    fn field_arithmetic() {
+        assert_eq!(mul(0x57, 0x83), 0xc1);
+        assert_eq!(mul(0x57, 0x13), 0xfe);
+        assert_eq!(inv(0), 0);
+        assert_eq!(inv(0x53), 0xca);
+
+        for a in 1..=255 {
+            assert_eq!(mul(a, div(1, a)), 1);
+            assert_eq!(mul(a, 0), 0);
+            assert_eq!(mul(a, 1), a);
+        }
+    }
+
+    #[test]
+    fn split_and_recover() {
+        let secret = *b"ABCDEFGHIJKLMNOP";
+        let shares = split_secret(&mut OsRng, 3, 5, &secret).unwrap();
+        assert_eq!(shares.len(), 5);
+
+        for subset in [[0, 1, 2], [1, 3, 4], [4, 2, 0]] {
+            let subset = subset.map(|i| shares[i].clone());
+            assert_eq!(*recover_secret(3, &subset).unwrap(), secret);
+        }
+
+        // Too few shares give an invalid digest
+        assert!(recover_secret(3, &shares[..2]).is_err());
+    }
+}
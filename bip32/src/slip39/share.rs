@@ -0,0 +1,268 @@
+//! SLIP-39 shares and their encoding as mnemonics.
+
+use crate::{Error, Result};
+use alloc::{string::String, vec::Vec};
+use zeroize::Zeroizing;
+
+/// SLIP-39 wordlist.
+const WORDLIST: &str = include_str!("wordlist.txt");
+
+/// Number of bits encoded by each word.
+const RADIX_BITS: usize = 10;
+
+/// Number of words encoding the identifier, extendable backup flag, and
+/// iteration exponent.
+const ID_EXP_WORDS: usize = 2;
+
+/// Number of words encoding the group and member parameters.
+const PARAMS_WORDS: usize = 2;
+
+/// Number of checksum words.
+const CHECKSUM_WORDS: usize = 3;
+
+/// Number of words which don't encode the share value.
+const METADATA_WORDS: usize = ID_EXP_WORDS + PARAMS_WORDS + CHECKSUM_WORDS;
+
+/// Minimum size of a share value in bytes.
+pub(super) const MIN_VALUE_SIZE: usize = 16;
+
+/// Minimum number of words in a share mnemonic.
+const MIN_WORDS: usize = METADATA_WORDS + (MIN_VALUE_SIZE * 8).div_ceil(RADIX_BITS);
+
+/// Checksum customization string for shares without the extendable backup
+/// flag.
+const CUSTOMIZATION: &[u8] = b"shamir";
+
+/// Checksum customization string for shares with the extendable backup flag.
+const CUSTOMIZATION_EXTENDABLE: &[u8] = b"shamir_extendable";
+
+/// Generator of the RS1024 checksum.
+const RS1024_GENERATOR: [u32; 10] = [
+    0x00e0_e040,
+    0x01c1_c080,
+    0x0383_8100,
+    0x0707_0200,
+    0x0e0e_0009,
+    0x1c0c_2412,
+    0x3808_6c24,
+    0x3090_fc48,
+    0x21b1_f890,
+    0x03f3_f120,
+];
+
+/// Single share of a SLIP-39 backup, which belongs to a group.
+pub(super) struct Share {
+    /// Random identifier shared by all shares of a backup.
+    pub identifier: u16,
+
+    /// Can more shares be created for this backup with the same identifier?
+    pub extendable: bool,
+
+    /// Exponent of the number of PBKDF2 iterations used for encryption.
+    pub iteration_exponent: u8,
+
+    /// Index of this share's group.
+    pub group_index: u8,
+
+    /// Number of groups required to recover the master secret.
+    pub group_threshold: u8,
+
+    /// Total number of groups.
+    pub group_count: u8,
+
+    /// Index of this share within its group.
+    pub member_index: u8,
+
+    /// Number of members of this share's group required to recover the
+    /// group secret.
+    pub member_threshold: u8,
+
+    /// Share value.
+    pub value: Zeroizing<Vec<u8>>,
+}
+
+impl Share {
+    /// Parse a share from a mnemonic.
+    pub(super) fn from_mnemonic(mnemonic: &str) -> Result<Self> {
+        let words = Zeroizing::new(
+            mnemonic
+                .split_whitespace()
+                .map(word_index)
+                .collect::<Option<Vec<u16>>>()
+                .ok_or(Error::Slip39)?,
+        );
+
+        if words.len() < MIN_WORDS {
+            return Err(Error::Slip39);
+        }
+
+        // Share values are a whole number of 16-bit words, and are left
+        // padded to a whole number of mnemonic words
+        let padding_bits = (RADIX_BITS * (words.len() - METADATA_WORDS)) % 16;
+        if padding_bits > 8 {
+            return Err(Error::Slip39);
+        }
+
+        let id_exp = u32::from(words[0]) << RADIX_BITS | u32::from(words[1]);
+        let extendable = (id_exp >> 4) & 1 == 1;
+
+        if rs1024_polymod(customization(extendable), words.iter().copied()) != 1 {
+            return Err(Error::Slip39);
+        }
+
+        let params = u32::from(words[2]) << RADIX_BITS | u32::from(words[3]);
+        let param = |shift: u32| ((params >> shift) & 0xf) as u8;
+
+        let share = Share {
+            identifier: (id_exp >> 5) as u16,
+            extendable,
+            iteration_exponent: (id_exp & 0xf) as u8,
+            group_index: param(16),
+            group_threshold: param(12) + 1,
+            group_count: param(8) + 1,
+            member_index: param(4),
+            member_threshold: param(0) + 1,
+            value: unpack(
+                &words[ID_EXP_WORDS + PARAMS_WORDS..words.len() - CHECKSUM_WORDS],
+                padding_bits,
+            )?,
+        };
+
+        if share.group_count < share.group_threshold {
+            return Err(Error::Slip39);
+        }
+
+        Ok(share)
+    }
+
+    /// Encode this share as a mnemonic.
+    pub(super) fn to_mnemonic(&self) -> Zeroizing<String> {
+        let id_exp = u32::from(self.identifier) << 5
+            | u32::from(self.extendable) << 4
+            | u32::from(self.iteration_exponent);
+
+        let params = u32::from(self.group_index) << 16
+            | u32::from(self.group_threshold - 1) << 12
+            | u32::from(self.group_count - 1) << 8
+            | u32::from(self.member_index) << 4
+            | u32::from(self.member_threshold - 1);
+
+        let mut words = Zeroizing::new(Vec::from([
+            (id_exp >> RADIX_BITS) as u16,
+            (id_exp & 0x3ff) as u16,
+            (params >> RADIX_BITS) as u16,
+            (params & 0x3ff) as u16,
+        ]));
+
+        pack(&self.value, &mut words);
+
+        let checksum = rs1024_polymod(
+            customization(self.extendable),
+            words.iter().copied().chain([0; CHECKSUM_WORDS]),
+        ) ^ 1;
+
+        for i in (0..CHECKSUM_WORDS).rev() {
+            words.push(((checksum >> (RADIX_BITS * i)) & 0x3ff) as u16);
+        }
+
+        let mut mnemonic = Zeroizing::new(String::new());
+
+        for (i, index) in words.iter().enumerate() {
+            if i > 0 {
+                mnemonic.push(' ');
+            }
+
+            mnemonic.push_str(word(*index));
+        }
+
+        mnemonic
+    }
+}
+
+/// Get the checksum customization string.
+fn customization(extendable: bool) -> &'static [u8] {
+    if extendable {
+        CUSTOMIZATION_EXTENDABLE
+    } else {
+        CUSTOMIZATION
+    }
+}
+
+/// Compute the RS1024 checksum of the given words.
+fn rs1024_polymod(customization: &[u8], words: impl IntoIterator<Item = u16>) -> u32 {
+    let mut chk = 1u32;
+
+    for value in customization
+        .iter()
+        .map(|byte| u16::from(*byte))
+        .chain(words)
+    {
+        let top = chk >> 20;
+        chk = ((chk & 0xf_ffff) << RADIX_BITS) ^ u32::from(value);
+
+        for (i, generator) in RS1024_GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= generator;
+            }
+        }
+    }
+
+    chk
+}
+
+/// Encode a share value as words, left-padding it with zero bits.
+fn pack(value: &[u8], words: &mut Vec<u16>) {
+    let bits = value.len() * 8;
+    let padding_bits = (RADIX_BITS - bits % RADIX_BITS) % RADIX_BITS;
+    let mut acc = 0u16;
+    let mut acc_bits = padding_bits;
+
+    for i in 0..bits {
+        let bit = (value[i / 8] >> (7 - i % 8)) & 1;
+        acc = (acc << 1) | u16::from(bit);
+        acc_bits += 1;
+
+        if acc_bits == RADIX_BITS {
+            words.push(acc);
+            acc = 0;
+            acc_bits = 0;
+        }
+    }
+}
+
+/// Decode a share value from words, checking that its padding is zero.
+fn unpack(words: &[u16], padding_bits: usize) -> Result<Zeroizing<Vec<u8>>> {
+    let bits = words.len() * RADIX_BITS;
+    let mut value = Zeroizing::new(alloc::vec![0u8; (bits - padding_bits) / 8]);
+
+    for i in 0..bits {
+        let bit = (words[i / RADIX_BITS] >> (RADIX_BITS - 1 - i % RADIX_BITS)) & 1;
+
+        if i < padding_bits {
+            if bit != 0 {
+                return Err(Error::Slip39);
+            }
+        } else {
+            let i = i - padding_bits;
+            value[i / 8] |= (bit as u8) << (7 - i % 8);
+        }
+    }
+
+    Ok(value)
+}
+
+/// Get the word with the given index.
+fn word(index: u16) -> &'static str {
+    WORDLIST
+        .lines()
+        .nth(index.into())
+        .expect("index should be less than 1024")
+}
+
+/// Get the index of the given word (case insensitive).
+fn word_index(word: &str) -> Option<u16> {
+    WORDLIST
+        .lines()
+        .position(|w| w.eq_ignore_ascii_case(word))
+        .map(|index| index as u16)
+}
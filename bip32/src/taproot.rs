@@ -0,0 +1,217 @@
+//! Taproot key tweaking as specified in BIP341, and BIP340 x-only public
+//! keys.
+//!
+//! The tweaked output key of a Taproot output commits to an internal key and
+//! an optional script tree. With no script tree, this is the output key used
+//! by BIP86 wallets.
+
+use crate::{Error, ExtendedPublicKey, KEY_SIZE, Result, XPrv};
+use k256::{
+    NonZeroScalar, ProjectivePoint, PublicKey, Scalar,
+    elliptic_curve::{ff::PrimeField, sec1::ToEncodedPoint},
+    schnorr,
+};
+use sha2::{Digest, Sha256};
+
+/// SEC1 tag for compressed points with an odd Y coordinate.
+const ODD_TAG: u8 = 0x03;
+
+/// Hash of a node in a Taproot script tree, e.g. the merkle root.
+pub type TapNodeHash = [u8; KEY_SIZE];
+
+/// Parity of the Y coordinate of a public key.
+///
+/// X-only public keys always have an even Y coordinate, but the parity of an
+/// output key is needed in control blocks when spending via a script path.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Parity {
+    /// Even Y coordinate.
+    Even,
+
+    /// Odd Y coordinate.
+    Odd,
+}
+
+impl Parity {
+    /// Get the parity of the Y coordinate of the given public key.
+    fn of(public_key: &PublicKey) -> Self {
+        if public_key.to_encoded_point(true).as_bytes()[0] == ODD_TAG {
+            Parity::Odd
+        } else {
+            Parity::Even
+        }
+    }
+}
+
+impl<K> ExtendedPublicKey<K>
+where
+    K: crate::PublicKey + Clone + Into<PublicKey>,
+{
+    /// Get this key as a BIP340 x-only public key, i.e. with its Y
+    /// coordinate made even.
+    pub fn to_x_only(&self) -> schnorr::VerifyingKey {
+        let public_key = self.public_key().clone().into();
+        schnorr::VerifyingKey::from_bytes(&x_only(&public_key)).expect("valid x-only key")
+    }
+
+    /// Tweak this key as the internal key of a Taproot output, returning the
+    /// x-only output key and the parity of its Y coordinate.
+    ///
+    /// The `merkle_root` is the root of the output's script tree, if any.
+    /// BIP86 outputs have no script tree.
+    pub fn tap_tweak(
+        &self,
+        merkle_root: Option<&TapNodeHash>,
+    ) -> Result<(schnorr::VerifyingKey, Parity)> {
+        let output_key = output_key(&self.public_key().clone().into(), merkle_root)?;
+        let x_only_key = schnorr::VerifyingKey::from_bytes(&x_only(&output_key))?;
+        Ok((x_only_key, Parity::of(&output_key)))
+    }
+}
+
+impl XPrv {
+    /// Tweak this key as the internal key of a Taproot output, returning the
+    /// key which signs for the output key when spending via the key path.
+    ///
+    /// The `merkle_root` must match the one passed to
+    /// [`ExtendedPublicKey::tap_tweak`].
+    pub fn tap_tweak(&self, merkle_root: Option<&TapNodeHash>) -> Result<schnorr::SigningKey> {
+        tweak_secret_key(self.private_key().as_nonzero_scalar(), merkle_root)
+            .map(schnorr::SigningKey::from)
+    }
+}
+
+/// Compute a BIP340 tagged hash: `SHA256(SHA256(tag) || SHA256(tag) || data)`.
+pub(crate) fn tagged_hash(tag: &[u8], data: &[&[u8]]) -> [u8; KEY_SIZE] {
+    let tag_hash = Sha256::digest(tag);
+    let mut hasher = Sha256::new();
+    hasher.update(tag_hash);
+    hasher.update(tag_hash);
+
+    for chunk in data {
+        hasher.update(chunk);
+    }
+
+    hasher.finalize().into()
+}
+
+/// Serialize a public key as a BIP340 x-only public key.
+pub(crate) fn x_only(public_key: &PublicKey) -> [u8; KEY_SIZE] {
+    let encoded = public_key.to_encoded_point(true);
+    encoded
+        .x()
+        .expect("compressed point")
+        .as_slice()
+        .try_into()
+        .expect("x-coordinate size")
+}
+
+/// Compute the tweak for an internal key and an optional script tree root.
+fn tweak(internal_key: &[u8; KEY_SIZE], merkle_root: Option<&TapNodeHash>) -> Result<Scalar> {
+    let hash = match merkle_root {
+        Some(root) => tagged_hash(b"TapTweak", &[internal_key, root]),
+        None => tagged_hash(b"TapTweak", &[internal_key]),
+    };
+
+    Option::from(Scalar::from_repr(hash.into())).ok_or(Error::Crypto)
+}
+
+/// Tweak an internal public key, returning the output key.
+pub(crate) fn output_key(
+    public_key: &PublicKey,
+    merkle_root: Option<&TapNodeHash>,
+) -> Result<PublicKey> {
+    let mut point = public_key.to_projective();
+
+    // BIP340 x-only keys implicitly have an even Y coordinate
+    if Parity::of(public_key) == Parity::Odd {
+        point = -point;
+    }
+
+    let output_key = point + ProjectivePoint::GENERATOR * tweak(&x_only(public_key), merkle_root)?;
+    Ok(PublicKey::from_affine(output_key.to_affine())?)
+}
+
+/// Tweak the secret key of an internal public key, returning the secret key
+/// of the output key.
+pub(crate) fn tweak_secret_key(
+    secret_key: &NonZeroScalar,
+    merkle_root: Option<&TapNodeHash>,
+) -> Result<NonZeroScalar> {
+    let public_key = PublicKey::from_secret_scalar(secret_key);
+    let mut scalar = **secret_key;
+
+    if Parity::of(&public_key) == Parity::Odd {
+        scalar = -scalar;
+    }
+
+    let tweaked = scalar + tweak(&x_only(&public_key), merkle_root)?;
+    Option::from(NonZeroScalar::new(tweaked)).ok_or(Error::Crypto)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Parity, TapNodeHash};
+    use crate::{ChildNumber, ExtendedKeyAttrs, ExtendedPublicKey, XPrv};
+    use hex_literal::hex;
+
+    /// Create an extended key from a BIP340 x-only public key.
+    fn from_x_only(x_only: &[u8; 32]) -> ExtendedPublicKey<k256::PublicKey> {
+        let mut bytes = [0x02; 33];
+        bytes[1..].copy_from_slice(x_only);
+
+        let attrs = ExtendedKeyAttrs {
+            depth: 0,
+            parent_fingerprint: [0; 4],
+            child_number: ChildNumber(0),
+            chain_code: [0; 32],
+        };
+
+        ExtendedPublicKey::new(k256::PublicKey::from_sec1_bytes(&bytes).unwrap(), attrs)
+    }
+
+    /// BIP341 wallet test vectors: internal key, merkle root, and output key.
+    #[test]
+    fn bip341_output_keys() {
+        let vectors: [([u8; 32], Option<TapNodeHash>, [u8; 32]); 2] = [
+            (
+                hex!("d6889cb081036e0faefa3a35157ad71086b123b2b144b649798b494c300a961d"),
+                None,
+                hex!("53a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343"),
+            ),
+            (
+                hex!("187791b6f712a8ea41c8ecdd0ee77fab3e85263b37e1ec18a3651926b3a6cf27"),
+                Some(hex!(
+                    "5b75adecf53548f3ec6ad7d78383bf84cc57b55a3127c72b9a2481752dd88b21"
+                )),
+                hex!("147c9c57132f6e7ecddba9800bb0c4449251c92a1e60371ee77557b6620f3ea3"),
+            ),
+        ];
+
+        for (internal_key, merkle_root, expected) in vectors {
+            let xpub = from_x_only(&internal_key);
+            assert_eq!(xpub.to_x_only().to_bytes().as_slice(), internal_key);
+
+            let (output_key, _) = xpub.tap_tweak(merkle_root.as_ref()).unwrap();
+            assert_eq!(output_key.to_bytes().as_slice(), expected);
+        }
+    }
+
+    #[test]
+    fn key_path_signing_key() {
+        let xprv = XPrv::new([0x42; 32]).unwrap();
+        let merkle_root = [0x01; 32];
+
+        for root in [None, Some(&merkle_root)] {
+            let (output_key, parity) = xprv.public_key().tap_tweak(root).unwrap();
+            let signing_key = xprv.tap_tweak(root).unwrap();
+            assert_eq!(signing_key.verifying_key(), &output_key);
+
+            // The full output key has the Y coordinate indicated by the parity
+            let tweaked = k256::PublicKey::from_secret_scalar(
+                &super::tweak_secret_key(xprv.private_key().as_nonzero_scalar(), root).unwrap(),
+            );
+            assert_eq!(Parity::of(&tweaked), parity);
+        }
+    }
+}
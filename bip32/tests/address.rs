@@ -0,0 +1,102 @@
+//! Address encoding test vectors.
+//!
+//! All addresses are derived from the mnemonic used in the BIP49, BIP84, and
+//! BIP86 test vectors.
+
+#![cfg(all(feature = "address", feature = "bip39"))]
+
+use bip32::{Mnemonic, XPrv, XPub, address::Network};
+
+/// Mnemonic used for all test vectors.
+const PHRASE: &str =
+    "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+/// Derive the [`XPub`] at the given derivation path.
+fn derive_xpub(path: &str) -> XPub {
+    let seed = Mnemonic::new(PHRASE, Default::default())
+        .unwrap()
+        .to_seed("");
+
+    XPrv::derive_from_path(&seed, &path.parse().unwrap())
+        .unwrap()
+        .public_key()
+}
+
+#[test]
+fn p2pkh() {
+    let xpub = derive_xpub("m/44'/0'/0'/0/0");
+    assert_eq!(
+        xpub.p2pkh_address(Network::Bitcoin),
+        "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA"
+    );
+}
+
+/// BIP49 test vector
+#[test]
+fn p2sh_p2wpkh() {
+    let xpub = derive_xpub("m/49'/1'/0'/0/0");
+    assert_eq!(
+        xpub.p2sh_p2wpkh_address(Network::Testnet),
+        "2Mww8dCYPUpKHofjgcXcBCEGmniw9CoaiD2"
+    );
+}
+
+/// BIP84 test vectors
+#[test]
+fn p2wpkh() {
+    let xpub = derive_xpub("m/84'/0'/0'/0/0");
+    assert_eq!(
+        xpub.p2wpkh_address(Network::Bitcoin).unwrap(),
+        "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu"
+    );
+
+    let xpub = derive_xpub("m/84'/0'/0'/1/0");
+    assert_eq!(
+        xpub.p2wpkh_address(Network::Bitcoin).unwrap(),
+        "bc1q8c6fshw2dlwun7ekn9qwf37cu2rn755upcp6el"
+    );
+}
+
+/// BIP86 test vectors
+#[test]
+fn p2tr() {
+    let xpub = derive_xpub("m/86'/0'/0'/0/0");
+    assert_eq!(
+        xpub.p2tr_address(Network::Bitcoin).unwrap(),
+        "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr"
+    );
+
+    let xpub = derive_xpub("m/86'/0'/0'/0/1");
+    assert_eq!(
+        xpub.p2tr_address(Network::Bitcoin).unwrap(),
+        "bc1p4qhjn9zdvkux4e44uhx8tc55attvtyu358kutcqkudyccelu0was9fqzwh"
+    );
+
+    let xpub = derive_xpub("m/86'/0'/0'/1/0");
+    assert_eq!(
+        xpub.p2tr_address(Network::Bitcoin).unwrap(),
+        "bc1p3qkhfews2uk44qtvauqyr2ttdsw7svhkl9nkm9s9c3x4ax5h60wqwruhk7"
+    );
+}
+
+#[test]
+fn cosmos() {
+    let xpub = derive_xpub("m/44'/118'/0'/0/0");
+    assert_eq!(
+        xpub.cosmos_address("cosmos").unwrap(),
+        "cosmos19rl4cm2hmr8afy4kldpxz3fka4jguq0auqdal4"
+    );
+
+    for hrp in ["", "Cosmos", "cos mos"] {
+        assert_eq!(xpub.cosmos_address(hrp), Err(bip32::Error::Hrp));
+    }
+}
+
+#[test]
+fn ethereum() {
+    let xpub = derive_xpub("m/44'/60'/0'/0/0");
+    assert_eq!(
+        xpub.ethereum_address(),
+        "0x9858EfFD232B4033E47d90003D41EC34EcaEda94"
+    );
+}
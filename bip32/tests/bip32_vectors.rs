@@ -8,7 +8,7 @@
 
 #![cfg(all(feature = "alloc", feature = "secp256k1"))]
 
-use bip32::{Prefix, XPrv};
+use bip32::{Error, Prefix, XPrv, XPub};
 use hex_literal::hex;
 
 /// Derive an [`XPrv`] for the given seed and derivation path.
@@ -259,3 +259,121 @@ fn test_vector_4() {
         "xpub6BJA1jSqiukeaesWfxe6sNK9CCGaujFFSJLomWHprUL9DePQ4JDkM5d88n49sMGJxrhpjazuXYWdMf17C9T5XnxkopaeS7jGk1GyyVziaMt"
     );
 }
+
+/// Deriving more than the maximum depth reports the index of the child
+/// number which couldn't be derived.
+#[test]
+fn max_depth() {
+    let seed = hex!("000102030405060708090a0b0c0d0e0f");
+    let mut path = "m".to_string();
+
+    for _ in 0..=XPrv::MAX_DEPTH {
+        path.push_str("/0");
+    }
+
+    assert_eq!(
+        XPrv::derive_from_path(seed, &path.parse().unwrap()).err(),
+        Some(Error::PathDepth { index: 255 })
+    );
+}
+
+/// BIP32 Test Vector 5
+/// <https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki#test-vector-5>
+///
+/// These vectors test that invalid extended keys are rejected.
+#[test]
+fn test_vector_5() {
+    let xpubs = [
+        // pubkey version / prvkey mismatch
+        (
+            "xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6LBpB85b3D2yc8sfvZU521AAwdZafEz7mnzBBsz4wKY5fTtTQBm",
+            Error::PublicKey,
+        ),
+        // invalid pubkey prefix 04
+        (
+            "xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6Txnt3siSujt9RCVYsx4qHZGc62TG4McvMGcAUjeuwZdduYEvFn",
+            Error::PublicKey,
+        ),
+        // invalid pubkey prefix 01
+        (
+            "xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6N8ZMMXctdiCjxTNq964yKkwrkBJJwpzZS4HS2fxvyYUA4q2Xe4",
+            Error::PublicKey,
+        ),
+        // zero depth with non-zero parent fingerprint
+        (
+            "xpub661no6RGEX3uJkY4bNnPcw4URcQTrSibUZ4NqJEw5eBkv7ovTwgiT91XX27VbEXGENhYRCf7hyEbWrR3FewATdCEebj6znwMfQkhRYHRLpJ",
+            Error::ParentFingerprint,
+        ),
+        // zero depth with non-zero index
+        (
+            "xpub661MyMwAuDcm6CRQ5N4qiHKrJ39Xe1R1NyfouMKTTWcguwVcfrZJaNvhpebzGerh7gucBvzEQWRugZDuDXjNDRmXzSZe4c7mnTK97pTvGS8",
+            Error::ChildNumber,
+        ),
+        // unknown extended key version
+        (
+            "DMwo58pR1QLEFihHiXPVykYB6fJmsTeHvyTp7hRThAtCX8CvYzgPcn8XnmdfHGMQzT7ayAmfo4z3gY5KfbrZWZ6St24UVf2Qgo6oujFktLHdHY4",
+            Error::Version(0x01010101),
+        ),
+        // invalid pubkey 020000000000000000000000000000000000000000000000000000000000000007
+        (
+            "xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6Q5JXayek4PRsn35jii4veMimro1xefsM58PgBMrvdYre8QyULY",
+            Error::PublicKey,
+        ),
+    ];
+
+    for (xpub, error) in xpubs {
+        assert_eq!(xpub.parse::<XPub>().err(), Some(error), "{xpub}");
+    }
+
+    let xprvs = [
+        // prvkey version / pubkey mismatch
+        (
+            "xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFGTQQD3dC4H2D5GBj7vWvSQaaBv5cxi9gafk7NF3pnBju6dwKvH",
+            Error::PrivateKey,
+        ),
+        // invalid prvkey prefix 04
+        (
+            "xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFGpWnsj83BHtEy5Zt8CcDr1UiRXuWCmTQLxEK9vbz5gPstX92JQ",
+            Error::PrivateKey,
+        ),
+        // invalid prvkey prefix 01
+        (
+            "xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFAzHGBP2UuGCqWLTAPLcMtD9y5gkZ6Eq3Rjuahrv17fEQ3Qen6J",
+            Error::PrivateKey,
+        ),
+        // zero depth with non-zero parent fingerprint
+        (
+            "xprv9s2SPatNQ9Vc6GTbVMFPFo7jsaZySyzk7L8n2uqKXJen3KUmvQNTuLh3fhZMBoG3G4ZW1N2kZuHEPY53qmbZzCHshoQnNf4GvELZfqTUrcv",
+            Error::ParentFingerprint,
+        ),
+        // zero depth with non-zero index
+        (
+            "xprv9s21ZrQH4r4TsiLvyLXqM9P7k1K3EYhA1kkD6xuquB5i39AU8KF42acDyL3qsDbU9NmZn6MsGSUYZEsuoePmjzsB3eFKSUEh3Gu1N3cqVUN",
+            Error::ChildNumber,
+        ),
+        // unknown extended key version
+        (
+            "DMwo58pR1QLEFihHiXPVykYB6fJmsTeHvyTp7hRThAtCX8CvYzgPcn8XnmdfHGMQzT7ayAmfo4z3gY5KfbrZWZ6St24UVf2Qgo6oujFktLHdHY4",
+            Error::Version(0x01010101),
+        ),
+        // private key 0 not in 1..n-1
+        (
+            "xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzF93Y5wvzdUayhgkkFoicQZcP3y52uPPxFnfoLZB21Teqt1VvEHx",
+            Error::PrivateKey,
+        ),
+        // private key n not in 1..n-1
+        (
+            "xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFAzHGBP2UuGCqWLTAPLcMtD5SDKr24z3aiUvKr9bJpdrcLg1y3G",
+            Error::PrivateKey,
+        ),
+        // invalid checksum
+        (
+            "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHL",
+            Error::Base58,
+        ),
+    ];
+
+    for (xprv, error) in xprvs {
+        assert_eq!(xprv.parse::<XPrv>().err(), Some(error), "{xprv}");
+    }
+}
@@ -2,8 +2,9 @@
 
 #![cfg(all(feature = "bip39", feature = "secp256k1"))]
 
-use bip32::{Mnemonic, Seed, XPrv};
+use bip32::{Error, Language, Mnemonic, Seed, WordCount, XPrv};
 use hex_literal::hex;
+use rand_core::OsRng;
 
 /// BIP39 test vector
 struct TestVector {
@@ -13,6 +14,13 @@ struct TestVector {
     xprv: &'static str,
 }
 
+/// BIP39 test vector for a mnemonic shorter than 24 words
+struct ShortTestVector {
+    entropy: &'static [u8],
+    phrase: &'static str,
+    seed: [u8; 64],
+}
+
 /// Password used on all test vectors
 const TEST_VECTOR_PASSWORD: &str = "TREZOR";
 
@@ -68,14 +76,144 @@ const TEST_VECTORS: &[TestVector] = &[
     }
 ];
 
+/// Vectors for mnemonics shorter than 24 words (12 and 18 words).
+///
+/// From: https://github.com/trezor/python-mnemonic/blob/master/vectors.json
+const SHORT_TEST_VECTORS: &[ShortTestVector] = &[
+    ShortTestVector {
+        entropy: &hex!("00000000000000000000000000000000"),
+        phrase: "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        seed: hex!(
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+        ),
+    },
+    ShortTestVector {
+        entropy: &hex!("7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f"),
+        phrase: "legal winner thank year wave sausage worth useful legal winner thank yellow",
+        seed: hex!(
+            "2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607"
+        ),
+    },
+    ShortTestVector {
+        entropy: &hex!("80808080808080808080808080808080"),
+        phrase: "letter advice cage absurd amount doctor acoustic avoid letter advice cage above",
+        seed: hex!(
+            "d71de856f81a8acc65e6fc851a38d4d7ec216fd0796d0a6827a3ad6ed5511a30fa280f12eb2e47ed2ac03b5c462a0358d18d69fe4f985ec81778c1b370b652a8"
+        ),
+    },
+    ShortTestVector {
+        entropy: &hex!("ffffffffffffffffffffffffffffffff"),
+        phrase: "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong",
+        seed: hex!(
+            "ac27495480225222079d7be181583751e86f571027b0497b5b5d11218e0a8a13332572917f0f8e5a589620c6f15b11c61dee327651a14c34e18231052e48c069"
+        ),
+    },
+    ShortTestVector {
+        entropy: &hex!("000000000000000000000000000000000000000000000000"),
+        phrase: "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon agent",
+        seed: hex!(
+            "035895f2f481b1b0f01fcf8c289c794660b289981a78f8106447707fdd9666ca06da5a9a565181599b79f53b844d8a71dd9f439c52a3d7b3e8a79c906ac845fa"
+        ),
+    },
+    ShortTestVector {
+        entropy: &hex!("7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f"),
+        phrase: "legal winner thank year wave sausage worth useful legal winner thank year wave sausage worth useful legal will",
+        seed: hex!(
+            "f2b94508732bcbacbcc020faefecfc89feafa6649a5491b8c952cede496c214a0c7b3c392d168748f2d4a612bada0753b52a1c7ac53c1e93abd5c6320b9e95dd"
+        ),
+    },
+    ShortTestVector {
+        entropy: &hex!("808080808080808080808080808080808080808080808080"),
+        phrase: "letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount doctor acoustic avoid letter always",
+        seed: hex!(
+            "107d7c02a5aa6f38c58083ff74f04c607c2d2c0ecc55501dadd72d025b751bc27fe913ffb796f841c49b1d33b610cf0e91d3aa239027f5e99fe4ce9e5088cd65"
+        ),
+    },
+    ShortTestVector {
+        entropy: &hex!("ffffffffffffffffffffffffffffffffffffffffffffffff"),
+        phrase: "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo when",
+        seed: hex!(
+            "0cd6e5d827bb62eb8fc1e262254223817fd068a74b5b449cc2f667c3f1f985a76379b43348d952e2265b4cd129090758b3e3c2c49103b5051aac2eaeb890a528"
+        ),
+    },
+    ShortTestVector {
+        entropy: &hex!("9e885d952ad362caeb4efe34a8e91bd2"),
+        phrase: "ozone drill grab fiber curtain grace pudding thank cruise elder eight picnic",
+        seed: hex!(
+            "274ddc525802f7c828d8ef7ddbcdc5304e87ac3535913611fbbfa986d0c9e5476c91689f9c8a54fd55bd38606aa6a8595ad213d4c9c9f9aca3fb217069a41028"
+        ),
+    },
+    ShortTestVector {
+        entropy: &hex!("6610b25967cdcca9d59875f5cb50b0ea75433311869e930b"),
+        phrase: "gravity machine north sort system female filter attitude volume fold club stay feature office ecology stable narrow fog",
+        seed: hex!(
+            "628c3827a8823298ee685db84f55caa34b5cc195a778e52d45f59bcf75aba68e4d7590e101dc414bc1bbd5737666fbbef35d1f1903953b66624f910feef245ac"
+        ),
+    },
+    ShortTestVector {
+        entropy: &hex!("c0ba5a8e914111210f2bd131f3d5e08d"),
+        phrase: "scheme spot photo card baby mountain device kick cradle pact join borrow",
+        seed: hex!(
+            "ea725895aaae8d4c1cf682c1bfd2d358d52ed9f0f0591131b559e2724bb234fca05aa9c02c57407e04ee9dc3b454aa63fbff483a8b11de949624b9f1831a9612"
+        ),
+    },
+    ShortTestVector {
+        entropy: &hex!("6d9be1ee6ebd27a258115aad99b7317b9c8d28b6d76431c3"),
+        phrase: "horn tenant knee talent sponsor spell gate clip pulse soap slush warm silver nephew swap uncle crack brave",
+        seed: hex!(
+            "fd579828af3da1d32544ce4db5c73d53fc8acc4ddb1e3b251a31179cdb71e853c56d2fcb11aed39898ce6c34b10b5382772db8796e52837b54468aeb312cfc3d"
+        ),
+    },
+    ShortTestVector {
+        entropy: &hex!("23db8160a31d3e0dca3688ed941adbf3"),
+        phrase: "cat swing flag economy stadium alone churn speed unique patch report train",
+        seed: hex!(
+            "deb5f45449e615feff5640f2e49f933ff51895de3b4381832b3139941c57b59205a42480c52175b6efcffaa58a2503887c1e8b363a707256bdd2b587b46541f5"
+        ),
+    },
+    ShortTestVector {
+        entropy: &hex!("8197a4a47f0425faeaa69deebc05ca29c0a5b5cc76ceacc0"),
+        phrase: "light rule cinnamon wrap drastic word pride squirrel upgrade then income fatal apart sustain crack supply proud access",
+        seed: hex!(
+            "4cbdff1ca2db800fd61cae72a57475fdc6bab03e441fd63f96dabd1f183ef5b782925f00105f318309a7e9c3ea6967c7801e46c8a58082674c860a37b93eda02"
+        ),
+    },
+    ShortTestVector {
+        entropy: &hex!("f30f8c1da665478f49b001d94c5fc452"),
+        phrase: "vessel ladder alter error federal sibling chat ability sun glass valve picture",
+        seed: hex!(
+            "2aaa9242daafcee6aa9d7269f17d4efe271e1b9a529178d7dc139cd18747090bf9d60295d0ce74309a78852a9caadf0af48aae1c6253839624076224374bc63f"
+        ),
+    },
+    ShortTestVector {
+        entropy: &hex!("c10ec20dc3cd9f652c7fac2f1230f7a3c828389a14392f05"),
+        phrase: "scissors invite lock maple supreme raw rapid void congress muscle digital elegant little brisk hair mango congress clump",
+        seed: hex!(
+            "7b4a10be9d98e6cba265566db7f136718e1398c71cb581e1b2f464cac1ceedf4f3e274dc270003c670ad8d02c4558b2f8e39edea2775c9e232c7cb798b069e88"
+        ),
+    },
+];
+
 #[test]
 fn test_mnemonic() {
     for vector in TEST_VECTORS {
-        let mnemonic = Mnemonic::from_entropy(vector.entropy, Default::default());
+        let mnemonic = Mnemonic::from_entropy(&vector.entropy, Default::default()).unwrap();
         assert_eq!(mnemonic.phrase(), vector.phrase);
     }
 }
 
+#[test]
+fn test_short_mnemonic() {
+    for vector in SHORT_TEST_VECTORS {
+        let mnemonic = Mnemonic::from_entropy(vector.entropy, Default::default()).unwrap();
+        assert_eq!(mnemonic.phrase(), vector.phrase);
+        assert_eq!(
+            mnemonic.word_count().words(),
+            vector.phrase.split(' ').count()
+        );
+    }
+}
+
 #[test]
 fn test_seed() {
     for vector in TEST_VECTORS {
@@ -87,6 +225,98 @@ fn test_seed() {
     }
 }
 
+#[test]
+fn test_short_seed() {
+    for vector in SHORT_TEST_VECTORS {
+        let mnemonic = Mnemonic::new(vector.phrase, Default::default()).unwrap();
+        assert_eq!(mnemonic.entropy(), vector.entropy);
+        assert_eq!(
+            &vector.seed,
+            mnemonic.to_seed(TEST_VECTOR_PASSWORD).as_bytes()
+        );
+    }
+}
+
+#[test]
+fn test_random() {
+    for words in [12, 15, 18, 21, 24] {
+        let word_count = WordCount::try_from(words).unwrap();
+        let mnemonic = Mnemonic::random(OsRng, word_count, Default::default());
+        assert_eq!(mnemonic.phrase().split(' ').count(), words);
+        assert_eq!(mnemonic.entropy().len(), word_count.entropy_size());
+
+        let parsed = Mnemonic::new(mnemonic.phrase(), Default::default()).unwrap();
+        assert_eq!(parsed.entropy(), mnemonic.entropy());
+    }
+}
+
+#[test]
+fn test_invalid_checksum() {
+    let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon";
+    assert_eq!(
+        Mnemonic::new(phrase, Default::default()).err(),
+        Some(Error::MnemonicChecksum)
+    );
+}
+
+#[test]
+fn test_unknown_word() {
+    let phrase = "abandon abandon abandon abandon abandon abandom abandon abandon abandon abandon abandon about";
+    assert_eq!(
+        Mnemonic::new(phrase, Default::default()).err(),
+        Some(Error::UnknownWord { index: 5 })
+    );
+}
+
+#[test]
+fn test_whitespace() {
+    let phrase = "  abandon abandon  abandon abandon abandon abandon abandon abandon abandon abandon abandon\tabout\n";
+    let mnemonic = Mnemonic::new(phrase, Default::default()).unwrap();
+    assert_eq!(
+        mnemonic.phrase(),
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+    );
+}
+
+#[test]
+fn test_invalid_length() {
+    let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    assert_eq!(
+        Mnemonic::new(phrase, Default::default()).err(),
+        Some(Error::Bip39)
+    );
+    assert!(Mnemonic::from_entropy(&[0u8; 17], Default::default()).is_err());
+}
+
+#[test]
+fn test_eq() {
+    let mnemonic = Mnemonic::new(TEST_VECTORS[0].phrase, Default::default()).unwrap();
+    let same = Mnemonic::from_entropy(&TEST_VECTORS[0].entropy, Default::default()).unwrap();
+    let other = Mnemonic::new(TEST_VECTORS[1].phrase, Default::default()).unwrap();
+    assert!(mnemonic == same);
+    assert!(mnemonic != other);
+
+    // Same (zero-padded) entropy, different word counts
+    let short = Mnemonic::from_entropy(&[0u8; 16], Default::default()).unwrap();
+    let long = Mnemonic::from_entropy(&[0u8; 32], Default::default()).unwrap();
+    assert!(short != long);
+}
+
+#[cfg(feature = "secrecy")]
+#[test]
+fn test_secret_seed() {
+    use bip32::secrecy::{ExposeSecret, SecretString};
+
+    let password = SecretString::from(TEST_VECTOR_PASSWORD);
+
+    for vector in TEST_VECTORS {
+        let phrase = SecretString::from(vector.phrase);
+        let mnemonic = Mnemonic::new_secret(&phrase, Default::default()).unwrap();
+        assert_eq!(mnemonic.phrase_secret().expose_secret(), vector.phrase);
+        assert_eq!(&vector.seed, mnemonic.to_seed_secret(&password).as_bytes());
+    }
+}
+
 #[test]
 fn test_xprv() {
     for vector in TEST_VECTORS {
@@ -96,3 +326,94 @@ fn test_xprv() {
         assert_eq!(expected_xprv, derived_xprv);
     }
 }
+
+#[test]
+fn test_completions() {
+    let english = Language::English;
+    assert_eq!(english.completions("aban"), ["abandon"]);
+    assert_eq!(
+        english.completions("act"),
+        ["act", "action", "actor", "actress", "actual"]
+    );
+    assert!(english.completions("xyz").is_empty());
+
+    assert_eq!(english.complete_word("aban"), Some("abandon"));
+    assert_eq!(english.complete_word("act"), Some("act"));
+    assert_eq!(english.complete_word("acti"), Some("action"));
+    assert_eq!(english.complete_word("ac"), None);
+    assert_eq!(english.complete_word("xyz"), None);
+}
+
+#[test]
+fn test_suggestions() {
+    let english = Language::English;
+    assert_eq!(english.suggestions("abandom", 1), ["abandon"]);
+    assert_eq!(english.suggestions("abandon", 0), ["abandon"]);
+    assert_eq!(english.suggestions("actr", 1)[0], "act");
+    assert!(english.suggestions("actr", 1).contains(&"actor"));
+    assert!(english.suggestions("zzzzzzzz", 2).is_empty());
+}
+
+#[test]
+fn test_recover_unknown_word() {
+    let phrase = "abandon abandon abandon abandon abandon abandom abandon abandon abandon abandon abandon about";
+    let candidates = Mnemonic::recover(phrase, Default::default()).unwrap();
+    assert!(candidates.len() > 1);
+    assert!(
+        candidates
+            .iter()
+            .any(|candidate| candidate.phrase() == phrase.replace("abandom", "abandon"))
+    );
+
+    for candidate in &candidates {
+        let words = candidate.phrase().split(' ').collect::<Vec<_>>();
+        assert_eq!(words[..5], ["abandon"; 5]);
+        assert_eq!(words[6..11], ["abandon"; 5]);
+        assert_eq!(words[11], "about");
+    }
+}
+
+#[test]
+fn test_recover_incorrect_word() {
+    let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon";
+    let candidates = Mnemonic::recover(phrase, Default::default()).unwrap();
+    assert!(candidates.iter().any(|candidate| candidate.phrase()
+        == "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"));
+}
+
+#[test]
+fn test_recover_missing_word() {
+    let vector = &TEST_VECTORS[0];
+    let mut words = vector.phrase.split(' ').collect::<Vec<_>>();
+    words.remove(7);
+
+    let candidates = Mnemonic::recover(words.join(" "), Default::default()).unwrap();
+    assert!(
+        candidates
+            .iter()
+            .any(|candidate| candidate.phrase() == vector.phrase)
+    );
+}
+
+#[test]
+fn test_recover_valid_phrase() {
+    let vector = &TEST_VECTORS[0];
+    let candidates = Mnemonic::recover(vector.phrase, Default::default()).unwrap();
+    assert_eq!(candidates.len(), 1);
+    assert_eq!(candidates[0].phrase(), vector.phrase);
+}
+
+#[test]
+fn test_recover_errors() {
+    let phrase = "abandon abandon abandon abandon abandon abandom abandon abandon abandon abandom abandon about";
+    assert_eq!(
+        Mnemonic::recover(phrase, Default::default()).err(),
+        Some(Error::UnknownWord { index: 9 })
+    );
+
+    let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    assert_eq!(
+        Mnemonic::recover(phrase, Default::default()).err(),
+        Some(Error::Bip39)
+    );
+}
@@ -0,0 +1,114 @@
+//! BIP85 test vectors.
+//!
+//! <https://github.com/bitcoin/bips/blob/master/bip-0085.mediawiki#test-vectors>
+
+#![cfg(feature = "bip85")]
+
+use bip32::{Error, XPrv};
+use hex_literal::hex;
+
+/// Master key used for all test vectors.
+const MASTER: &str = "xprv9s21ZrQH143K2LBWUUQRFXhucrQqBpKdRRxNVq2zBqsx8HVqFk2uYo8kmbaLLHRdqtQpUm98uKfu3vca1LqdGhUtyoFnCNkfmXRyPXLjbKb";
+
+/// Parse the master key.
+fn master() -> XPrv {
+    MASTER.parse().unwrap()
+}
+
+#[test]
+fn test_case_1() {
+    let entropy = master()
+        .bip85_entropy(&"m/83696968'/0'/0'".parse().unwrap())
+        .unwrap();
+
+    assert_eq!(
+        *entropy,
+        hex!(
+            "efecfbccffea313214232d29e71563d941229afb4338c21f9517c41aaa0d16f0"
+            "0b83d2a09ef747e7a64e8e2bd5a14869e693da66ce94ac2da570ab7ee48618f7"
+        )
+    );
+}
+
+#[test]
+fn test_case_2() {
+    let entropy = master()
+        .bip85_entropy(&"m/83696968'/0'/1'".parse().unwrap())
+        .unwrap();
+
+    assert_eq!(
+        *entropy,
+        hex!(
+            "70c6e3e8ebee8dc4c0dbba66076819bb8c09672527c4277ca8729532ad711872"
+            "218f826919f6b67218adde99018a6df9095ab2b58d803b5b93ec9802085a690e"
+        )
+    );
+}
+
+#[test]
+fn unhardened_path() {
+    assert_eq!(
+        master().bip85_entropy(&"m/83696968'/0'/0".parse().unwrap()),
+        Err(Error::ChildNumber)
+    );
+}
+
+#[cfg(feature = "bip39")]
+#[test]
+fn bip39() {
+    use bip32::{Language, WordCount};
+
+    for (word_count, phrase) in [
+        (
+            WordCount::Words12,
+            "girl mad pet galaxy egg matter matrix prison refuse sense ordinary nose",
+        ),
+        (
+            WordCount::Words18,
+            "near account window bike charge season chef number sketch tomorrow excuse sniff \
+             circle vital hockey outdoor supply token",
+        ),
+        (
+            WordCount::Words24,
+            "puppy ocean match cereal symbol another shed magic wrap hammer bulb intact gadget \
+             divorce twin tonight reason outdoor destroy simple truth cigar social volcano",
+        ),
+    ] {
+        let mnemonic = master()
+            .bip85_mnemonic(Language::English, word_count, 0)
+            .unwrap();
+
+        assert_eq!(mnemonic.phrase(), phrase);
+    }
+}
+
+#[test]
+fn wif() {
+    assert_eq!(
+        master().bip85_wif(0).unwrap().as_str(),
+        "Kzyv4uF39d4Jrw2W7UryTHwZr1zQVNk4dAFyqE6BuMrMh1Za7uhp"
+    );
+}
+
+#[test]
+fn xprv() {
+    assert_eq!(
+        master().bip85_xprv(0).unwrap(),
+        "xprv9s21ZrQH143K2srSbCSg4m4kLvPMzcWydgmKEnMmoZUurYuBuYG46c6P71UGXMzmriLzCCBvKQWBUv3vPB3m1SATMhp3uEjXHJ42jFg7myX"
+            .parse()
+            .unwrap()
+    );
+}
+
+#[test]
+fn hex() {
+    assert_eq!(
+        master().bip85_hex(64, 0).unwrap().as_str(),
+        "492db4698cf3b73a5a24998aa3e9d7fa96275d85724a91e71aa2d645442f8785\
+         55d078fd1f1f67e368976f04137b1f7a0d19232136ca50c44614af72b5582a5c"
+    );
+
+    assert_eq!(master().bip85_hex(35, 0).unwrap().len(), 70);
+    assert_eq!(master().bip85_hex(15, 0), Err(Error::SeedLength));
+    assert_eq!(master().bip85_hex(65, 0), Err(Error::SeedLength));
+}
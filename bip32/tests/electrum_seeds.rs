@@ -0,0 +1,85 @@
+//! Electrum seed phrase test vectors (from Electrum's test suite)
+
+#![cfg(all(feature = "bip39", feature = "secp256k1"))]
+
+use bip32::{ElectrumMnemonic, ElectrumSeedType, Error, Prefix, XPrv};
+use hex_literal::hex;
+
+/// Segwit seed phrase.
+const SEGWIT_PHRASE: &str =
+    "wild father tree among universe such mobile favorite target dynamic credit identify";
+
+/// Passphrase ("seed extension") used with [`SEGWIT_PHRASE`].
+const SEGWIT_PASSPHRASE: &str = "Did you ever hear the tragedy of Darth Plagueis the Wise?";
+
+#[test]
+fn segwit_seed() {
+    let mnemonic = ElectrumMnemonic::new(SEGWIT_PHRASE).unwrap();
+    assert_eq!(mnemonic.seed_type(), ElectrumSeedType::Segwit);
+
+    assert_eq!(
+        mnemonic.to_seed("").as_bytes(),
+        &hex!(
+            "aac2a6302e48577ab4b46f23dbae0774e2e62c796f797d0a1b5faeb528301e30"
+            "64342dafb79069e7c4c6b8c38ae11d7a973bec0d4f70626f8cc5184a8d0b0756"
+        )
+    );
+
+    assert_eq!(
+        mnemonic.to_seed(SEGWIT_PASSPHRASE).as_bytes(),
+        &hex!(
+            "4aa29f2aeb0127efb55138ab9e7be83b36750358751906f86c662b21a1ea1370"
+            "f949e6d1a12fa56d3d93cadda93038c76ac8118597364e46f5156fde6183c82f"
+        )
+    );
+}
+
+#[test]
+fn normalized_phrase() {
+    let mnemonic = ElectrumMnemonic::new(
+        " Wild  FATHER tree among universe such mobile favorite target dynamic credit\nidentify ",
+    )
+    .unwrap();
+    assert_eq!(mnemonic.phrase(), SEGWIT_PHRASE);
+    assert_eq!(mnemonic.seed_type(), ElectrumSeedType::Segwit);
+}
+
+#[test]
+fn standard_wallet() {
+    let mnemonic = ElectrumMnemonic::new(
+        "cycle rocket west magnet parrot shuffle foot correct salt library feed song",
+    )
+    .unwrap();
+    assert_eq!(mnemonic.seed_type(), ElectrumSeedType::Standard);
+    assert_eq!(mnemonic.derivation_path().to_string(), "m");
+
+    let xprv = XPrv::derive_from_path(mnemonic.to_seed(""), &mnemonic.derivation_path()).unwrap();
+    assert_eq!(xprv.attrs().depth, 0);
+}
+
+#[test]
+fn segwit_wallet() {
+    let mnemonic = ElectrumMnemonic::new(
+        "bitter grass shiver impose acquire brush forget axis eager alone wine silver",
+    )
+    .unwrap();
+    assert_eq!(mnemonic.seed_type(), ElectrumSeedType::Segwit);
+    assert_eq!(mnemonic.derivation_path().to_string(), "m/0'");
+    assert_eq!(mnemonic.seed_type().prefix(), Prefix::ZPRV);
+
+    let xprv = XPrv::derive_from_path(mnemonic.to_seed(""), &mnemonic.derivation_path()).unwrap();
+    assert_eq!(xprv.attrs().depth, 1);
+    assert!(xprv.attrs().child_number.is_hardened());
+    assert!(xprv.to_string(Prefix::ZPRV).starts_with("zprv"));
+}
+
+#[test]
+fn bip39_phrase_rejected() {
+    assert_eq!(
+        ElectrumMnemonic::new(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+        )
+        .err(),
+        Some(Error::Electrum)
+    );
+}
@@ -0,0 +1,323 @@
+//! PSBT parsing and serialization test vectors.
+//!
+//! Vectors are from BIP174 and BIP371.
+
+#![cfg(feature = "psbt")]
+
+use bip32::{Error, Psbt};
+use hex_literal::hex;
+
+/// Valid PSBTs from BIP174.
+const BIP174_VALID: &[&[u8]] = &[
+    &hex!(
+        "70736274ff0100750200000001268171371edff285e937adeea4b37b78000c0566cbb3ad64641713"
+        "ca42171bf60000000000feffffff02d3dff505000000001976a914d0c59903c5bac2868760e90fd5"
+        "21a4665aa7652088ac00e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc7"
+        "87b32e1300000100fda5010100000000010289a3c71eab4d20e0371bbba4cc698fa295c9463afa2e"
+        "397f8533ccb62f9567e50100000017160014be18d152a9b012039daf3da7de4f53349eecb985ffff"
+        "ffff86f8aa43a71dff1448893a530a7237ef6b4608bbb2dd2d0171e63aec6a4890b4010000001716"
+        "0014fe3e9ef1a745e974d902c4355943abcb34bd5353ffffffff0200c2eb0b000000001976a91485"
+        "cff1097fd9e008bb34af709c62197b38978a4888ac72fef84e2c00000017a914339725ba21efd62a"
+        "c753a9bcd067d6c7a6a39d05870247304402202712be22e0270f394f568311dc7ca9a68970b8025f"
+        "dd3b240229f07f8a5f3a240220018b38d7dcd314e734c9276bd6fb40f673325bc4baa144c800d2f2"
+        "f02db2765c012103d2e15674941bad4a996372cb87e1856d3652606d98562fe39c5e9e7e413f2105"
+        "02483045022100d12b852d85dcd961d2f5f4ab660654df6eedcc794c0c33ce5cc309ffb5fce58d02"
+        "2067338a8e0e1725c197fb1a88af59f51e44e4255b20167c8684031c05d1f2592a01210223b72bee"
+        "f0965d10be0778efecd61fcac6f79a4ea169393380734464f84f2ab300000000000000"
+    ),
+    &hex!(
+        "70736274ff0100a00200000002ab0949a08c5af7c49b8212f417e2f15ab3f5c33dcf153821a8139f"
+        "877a5b7be40000000000feffffffab0949a08c5af7c49b8212f417e2f15ab3f5c33dcf153821a813"
+        "9f877a5b7be40100000000feffffff02603bea0b000000001976a914768a40bbd740cbe81d988e71"
+        "de2a4d5c71396b1d88ac8e240000000000001976a9146f4620b553fa095e721b9ee0efe9fa039cca"
+        "459788ac000000000001076a47304402204759661797c01b036b25928948686218347d89864b719e"
+        "1f7fcf57d1e511658702205309eabf56aa4d8891ffd111fdf1336f3a29da866d7f8486d75546ceed"
+        "af93190121035cdc61fc7ba971c0b501a646a2a83b102cb43881217ca682dc86e2d73fa882920001"
+        "012000e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787010416001485"
+        "d13537f2e265405a34dbafa9e3dda01fb82308000000"
+    ),
+    &hex!(
+        "70736274ff0100750200000001268171371edff285e937adeea4b37b78000c0566cbb3ad64641713"
+        "ca42171bf60000000000feffffff02d3dff505000000001976a914d0c59903c5bac2868760e90fd5"
+        "21a4665aa7652088ac00e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc7"
+        "87b32e1300000100fda5010100000000010289a3c71eab4d20e0371bbba4cc698fa295c9463afa2e"
+        "397f8533ccb62f9567e50100000017160014be18d152a9b012039daf3da7de4f53349eecb985ffff"
+        "ffff86f8aa43a71dff1448893a530a7237ef6b4608bbb2dd2d0171e63aec6a4890b4010000001716"
+        "0014fe3e9ef1a745e974d902c4355943abcb34bd5353ffffffff0200c2eb0b000000001976a91485"
+        "cff1097fd9e008bb34af709c62197b38978a4888ac72fef84e2c00000017a914339725ba21efd62a"
+        "c753a9bcd067d6c7a6a39d05870247304402202712be22e0270f394f568311dc7ca9a68970b8025f"
+        "dd3b240229f07f8a5f3a240220018b38d7dcd314e734c9276bd6fb40f673325bc4baa144c800d2f2"
+        "f02db2765c012103d2e15674941bad4a996372cb87e1856d3652606d98562fe39c5e9e7e413f2105"
+        "02483045022100d12b852d85dcd961d2f5f4ab660654df6eedcc794c0c33ce5cc309ffb5fce58d02"
+        "2067338a8e0e1725c197fb1a88af59f51e44e4255b20167c8684031c05d1f2592a01210223b72bee"
+        "f0965d10be0778efecd61fcac6f79a4ea169393380734464f84f2ab3000000000103040100000000"
+        "0000"
+    ),
+    &hex!(
+        "70736274ff0100a00200000002ab0949a08c5af7c49b8212f417e2f15ab3f5c33dcf153821a8139f"
+        "877a5b7be40000000000feffffffab0949a08c5af7c49b8212f417e2f15ab3f5c33dcf153821a813"
+        "9f877a5b7be40100000000feffffff02603bea0b000000001976a914768a40bbd740cbe81d988e71"
+        "de2a4d5c71396b1d88ac8e240000000000001976a9146f4620b553fa095e721b9ee0efe9fa039cca"
+        "459788ac00000000000100df0200000001268171371edff285e937adeea4b37b78000c0566cbb3ad"
+        "64641713ca42171bf6000000006a473044022070b2245123e6bf474d60c5b50c043d4c691a5d2435"
+        "f09a34a7662a9dc251790a022001329ca9dacf280bdf30740ec0390422422c81cb45839457aeb76f"
+        "c12edd95b3012102657d118d3357b8e0f4c2cd46db7b39f6d9c38d9a70abcb9b2de5dc8dbfe4ce31"
+        "feffffff02d3dff505000000001976a914d0c59903c5bac2868760e90fd521a4665aa7652088ac00"
+        "e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787b32e13000001012000"
+        "e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787010416001485d13537"
+        "f2e265405a34dbafa9e3dda01fb8230800220202ead596687ca806043edc3de116cdf29d5e9257c1"
+        "96cd055cf698c8d02bf24e9910b4a6ba670000008000000080020000800022020394f62be9df1995"
+        "2c5587768aeb7698061ad2c4a25c894f47d8c162b4d7213d0510b4a6ba6700000080010000800200"
+        "008000"
+    ),
+    &hex!(
+        "70736274ff0100550200000001279a2323a5dfb51fc45f220fa58b0fc13e1e3342792a85d7e36cd6"
+        "333b5cbc390000000000ffffffff01a05aea0b000000001976a914ffe9c0061097cc3b636f2cb046"
+        "0fa4fc427d2b4588ac0000000000010120955eea0b0000000017a9146345200f68d189e1adc0df1c"
+        "4d16ea8f14c0dbeb87220203b1341ccba7683b6af4f1238cd6e97e7167d569fac47f1e48d4754184"
+        "4355bd4646304302200424b58effaaa694e1559ea5c93bbfd4a89064224055cdf070b6771469442d"
+        "07021f5c8eb0fea6516d60b8acb33ad64ede60e8785bfb3aa94b99bdf86151db9a9a010104220020"
+        "771fd18ad459666dd49f3d564e3dbc42f4c84774e360ada16816a8ed488d5681010547522103b134"
+        "1ccba7683b6af4f1238cd6e97e7167d569fac47f1e48d47541844355bd462103de55d1e1dac805e3"
+        "f8a58c1fbf9b94c02f3dbaafe127fefca4995f26f82083bd52ae220603b1341ccba7683b6af4f123"
+        "8cd6e97e7167d569fac47f1e48d47541844355bd4610b4a6ba670000008000000080040000802206"
+        "03de55d1e1dac805e3f8a58c1fbf9b94c02f3dbaafe127fefca4995f26f82083bd10b4a6ba670000"
+        "008000000080050000800000"
+    ),
+    &hex!(
+        "70736274ff01003f0200000001ffffffffffffffffffffffffffffffffffffffffffffffffffffff"
+        "ffffffffff0000000000ffffffff010000000000000000036a010000000000000a0f010203040506"
+        "0708090f0102030405060708090a0b0c0d0e0f0000"
+    ),
+];
+
+/// Valid PSBTs with Taproot fields from BIP371.
+const BIP371_VALID: &[&[u8]] = &[
+    &hex!(
+        "70736274ff010052020000000127744ababf3027fe0d6cf23a96eee2efb188ef52301954585883e6"
+        "9b6624b2420000000000ffffffff0148e6052a01000000160014768e1eeb4cf420866033f80aceff"
+        "0f9720744969000000000001012b00f2052a010000002251205a2c2cf5b52cf31f83ad2e8da63ff0"
+        "3183ecd8f609c7510ae8a48e03910a07572116fe349064c98d6e2a853fa3c9b12bd8b304a19c195c"
+        "60efa7ee2393046d3fa2321900772b2da75600008001000080000000800100000000000000011720"
+        "fe349064c98d6e2a853fa3c9b12bd8b304a19c195c60efa7ee2393046d3fa232002202036b772a6d"
+        "b74d8753c98a827958de6c78ab3312109f37d3e0304484242ece73d818772b2da754000080010000"
+        "8000000080000000000000000000"
+    ),
+    &hex!(
+        "70736274ff010052020000000127744ababf3027fe0d6cf23a96eee2efb188ef52301954585883e6"
+        "9b6624b2420000000000ffffffff0148e6052a01000000160014768e1eeb4cf420866033f80aceff"
+        "0f9720744969000000000001012b00f2052a010000002251205a2c2cf5b52cf31f83ad2e8da63ff0"
+        "3183ecd8f609c7510ae8a48e03910a0757011340bb53ec917bad9d906af1ba87181c48b86ace5aae"
+        "2b53605a725ca74625631476fc6f5baedaf4f2ee0f477f36f58f3970d5b8273b7e497b97af2e3f12"
+        "5c97af342116fe349064c98d6e2a853fa3c9b12bd8b304a19c195c60efa7ee2393046d3fa2321900"
+        "772b2da75600008001000080000000800100000000000000011720fe349064c98d6e2a853fa3c9b1"
+        "2bd8b304a19c195c60efa7ee2393046d3fa232002202036b772a6db74d8753c98a827958de6c78ab"
+        "3312109f37d3e0304484242ece73d818772b2da75400008001000080000000800000000000000000"
+        "00"
+    ),
+    &hex!(
+        "70736274ff01005e020000000127744ababf3027fe0d6cf23a96eee2efb188ef52301954585883e6"
+        "9b6624b2420000000000ffffffff0148e6052a0100000022512083698e458c6664e1595d75da2597"
+        "de1e22ee97d798e706c4c0a4b5a9823cd743000000000001012b00f2052a010000002251205a2c2c"
+        "f5b52cf31f83ad2e8da63ff03183ecd8f609c7510ae8a48e03910a07572116fe349064c98d6e2a85"
+        "3fa3c9b12bd8b304a19c195c60efa7ee2393046d3fa2321900772b2da75600008001000080000000"
+        "800100000000000000011720fe349064c98d6e2a853fa3c9b12bd8b304a19c195c60efa7ee239304"
+        "6d3fa232000105201124da7aec92ccd06c954562647f437b138b95721a84be2bf2276bbddab3e671"
+        "21071124da7aec92ccd06c954562647f437b138b95721a84be2bf2276bbddab3e6711900772b2da7"
+        "560000800100008000000080000000000500000000"
+    ),
+    &hex!(
+        "70736274ff01005e02000000019bd48765230bf9a72e662001f972556e54f0c6f97feb56bcb5600d"
+        "817f6995260100000000ffffffff0148e6052a0100000022512083698e458c6664e1595d75da2597"
+        "de1e22ee97d798e706c4c0a4b5a9823cd743000000000001012b00f2052a01000000225120c2247e"
+        "fbfd92ac47f6f40b8d42d169175a19fa9fa10e4a25d7f35eb4dd85b6926215c150929b74c1a04954"
+        "b78b4b6035e97a5e078a5a0f28ec96d547bfee9ace803ac06f7d62059e9497a1a4a267569d9876da"
+        "60101aff38e3529b9b939ce7f91ae970115f2e490af7cc45c4f78511f36057ce5c5a5c56325a29fb"
+        "44dfc203f356e1f823202cb13ac68248de806aa6a3659cf3c03eb6821d09c8114a4e868febde865b"
+        "b6d2acc04215c150929b74c1a04954b78b4b6035e97a5e078a5a0f28ec96d547bfee9ace803ac097"
+        "c6e6fea5ff714ff5724499990810e406e98aa10f5bf7e5f6784bc1d0a9a6ce23204320b0bf16f011"
+        "b53ea7be615924aa7f27e5d29ad20ea1155d848676c3bad1b2acc06215c150929b74c1a04954b78b"
+        "4b6035e97a5e078a5a0f28ec96d547bfee9ace803ac0cd970e15f53fc0c82f950fd560ffa919b761"
+        "72be017368a89913af074f400b09115f2e490af7cc45c4f78511f36057ce5c5a5c56325a29fb44df"
+        "c203f356e1f82320fa0f7a3cef3b1d0c0a6ce7d26e17ada0b2e5c92d19efad48b41859cb8a451ca9"
+        "acc021162cb13ac68248de806aa6a3659cf3c03eb6821d09c8114a4e868febde865bb6d23901cd97"
+        "0e15f53fc0c82f950fd560ffa919b76172be017368a89913af074f400b09772b2da7560000800100"
+        "008002000080000000000000000021164320b0bf16f011b53ea7be615924aa7f27e5d29ad20ea115"
+        "5d848676c3bad1b23901115f2e490af7cc45c4f78511f36057ce5c5a5c56325a29fb44dfc203f356"
+        "e1f8772b2da75600008001000080010000800000000000000000211650929b74c1a04954b78b4b60"
+        "35e97a5e078a5a0f28ec96d547bfee9ace803ac005007c461e5d2116fa0f7a3cef3b1d0c0a6ce7d2"
+        "6e17ada0b2e5c92d19efad48b41859cb8a451ca939016f7d62059e9497a1a4a267569d9876da6010"
+        "1aff38e3529b9b939ce7f91ae970772b2da756000080010000800300008000000000000000000117"
+        "2050929b74c1a04954b78b4b6035e97a5e078a5a0f28ec96d547bfee9ace803ac0011820f0362e2f"
+        "75a6f420a5bde3eb221d96ae6720cf25f81890c95b1d775acb515e65000105201124da7aec92ccd0"
+        "6c954562647f437b138b95721a84be2bf2276bbddab3e67121071124da7aec92ccd06c954562647f"
+        "437b138b95721a84be2bf2276bbddab3e6711900772b2da756000080010000800000008000000000"
+        "0500000000"
+    ),
+    &hex!(
+        "70736274ff01005e020000000127744ababf3027fe0d6cf23a96eee2efb188ef52301954585883e6"
+        "9b6624b2420000000000ffffffff0148e6052a010000002251200a8cbdc86de1ce1c0f9caeb22d6d"
+        "f7ced3683fe423e05d1e402a879341d6f6f5000000000001012b00f2052a010000002251205a2c2c"
+        "f5b52cf31f83ad2e8da63ff03183ecd8f609c7510ae8a48e03910a07572116fe349064c98d6e2a85"
+        "3fa3c9b12bd8b304a19c195c60efa7ee2393046d3fa2321900772b2da75600008001000080000000"
+        "800100000000000000011720fe349064c98d6e2a853fa3c9b12bd8b304a19c195c60efa7ee239304"
+        "6d3fa2320001052050929b74c1a04954b78b4b6035e97a5e078a5a0f28ec96d547bfee9ace803ac0"
+        "01066f02c02220736e572900fe1252589a2143c8f3c79f71a0412d2353af755e9701c782694a02ac"
+        "02c02220631c5f3b5832b8fbdebfb19704ceeb323c21f40f7a24f43d68ef0cc26b125969ac01c022"
+        "2044faa49a0338de488c8dfffecdfb6f329f380bd566ef20c8df6d813eab1c4273ac210744faa49a"
+        "0338de488c8dfffecdfb6f329f380bd566ef20c8df6d813eab1c42733901f06b798b92a10ed9a9d0"
+        "bbfd3af173a53b1617da3a4159ca008216cd856b2e0e772b2da75600008001000080010000800000"
+        "000003000000210750929b74c1a04954b78b4b6035e97a5e078a5a0f28ec96d547bfee9ace803ac0"
+        "05007c461e5d2107631c5f3b5832b8fbdebfb19704ceeb323c21f40f7a24f43d68ef0cc26b125969"
+        "390118ace409889785e0ea70ceebb8e1ca892a7a78eaede0f2e296cf435961a8f4ca772b2da75600"
+        "0080010000800200008000000000030000002107736e572900fe1252589a2143c8f3c79f71a0412d"
+        "2353af755e9701c782694a02390129a5b4915090162d759afd3fe0f93fa3326056d0b4088cb933ca"
+        "e7826cb8d82c772b2da7560000800100008003000080000000000300000000"
+    ),
+    &hex!(
+        "70736274ff01005e02000000019bd48765230bf9a72e662001f972556e54f0c6f97feb56bcb5600d"
+        "817f6995260100000000ffffffff0148e6052a0100000022512083698e458c6664e1595d75da2597"
+        "de1e22ee97d798e706c4c0a4b5a9823cd743000000000001012b00f2052a01000000225120c2247e"
+        "fbfd92ac47f6f40b8d42d169175a19fa9fa10e4a25d7f35eb4dd85b69241142cb13ac68248de806a"
+        "a6a3659cf3c03eb6821d09c8114a4e868febde865bb6d2cd970e15f53fc0c82f950fd560ffa919b7"
+        "6172be017368a89913af074f400b0940bf818d9757d6ffeb538ba057fb4c1fc4e0f5ef186e765beb"
+        "564791e02af5fd3d5e2551d4e34e33d86f276b82c99c79aed3f0395a081efcd2cc2c65dd7e693d79"
+        "41144320b0bf16f011b53ea7be615924aa7f27e5d29ad20ea1155d848676c3bad1b2115f2e490af7"
+        "cc45c4f78511f36057ce5c5a5c56325a29fb44dfc203f356e1f840e1f1ab6fabfa26b236f2183371"
+        "9dc1d428ab768d80f91f9988d8abef47bfb863bb1f2a529f768c15f00ce34ec283cdc07e88f8428b"
+        "e28f6ef64043c32911811a4114fa0f7a3cef3b1d0c0a6ce7d26e17ada0b2e5c92d19efad48b41859"
+        "cb8a451ca96f7d62059e9497a1a4a267569d9876da60101aff38e3529b9b939ce7f91ae97040ec1f"
+        "0379206461c83342285423326708ab031f0da4a253ee45aafa5b8c92034d8b605490f8cd13e00f98"
+        "9989b97e215faa36f12dee3693d2daccf3781c1757f66215c150929b74c1a04954b78b4b6035e97a"
+        "5e078a5a0f28ec96d547bfee9ace803ac06f7d62059e9497a1a4a267569d9876da60101aff38e352"
+        "9b9b939ce7f91ae970115f2e490af7cc45c4f78511f36057ce5c5a5c56325a29fb44dfc203f356e1"
+        "f823202cb13ac68248de806aa6a3659cf3c03eb6821d09c8114a4e868febde865bb6d2acc04215c1"
+        "50929b74c1a04954b78b4b6035e97a5e078a5a0f28ec96d547bfee9ace803ac097c6e6fea5ff714f"
+        "f5724499990810e406e98aa10f5bf7e5f6784bc1d0a9a6ce23204320b0bf16f011b53ea7be615924"
+        "aa7f27e5d29ad20ea1155d848676c3bad1b2acc06215c150929b74c1a04954b78b4b6035e97a5e07"
+        "8a5a0f28ec96d547bfee9ace803ac0cd970e15f53fc0c82f950fd560ffa919b76172be017368a899"
+        "13af074f400b09115f2e490af7cc45c4f78511f36057ce5c5a5c56325a29fb44dfc203f356e1f823"
+        "20fa0f7a3cef3b1d0c0a6ce7d26e17ada0b2e5c92d19efad48b41859cb8a451ca9acc021162cb13a"
+        "c68248de806aa6a3659cf3c03eb6821d09c8114a4e868febde865bb6d23901cd970e15f53fc0c82f"
+        "950fd560ffa919b76172be017368a89913af074f400b09772b2da756000080010000800200008000"
+        "0000000000000021164320b0bf16f011b53ea7be615924aa7f27e5d29ad20ea1155d848676c3bad1"
+        "b23901115f2e490af7cc45c4f78511f36057ce5c5a5c56325a29fb44dfc203f356e1f8772b2da756"
+        "00008001000080010000800000000000000000211650929b74c1a04954b78b4b6035e97a5e078a5a"
+        "0f28ec96d547bfee9ace803ac005007c461e5d2116fa0f7a3cef3b1d0c0a6ce7d26e17ada0b2e5c9"
+        "2d19efad48b41859cb8a451ca939016f7d62059e9497a1a4a267569d9876da60101aff38e3529b9b"
+        "939ce7f91ae970772b2da7560000800100008003000080000000000000000001172050929b74c1a0"
+        "4954b78b4b6035e97a5e078a5a0f28ec96d547bfee9ace803ac0011820f0362e2f75a6f420a5bde3"
+        "eb221d96ae6720cf25f81890c95b1d775acb515e65000105201124da7aec92ccd06c954562647f43"
+        "7b138b95721a84be2bf2276bbddab3e67121071124da7aec92ccd06c954562647f437b138b95721a"
+        "84be2bf2276bbddab3e6711900772b2da7560000800100008000000080000000000500000000"
+    ),
+];
+
+/// Invalid PSBTs from BIP174.
+const BIP174_INVALID: &[&[u8]] = &[
+    // Invalid magic bytes (a network-serialized transaction)
+    &hex!(
+        "0200000001268171371edff285e937adeea4b37b78000c0566cbb3ad64641713ca42171bf6000000"
+        "006a473044022070b2245123e6bf474d60c5b50c043d4c691a5d2435f09a34a7662a9dc251790a02"
+        "2001329ca9dacf280bdf30740ec0390422422c81cb45839457aeb76fc12edd95b3012102657d118d"
+        "3357b8e0f4c2cd46db7b39f6d9c38d9a70abcb9b2de5dc8dbfe4ce31feffffff02d3dff505000000"
+        "001976a914d0c59903c5bac2868760e90fd521a4665aa7652088ac00e1f5050000000017a9143545"
+        "e6e33b832c47050f24d3eeb93c9c03948bc787b32e1300"
+    ),
+    // Unsigned transaction serialized with witnesses
+    &hex!(
+        "70736274ff0100750200000001268171371edff285e937adeea4b37b78000c0566cbb3ad64641713"
+        "ca42171bf60000000000feffffff02d3dff505000000001976a914d0c59903c5bac2868760e90fd5"
+        "21a4665aa7652088ac00e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc7"
+        "87b32e1300000100fda5010100000000010289a3c71eab4d20e0371bbba4cc698fa295c9463afa2e"
+        "397f8533ccb62f9567e50100000017160014be18d152a9b012039daf3da7de4f53349eecb985ffff"
+        "ffff86f8aa43a71dff1448893a530a7237ef6b4608bbb2dd2d0171e63aec6a4890b4010000001716"
+        "0014fe3e9ef1a745e974d902c4355943abcb34bd5353ffffffff0200c2eb0b000000001976a91485"
+        "cff1097fd9e008bb34af709c62197b38978a4888ac72fef84e2c00000017a914339725ba21efd62a"
+        "c753a9bcd067d6c7a6a39d05870247304402202712be22e0270f394f568311dc7ca9a68970b8025f"
+        "dd3b240229f07f8a5f3a240220018b38d7dcd314e734c9276bd6fb40f673325bc4baa144c800d2f2"
+        "f02db2765c012103d2e15674941bad4a996372cb87e1856d3652606d98562fe39c5e9e7e413f2105"
+        "02483045022100d12b852d85dcd961d2f5f4ab660654df6eedcc794c0c33ce5cc309ffb5fce58d02"
+        "2067338a8e0e1725c197fb1a88af59f51e44e4255b20167c8684031c05d1f2592a01210223b72bee"
+        "f0965d10be0778efecd61fcac6f79a4ea169393380734464f84f2ab30000000000"
+    ),
+    // Unsigned transaction with a scriptSig
+    &hex!(
+        "70736274ff0100fd0a010200000002ab0949a08c5af7c49b8212f417e2f15ab3f5c33dcf153821a8"
+        "139f877a5b7be4000000006a47304402204759661797c01b036b25928948686218347d89864b719e"
+        "1f7fcf57d1e511658702205309eabf56aa4d8891ffd111fdf1336f3a29da866d7f8486d75546ceed"
+        "af93190121035cdc61fc7ba971c0b501a646a2a83b102cb43881217ca682dc86e2d73fa88292feff"
+        "ffffab0949a08c5af7c49b8212f417e2f15ab3f5c33dcf153821a8139f877a5b7be40100000000fe"
+        "ffffff02603bea0b000000001976a914768a40bbd740cbe81d988e71de2a4d5c71396b1d88ac8e24"
+        "0000000000001976a9146f4620b553fa095e721b9ee0efe9fa039cca459788ac0000000000000101"
+        "2000e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787010416001485d1"
+        "3537f2e265405a34dbafa9e3dda01fb82308000000"
+    ),
+    // Missing unsigned transaction
+    &hex!(
+        "70736274ff000100fda5010100000000010289a3c71eab4d20e0371bbba4cc698fa295c9463afa2e"
+        "397f8533ccb62f9567e50100000017160014be18d152a9b012039daf3da7de4f53349eecb985ffff"
+        "ffff86f8aa43a71dff1448893a530a7237ef6b4608bbb2dd2d0171e63aec6a4890b4010000001716"
+        "0014fe3e9ef1a745e974d902c4355943abcb34bd5353ffffffff0200c2eb0b000000001976a91485"
+        "cff1097fd9e008bb34af709c62197b38978a4888ac72fef84e2c00000017a914339725ba21efd62a"
+        "c753a9bcd067d6c7a6a39d05870247304402202712be22e0270f394f568311dc7ca9a68970b8025f"
+        "dd3b240229f07f8a5f3a240220018b38d7dcd314e734c9276bd6fb40f673325bc4baa144c800d2f2"
+        "f02db2765c012103d2e15674941bad4a996372cb87e1856d3652606d98562fe39c5e9e7e413f2105"
+        "02483045022100d12b852d85dcd961d2f5f4ab660654df6eedcc794c0c33ce5cc309ffb5fce58d02"
+        "2067338a8e0e1725c197fb1a88af59f51e44e4255b20167c8684031c05d1f2592a01210223b72bee"
+        "f0965d10be0778efecd61fcac6f79a4ea169393380734464f84f2ab30000000000"
+    ),
+    // Duplicate key in an input map
+    &hex!(
+        "70736274ff0100750200000001268171371edff285e937adeea4b37b78000c0566cbb3ad64641713"
+        "ca42171bf60000000000feffffff02d3dff505000000001976a914d0c59903c5bac2868760e90fd5"
+        "21a4665aa7652088ac00e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc7"
+        "87b32e1300000100fda5010100000000010289a3c71eab4d20e0371bbba4cc698fa295c9463afa2e"
+        "397f8533ccb62f9567e50100000017160014be18d152a9b012039daf3da7de4f53349eecb985ffff"
+        "ffff86f8aa43a71dff1448893a530a7237ef6b4608bbb2dd2d0171e63aec6a4890b4010000001716"
+        "0014fe3e9ef1a745e974d902c4355943abcb34bd5353ffffffff0200c2eb0b000000001976a91485"
+        "cff1097fd9e008bb34af709c62197b38978a4888ac72fef84e2c00000017a914339725ba21efd62a"
+        "c753a9bcd067d6c7a6a39d05870247304402202712be22e0270f394f568311dc7ca9a68970b8025f"
+        "dd3b240229f07f8a5f3a240220018b38d7dcd314e734c9276bd6fb40f673325bc4baa144c800d2f2"
+        "f02db2765c012103d2e15674941bad4a996372cb87e1856d3652606d98562fe39c5e9e7e413f2105"
+        "02483045022100d12b852d85dcd961d2f5f4ab660654df6eedcc794c0c33ce5cc309ffb5fce58d02"
+        "2067338a8e0e1725c197fb1a88af59f51e44e4255b20167c8684031c05d1f2592a01210223b72bee"
+        "f0965d10be0778efecd61fcac6f79a4ea169393380734464f84f2ab30000000001003f0200000001"
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff0000000000ffffff"
+        "ff010000000000000000036a010000000000000000"
+    ),
+];
+
+#[test]
+fn bip174_valid() {
+    for bytes in BIP174_VALID {
+        let psbt = Psbt::from_bytes(bytes).unwrap();
+        assert_eq!(psbt.version(), 0);
+        assert_eq!(&psbt.to_bytes(), bytes);
+    }
+}
+
+#[test]
+fn bip371_valid() {
+    for bytes in BIP371_VALID {
+        let psbt = Psbt::from_bytes(bytes).unwrap();
+        assert_eq!(psbt.input_count(), 1);
+        assert_eq!(psbt.output_count(), 1);
+        assert_eq!(&psbt.to_bytes(), bytes);
+    }
+}
+
+#[test]
+fn bip174_invalid() {
+    for bytes in BIP174_INVALID {
+        assert_eq!(Psbt::from_bytes(bytes), Err(Error::Psbt));
+    }
+}
+
+#[test]
+fn base64_round_trip() {
+    for bytes in BIP174_VALID {
+        let psbt = Psbt::from_bytes(bytes).unwrap();
+        assert_eq!(psbt.to_string().parse::<Psbt>().unwrap(), psbt);
+    }
+}
@@ -0,0 +1,69 @@
+//! `serde` (de)serialization tests
+
+#![cfg(all(feature = "serde", feature = "secp256k1"))]
+
+use bip32::{
+    ChildNumber, DerivationPath, Prefix, SerializableExtendedPrivateKey, XPrv, XPub, secp256k1,
+};
+
+type SerializableXPrv = SerializableExtendedPrivateKey<secp256k1::ecdsa::SigningKey>;
+
+/// BIP32 test vector 1: chain m/0'
+const XPRV: &str = "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7";
+const XPUB: &str = "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw";
+
+#[test]
+fn derivation_path() {
+    let path = "m/44'/0'/0'".parse::<DerivationPath>().unwrap();
+    let json = serde_json::to_string(&path).unwrap();
+    assert_eq!(json, r#""m/44'/0'/0'""#);
+    assert_eq!(serde_json::from_str::<DerivationPath>(&json).unwrap(), path);
+    assert!(serde_json::from_str::<DerivationPath>(r#""44'/0'""#).is_err());
+}
+
+#[test]
+fn child_number() {
+    let child_number = ChildNumber::new(44, true).unwrap();
+    let json = serde_json::to_string(&child_number).unwrap();
+    assert_eq!(json, r#""44'""#);
+    assert_eq!(
+        serde_json::from_str::<ChildNumber>(&json).unwrap(),
+        child_number
+    );
+}
+
+#[test]
+fn prefix() {
+    let json = serde_json::to_string(&Prefix::ZPUB).unwrap();
+    assert_eq!(json, r#""zpub""#);
+    assert_eq!(serde_json::from_str::<Prefix>(&json).unwrap(), Prefix::ZPUB);
+    assert!(serde_json::from_str::<Prefix>(r#""abcd""#).is_err());
+}
+
+#[test]
+fn xpub() {
+    let xpub = XPUB.parse::<XPub>().unwrap();
+    let json = serde_json::to_string(&xpub).unwrap();
+    assert_eq!(json, format!("\"{XPUB}\""));
+    assert_eq!(serde_json::from_str::<XPub>(&json).unwrap(), xpub);
+    assert!(serde_json::from_str::<XPub>(r#""xpub""#).is_err());
+
+    // Other prefixes would be lost when serializing
+    let tpub = xpub.to_string(Prefix::TPUB);
+    assert!(serde_json::from_str::<XPub>(&format!("\"{tpub}\"")).is_err());
+}
+
+#[test]
+fn xprv() {
+    let xprv = SerializableExtendedPrivateKey(XPRV.parse::<XPrv>().unwrap());
+    let json = serde_json::to_string(&xprv).unwrap();
+    assert_eq!(json, format!("\"{XPRV}\""));
+    assert_eq!(
+        serde_json::from_str::<SerializableXPrv>(&json).unwrap(),
+        xprv
+    );
+    assert!(serde_json::from_str::<SerializableXPrv>(&format!("\"{XPUB}\"")).is_err());
+
+    let tprv = xprv.0.to_string(Prefix::TPRV);
+    assert!(serde_json::from_str::<SerializableXPrv>(&format!("\"{}\"", *tprv)).is_err());
+}
@@ -0,0 +1,316 @@
+//! SLIP-10 Test Vectors.
+//!
+//! Sourced from: <https://github.com/satoshilabs/slips/blob/master/slip-0010.md#test-vectors>
+
+#![cfg(all(feature = "alloc", any(feature = "ed25519", feature = "nistp256")))]
+
+use bip32::{ExtendedPrivateKey, PrivateKey};
+use hex_literal::hex;
+
+/// SLIP-10 test vector: keys derived from `seed` along `path`.
+struct TestVector {
+    seed: &'static [u8],
+    path: &'static str,
+    chain_code: [u8; 32],
+    private_key: [u8; 32],
+    public_key: [u8; 33],
+}
+
+/// Check that the keys derived for each test vector match.
+fn check_vectors<K: PrivateKey>(vectors: &[TestVector]) {
+    for vector in vectors {
+        let xprv =
+            ExtendedPrivateKey::<K>::derive_from_path(vector.seed, &vector.path.parse().unwrap())
+                .unwrap();
+
+        assert_eq!(
+            xprv.attrs().chain_code,
+            vector.chain_code,
+            "{}",
+            vector.path
+        );
+        assert_eq!(xprv.to_bytes(), vector.private_key, "{}", vector.path);
+        assert_eq!(
+            xprv.public_key().to_bytes(),
+            vector.public_key,
+            "{}",
+            vector.path
+        );
+    }
+}
+
+/// Test vectors 1 and 2 for ed25519
+#[cfg(feature = "ed25519")]
+const ED25519_VECTORS: &[TestVector] = &[
+    TestVector {
+        seed: &hex!("000102030405060708090a0b0c0d0e0f"),
+        path: "m",
+        chain_code: hex!("90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb"),
+        private_key: hex!("2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7"),
+        public_key: hex!(
+            "00a4b2856bfec510abab89753fac1ac0e1112364e7d250545963f135f2a33188"
+            "ed"
+        ),
+    },
+    TestVector {
+        seed: &hex!("000102030405060708090a0b0c0d0e0f"),
+        path: "m/0'",
+        chain_code: hex!("8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69"),
+        private_key: hex!("68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3"),
+        public_key: hex!(
+            "008c8a13df77a28f3445213a0f432fde644acaa215fc72dcdf300d5efaa85d35"
+            "0c"
+        ),
+    },
+    TestVector {
+        seed: &hex!("000102030405060708090a0b0c0d0e0f"),
+        path: "m/0'/1'",
+        chain_code: hex!("a320425f77d1b5c2505a6b1b27382b37368ee640e3557c315416801243552f14"),
+        private_key: hex!("b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2"),
+        public_key: hex!(
+            "001932a5270f335bed617d5b935c80aedb1a35bd9fc1e31acafd5372c30f5c11"
+            "87"
+        ),
+    },
+    TestVector {
+        seed: &hex!("000102030405060708090a0b0c0d0e0f"),
+        path: "m/0'/1'/2'",
+        chain_code: hex!("2e69929e00b5ab250f49c3fb1c12f252de4fed2c1db88387094a0f8c4c9ccd6c"),
+        private_key: hex!("92a5b23c0b8a99e37d07df3fb9966917f5d06e02ddbd909c7e184371463e9fc9"),
+        public_key: hex!(
+            "00ae98736566d30ed0e9d2f4486a64bc95740d89c7db33f52121f8ea8f76ff0f"
+            "c1"
+        ),
+    },
+    TestVector {
+        seed: &hex!("000102030405060708090a0b0c0d0e0f"),
+        path: "m/0'/1'/2'/2'",
+        chain_code: hex!("8f6d87f93d750e0efccda017d662a1b31a266e4a6f5993b15f5c1f07f74dd5cc"),
+        private_key: hex!("30d1dc7e5fc04c31219ab25a27ae00b50f6fd66622f6e9c913253d6511d1e662"),
+        public_key: hex!(
+            "008abae2d66361c879b900d204ad2cc4984fa2aa344dd7ddc46007329ac76c42"
+            "9c"
+        ),
+    },
+    TestVector {
+        seed: &hex!("000102030405060708090a0b0c0d0e0f"),
+        path: "m/0'/1'/2'/2'/1000000000'",
+        chain_code: hex!("68789923a0cac2cd5a29172a475fe9e0fb14cd6adb5ad98a3fa70333e7afa230"),
+        private_key: hex!("8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793"),
+        public_key: hex!(
+            "003c24da049451555d51a7014a37337aa4e12d41e485abccfa46b47dfb2af54b"
+            "7a"
+        ),
+    },
+    TestVector {
+        seed: &hex!(
+            "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a2"
+            "9f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542"
+        ),
+        path: "m",
+        chain_code: hex!("ef70a74db9c3a5af931b5fe73ed8e1a53464133654fd55e7a66f8570b8e33c3b"),
+        private_key: hex!("171cb88b1b3c1db25add599712e36245d75bc65a1a5c9e18d76f9f2b1eab4012"),
+        public_key: hex!(
+            "008fe9693f8fa62a4305a140b9764c5ee01e455963744fe18204b4fb94824930"
+            "8a"
+        ),
+    },
+    TestVector {
+        seed: &hex!(
+            "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a2"
+            "9f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542"
+        ),
+        path: "m/0'",
+        chain_code: hex!("0b78a3226f915c082bf118f83618a618ab6dec793752624cbeb622acb562862d"),
+        private_key: hex!("1559eb2bbec5790b0c65d8693e4d0875b1747f4970ae8b650486ed7470845635"),
+        public_key: hex!(
+            "0086fab68dcb57aa196c77c5f264f215a112c22a912c10d123b0d03c3c28ef10"
+            "37"
+        ),
+    },
+    TestVector {
+        seed: &hex!(
+            "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a2"
+            "9f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542"
+        ),
+        path: "m/0'/2147483647'",
+        chain_code: hex!("138f0b2551bcafeca6ff2aa88ba8ed0ed8de070841f0c4ef0165df8181eaad7f"),
+        private_key: hex!("ea4f5bfe8694d8bb74b7b59404632fd5968b774ed545e810de9c32a4fb4192f4"),
+        public_key: hex!(
+            "005ba3b9ac6e90e83effcd25ac4e58a1365a9e35a3d3ae5eb07b9e4d90bcf750"
+            "6d"
+        ),
+    },
+    TestVector {
+        seed: &hex!(
+            "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a2"
+            "9f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542"
+        ),
+        path: "m/0'/2147483647'/1'",
+        chain_code: hex!("73bd9fff1cfbde33a1b846c27085f711c0fe2d66fd32e139d3ebc28e5a4a6b90"),
+        private_key: hex!("3757c7577170179c7868353ada796c839135b3d30554bbb74a4b1e4a5a58505c"),
+        public_key: hex!(
+            "002e66aa57069c86cc18249aecf5cb5a9cebbfd6fadeab056254763874a9352b"
+            "45"
+        ),
+    },
+    TestVector {
+        seed: &hex!(
+            "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a2"
+            "9f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542"
+        ),
+        path: "m/0'/2147483647'/1'/2147483646'",
+        chain_code: hex!("0902fe8a29f9140480a00ef244bd183e8a13288e4412d8389d140aac1794825a"),
+        private_key: hex!("5837736c89570de861ebc173b1086da4f505d4adb387c6a1b1342d5e4ac9ec72"),
+        public_key: hex!(
+            "00e33c0f7d81d843c572275f287498e8d408654fdf0d1e065b84e2e6f157aab0"
+            "9b"
+        ),
+    },
+    TestVector {
+        seed: &hex!(
+            "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a2"
+            "9f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542"
+        ),
+        path: "m/0'/2147483647'/1'/2147483646'/2'",
+        chain_code: hex!("5d70af781f3a37b829f0d060924d5e960bdc02e85423494afc0b1a41bbe196d4"),
+        private_key: hex!("551d333177df541ad876a60ea71f00447931c0a9da16f227c11ea080d7391b8d"),
+        public_key: hex!(
+            "0047150c75db263559a70d5778bf36abbab30fb061ad69f69ece61a72b0cfa4f"
+            "c0"
+        ),
+    },
+];
+
+/// Test vectors 1 and 2 for nist256p1, as well as the derivation retry and
+/// seed retry vectors
+#[cfg(feature = "nistp256")]
+const NIST256P1_VECTORS: &[TestVector] = &[
+    TestVector {
+        seed: &hex!("000102030405060708090a0b0c0d0e0f"),
+        path: "m",
+        chain_code: hex!("beeb672fe4621673f722f38529c07392fecaa61015c80c34f29ce8b41b3cb6ea"),
+        private_key: hex!("612091aaa12e22dd2abef664f8a01a82cae99ad7441b7ef8110424915c268bc2"),
+        public_key: hex!(
+            "0266874dc6ade47b3ecd096745ca09bcd29638dd52c2c12117b11ed3e458cfa9"
+            "e8"
+        ),
+    },
+    TestVector {
+        seed: &hex!("000102030405060708090a0b0c0d0e0f"),
+        path: "m/0'",
+        chain_code: hex!("3460cea53e6a6bb5fb391eeef3237ffd8724bf0a40e94943c98b83825342ee11"),
+        private_key: hex!("6939694369114c67917a182c59ddb8cafc3004e63ca5d3b84403ba8613debc0c"),
+        public_key: hex!(
+            "0384610f5ecffe8fda089363a41f56a5c7ffc1d81b59a612d0d649b2d2235559"
+            "0c"
+        ),
+    },
+    TestVector {
+        seed: &hex!("000102030405060708090a0b0c0d0e0f"),
+        path: "m/0'/1",
+        chain_code: hex!("4187afff1aafa8445010097fb99d23aee9f599450c7bd140b6826ac22ba21d0c"),
+        private_key: hex!("284e9d38d07d21e4e281b645089a94f4cf5a5a81369acf151a1c3a57f18b2129"),
+        public_key: hex!(
+            "03526c63f8d0b4bbbf9c80df553fe66742df4676b241dabefdef67733e070f68"
+            "44"
+        ),
+    },
+    TestVector {
+        seed: &hex!("000102030405060708090a0b0c0d0e0f"),
+        path: "m/0'/1/2'",
+        chain_code: hex!("98c7514f562e64e74170cc3cf304ee1ce54d6b6da4f880f313e8204c2a185318"),
+        private_key: hex!("694596e8a54f252c960eb771a3c41e7e32496d03b954aeb90f61635b8e092aa7"),
+        public_key: hex!(
+            "0359cf160040778a4b14c5f4d7b76e327ccc8c4a6086dd9451b7482b5a4972dd"
+            "a0"
+        ),
+    },
+    TestVector {
+        seed: &hex!("000102030405060708090a0b0c0d0e0f"),
+        path: "m/0'/1/2'/2",
+        chain_code: hex!("ba96f776a5c3907d7fd48bde5620ee374d4acfd540378476019eab70790c63a0"),
+        private_key: hex!("5996c37fd3dd2679039b23ed6f70b506c6b56b3cb5e424681fb0fa64caf82aaa"),
+        public_key: hex!(
+            "029f871f4cb9e1c97f9f4de9ccd0d4a2f2a171110c61178f84430062230833ff"
+            "20"
+        ),
+    },
+    TestVector {
+        seed: &hex!("000102030405060708090a0b0c0d0e0f"),
+        path: "m/0'/1/2'/2/1000000000",
+        chain_code: hex!("b9b7b82d326bb9cb5b5b121066feea4eb93d5241103c9e7a18aad40f1dde8059"),
+        private_key: hex!("21c4f269ef0a5fd1badf47eeacebeeaa3de22eb8e5b0adcd0f27dd99d34d0119"),
+        public_key: hex!(
+            "02216cd26d31147f72427a453c443ed2cde8a1e53c9cc44e5ddf739725413fe3"
+            "f4"
+        ),
+    },
+    TestVector {
+        seed: &hex!("000102030405060708090a0b0c0d0e0f"),
+        path: "m",
+        chain_code: hex!("beeb672fe4621673f722f38529c07392fecaa61015c80c34f29ce8b41b3cb6ea"),
+        private_key: hex!("612091aaa12e22dd2abef664f8a01a82cae99ad7441b7ef8110424915c268bc2"),
+        public_key: hex!(
+            "0266874dc6ade47b3ecd096745ca09bcd29638dd52c2c12117b11ed3e458cfa9"
+            "e8"
+        ),
+    },
+    TestVector {
+        seed: &hex!("000102030405060708090a0b0c0d0e0f"),
+        path: "m/28578'",
+        chain_code: hex!("e94c8ebe30c2250a14713212f6449b20f3329105ea15b652ca5bdfc68f6c65c2"),
+        private_key: hex!("06f0db126f023755d0b8d86d4591718a5210dd8d024e3e14b6159d63f53aa669"),
+        public_key: hex!(
+            "02519b5554a4872e8c9c1c847115363051ec43e93400e030ba3c36b52a3e70a5"
+            "b7"
+        ),
+    },
+    TestVector {
+        seed: &hex!("000102030405060708090a0b0c0d0e0f"),
+        path: "m/28578'/33941",
+        chain_code: hex!("9e87fe95031f14736774cd82f25fd885065cb7c358c1edf813c72af535e83071"),
+        private_key: hex!("092154eed4af83e078ff9b84322015aefe5769e31270f62c3f66c33888335f3a"),
+        public_key: hex!(
+            "0235bfee614c0d5b2cae260000bb1d0d84b270099ad790022c1ae0b2e782efe1"
+            "20"
+        ),
+    },
+    TestVector {
+        seed: &hex!("a7305bc8df8d0951f0cb224c0e95d7707cbdf2c6ce7e8d481fec69c7ff5e9446"),
+        path: "m",
+        chain_code: hex!("7762f9729fed06121fd13f326884c82f59aa95c57ac492ce8c9654e60efd130c"),
+        private_key: hex!("3b8c18469a4634517d6d0b65448f8e6c62091b45540a1743c5846be55d47d88f"),
+        public_key: hex!(
+            "0383619fadcde31063d8c5cb00dbfe1713f3e6fa169d8541a798752a1c1ca0cb"
+            "20"
+        ),
+    },
+];
+
+#[cfg(feature = "ed25519")]
+#[test]
+fn ed25519_vectors() {
+    check_vectors::<bip32::ed25519::SigningKey>(ED25519_VECTORS);
+}
+
+/// Test vector 1 for ed25519 lists the parent fingerprint of each key.
+#[cfg(feature = "ed25519")]
+#[test]
+fn ed25519_fingerprint() {
+    let seed = hex!("000102030405060708090a0b0c0d0e0f");
+    let xprv = ExtendedPrivateKey::<bip32::ed25519::SigningKey>::derive_from_path(
+        seed,
+        &"m/0'".parse().unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(xprv.attrs().parent_fingerprint, hex!("ddebc675"));
+}
+
+#[cfg(feature = "nistp256")]
+#[test]
+fn nist256p1_vectors() {
+    check_vectors::<bip32::nistp256::SecretKey>(NIST256P1_VECTORS);
+    check_vectors::<bip32::nistp256::ecdsa::SigningKey>(NIST256P1_VECTORS);
+}
@@ -5,6 +5,9 @@ use crate::{Algorithm, Error, KeyHandle, LoadPkcs8, Result};
 #[cfg(feature = "nistp256")]
 use super::nistp256;
 
+#[cfg(feature = "nistp384")]
+use super::nistp384;
+
 #[cfg(feature = "secp256k1")]
 use super::secp256k1;
 
@@ -15,6 +18,10 @@ pub struct KeyRing {
     #[cfg(feature = "nistp256")]
     pub nistp256: nistp256::KeyRing,
 
+    /// ECDSA/P-384 keys.
+    #[cfg(feature = "nistp384")]
+    pub nistp384: nistp384::KeyRing,
+
     /// ECDSA/secp256k1 keys.
     #[cfg(feature = "secp256k1")]
     pub secp256k1: secp256k1::KeyRing,
@@ -25,10 +32,25 @@ impl LoadPkcs8 for KeyRing {
         match Algorithm::try_from(private_key.algorithm)? {
             #[cfg(feature = "nistp256")]
             Algorithm::EcdsaNistP256 => self.nistp256.load_pkcs8(private_key),
+            #[cfg(feature = "nistp384")]
+            Algorithm::EcdsaNistP384 => self.nistp384.load_pkcs8(private_key),
             #[cfg(feature = "secp256k1")]
             Algorithm::EcdsaSecp256k1 => self.secp256k1.load_pkcs8(private_key),
             #[allow(unreachable_patterns)]
             _ => Err(Error::AlgorithmInvalid),
         }
     }
+
+    fn load_pkcs8_checked(&mut self, private_key: pkcs8::PrivateKeyInfo<'_>) -> Result<KeyHandle> {
+        match Algorithm::try_from(private_key.algorithm)? {
+            #[cfg(feature = "nistp256")]
+            Algorithm::EcdsaNistP256 => self.nistp256.load_pkcs8_checked(private_key),
+            #[cfg(feature = "nistp384")]
+            Algorithm::EcdsaNistP384 => self.nistp384.load_pkcs8_checked(private_key),
+            #[cfg(feature = "secp256k1")]
+            Algorithm::EcdsaSecp256k1 => self.secp256k1.load_pkcs8_checked(private_key),
+            #[allow(unreachable_patterns)]
+            _ => Err(Error::AlgorithmInvalid),
+        }
+    }
 }
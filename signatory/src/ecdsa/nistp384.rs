@@ -4,11 +4,12 @@ pub use p384::ecdsa::{Signature, VerifyingKey};
 
 use crate::{
     Error, KeyHandle, Map, Result,
-    key::{ring::LoadPkcs8, store::GeneratePkcs8},
+    key::{ring::LoadPkcs8, self_test, store::GeneratePkcs8},
 };
 use alloc::boxed::Box;
 use core::fmt;
 use pkcs8::EncodePrivateKey;
+use secrecy::{ExposeSecret, SecretSlice};
 use signature::Signer;
 
 /// ECDSA/P-384 key ring.
@@ -33,11 +34,9 @@ impl KeyRing {
     pub fn iter(&self) -> impl Iterator<Item = &SigningKey> {
         self.keys.values()
     }
-}
 
-impl LoadPkcs8 for KeyRing {
-    fn load_pkcs8(&mut self, private_key: pkcs8::PrivateKeyInfo<'_>) -> Result<KeyHandle> {
-        let signing_key = SigningKey::try_from(private_key)?;
+    /// Add a signing key to the keyring.
+    fn insert(&mut self, signing_key: SigningKey) -> Result<KeyHandle> {
         let verifying_key = signing_key.verifying_key();
 
         if self.keys.contains_key(&verifying_key) {
@@ -49,6 +48,19 @@ impl LoadPkcs8 for KeyRing {
     }
 }
 
+impl LoadPkcs8 for KeyRing {
+    fn load_pkcs8(&mut self, private_key: pkcs8::PrivateKeyInfo<'_>) -> Result<KeyHandle> {
+        self.insert(SigningKey::try_from(private_key)?)
+    }
+
+    fn load_pkcs8_checked(&mut self, private_key: pkcs8::PrivateKeyInfo<'_>) -> Result<KeyHandle> {
+        let public_key = private_key.public_key;
+        let signing_key = SigningKey::try_from(private_key)?;
+        self_test::check_nistp384(&signing_key, public_key)?;
+        self.insert(signing_key)
+    }
+}
+
 /// ECDSA/NIST P-384 signing key.
 pub struct SigningKey {
     inner: Box<dyn NistP384Signer + Send + Sync>,
@@ -68,6 +80,12 @@ impl SigningKey {
         Ok(Self::new(Box::new(signing_key)))
     }
 
+    /// Initialize from a raw scalar value (big endian) held in a
+    /// [`SecretSlice`].
+    pub fn from_secret_bytes(bytes: &SecretSlice<u8>) -> Result<Self> {
+        Self::from_bytes(bytes.expose_secret())
+    }
+
     /// Get the verifying key that corresponds to this signing key.
     pub fn verifying_key(&self) -> VerifyingKey {
         self.inner.verifying_key()
@@ -1,7 +1,7 @@
 //! Ed25519 keyring.
 
 use super::{SigningKey, VerifyingKey};
-use crate::{Error, KeyHandle, LoadPkcs8, Map, Result};
+use crate::{Error, KeyHandle, LoadPkcs8, Map, Result, key::self_test};
 
 /// Ed25519 keyring.
 #[derive(Debug, Default)]
@@ -25,11 +25,9 @@ impl KeyRing {
     pub fn iter(&self) -> impl Iterator<Item = &SigningKey> {
         self.keys.values()
     }
-}
 
-impl LoadPkcs8 for KeyRing {
-    fn load_pkcs8(&mut self, private_key: pkcs8::PrivateKeyInfo<'_>) -> Result<KeyHandle> {
-        let signing_key = SigningKey::try_from(private_key)?;
+    /// Add a signing key to the keyring.
+    fn insert(&mut self, signing_key: SigningKey) -> Result<KeyHandle> {
         let verifying_key = signing_key.verifying_key();
 
         if self.keys.contains_key(&verifying_key) {
@@ -40,3 +38,16 @@ impl LoadPkcs8 for KeyRing {
         Ok(KeyHandle::Ed25519(verifying_key))
     }
 }
+
+impl LoadPkcs8 for KeyRing {
+    fn load_pkcs8(&mut self, private_key: pkcs8::PrivateKeyInfo<'_>) -> Result<KeyHandle> {
+        self.insert(SigningKey::try_from(private_key)?)
+    }
+
+    fn load_pkcs8_checked(&mut self, private_key: pkcs8::PrivateKeyInfo<'_>) -> Result<KeyHandle> {
+        let public_key = private_key.public_key;
+        let signing_key = SigningKey::try_from(private_key)?;
+        self_test::check_ed25519(&signing_key, public_key)?;
+        self.insert(signing_key)
+    }
+}
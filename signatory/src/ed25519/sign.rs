@@ -6,6 +6,7 @@ use alloc::boxed::Box;
 use core::fmt;
 use ed25519_dalek::SECRET_KEY_LENGTH;
 use rand_core::{OsRng, RngCore};
+use secrecy::{ExposeSecret, SecretSlice};
 use signature::Signer;
 use zeroize::Zeroizing;
 
@@ -22,12 +23,18 @@ impl SigningKey {
         Self { inner: signer }
     }
 
-    /// Initialize from a raw scalar value (big endian).
+    /// Initialize from a raw 32-byte Ed25519 seed (i.e. private key).
     pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
         let signing_key = ed25519_dalek::SigningKey::try_from(bytes).map_err(|_| Error::Parse)?;
         Ok(Self::new(Box::new(signing_key)))
     }
 
+    /// Initialize from a raw 32-byte Ed25519 seed (i.e. private key) held in
+    /// a [`SecretSlice`].
+    pub fn from_secret_bytes(bytes: &SecretSlice<u8>) -> Result<Self> {
+        Self::from_bytes(bytes.expose_secret())
+    }
+
     /// Get the verifying key that corresponds to this signing key.
     pub fn verifying_key(&self) -> VerifyingKey {
         self.inner.verifying_key()
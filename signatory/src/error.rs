@@ -12,16 +12,29 @@ pub enum Error {
     /// Algorithm is invalid.
     AlgorithmInvalid,
 
-    /// Duplicate key in keyring.
+    /// Duplicate key in keyring or keystore.
     DuplicateKey,
 
     /// ECDSA errors.
     #[cfg(feature = "ecdsa")]
     Ecdsa,
 
+    /// Encoding errors: a value couldn't be serialized.
+    Encode,
+
+    /// Key failed pairwise consistency check.
+    KeyInconsistent,
+
     /// Key name is invalid.
     KeyNameInvalid,
 
+    /// Key not found in keyring.
+    KeyNotFound,
+
+    /// Keystore is locked in a mode which doesn't permit this operation.
+    #[cfg(feature = "std")]
+    Locked,
+
     /// I/O errors
     #[cfg(feature = "std")]
     Io(std::io::Error),
@@ -33,12 +46,19 @@ pub enum Error {
     /// Parse errors for raw/non-PKCS#8 keys.
     Parse,
 
+    /// Signature errors: signing failed or signature is invalid.
+    Signature,
+
     /// Permissions error, not required mode
     #[cfg(feature = "std")]
     Permissions,
 
     /// PKCS#8 errors
     Pkcs8(pkcs8::Error),
+
+    /// X.509 certificate builder errors
+    #[cfg(feature = "x509")]
+    X509(x509_cert::builder::Error),
 }
 
 impl Display for Error {
@@ -48,7 +68,12 @@ impl Display for Error {
             Self::DuplicateKey => f.write_str("duplicate key"),
             #[cfg(feature = "ecdsa")]
             Self::Ecdsa => f.write_str("ECDSA error"),
+            Self::Encode => f.write_str("encoding error"),
+            Self::KeyInconsistent => f.write_str("key failed pairwise consistency check"),
             Self::KeyNameInvalid => f.write_str("invalid key name"),
+            Self::KeyNotFound => f.write_str("key not found"),
+            #[cfg(feature = "std")]
+            Self::Locked => f.write_str("keystore locked"),
             #[cfg(feature = "std")]
             Self::Io(err) => write!(f, "{}", err),
             #[cfg(feature = "std")]
@@ -57,6 +82,9 @@ impl Display for Error {
             #[cfg(feature = "std")]
             Self::Permissions => f.write_str("invalid file permissions"),
             Self::Pkcs8(err) => write!(f, "{}", err),
+            Self::Signature => f.write_str("signature error"),
+            #[cfg(feature = "x509")]
+            Self::X509(err) => write!(f, "{}", err),
         }
     }
 }
@@ -89,9 +117,22 @@ impl From<pkcs8::der::pem::Error> for Error {
     }
 }
 
+impl From<pkcs8::spki::Error> for Error {
+    fn from(err: pkcs8::spki::Error) -> Error {
+        Error::Pkcs8(err.into())
+    }
+}
+
 #[cfg(feature = "std")]
 impl From<std::io::Error> for Error {
     fn from(err: std::io::Error) -> Error {
         Error::Io(err)
     }
 }
+
+#[cfg(feature = "x509")]
+impl From<x509_cert::builder::Error> for Error {
+    fn from(err: x509_cert::builder::Error) -> Error {
+        Error::X509(err)
+    }
+}
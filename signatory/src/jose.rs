@@ -0,0 +1,284 @@
+//! JSON Object Signing and Encryption (JOSE) support.
+//!
+//! Signs and verifies JSON Web Signatures (JWS, [RFC 7515]) in the compact
+//! serialization using keys held in a [`KeyRing`], and exports verifying keys
+//! as JSON Web Keys (JWK, [RFC 7517]).
+//!
+//! [RFC 7515]: https://www.rfc-editor.org/rfc/rfc7515
+//! [RFC 7517]: https://www.rfc-editor.org/rfc/rfc7517
+
+mod jwk;
+
+pub use self::jwk::Jwk;
+
+use crate::{Error, KeyHandle, KeyRing, Result};
+use alloc::{
+    borrow::ToOwned,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use base64ct::{Base64UrlUnpadded, Encoding};
+use core::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use signature::{Signer, Verifier};
+
+/// JWS algorithms ([RFC 7518] Section 3.1, [RFC 8037], and [RFC 8812]).
+///
+/// [RFC 7518]: https://www.rfc-editor.org/rfc/rfc7518#section-3.1
+/// [RFC 8037]: https://www.rfc-editor.org/rfc/rfc8037
+/// [RFC 8812]: https://www.rfc-editor.org/rfc/rfc8812
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum Algorithm {
+    /// ECDSA using P-256 and SHA-256.
+    Es256,
+
+    /// ECDSA using P-384 and SHA-384.
+    Es384,
+
+    /// ECDSA using secp256k1 and SHA-256.
+    Es256K,
+
+    /// Edwards-curve Digital Signature Algorithm (i.e. Ed25519).
+    EdDsa,
+}
+
+impl Algorithm {
+    /// Get the JWS algorithm used by the key with the given handle.
+    pub fn for_key_handle(key_handle: &KeyHandle) -> Result<Self> {
+        match key_handle {
+            #[cfg(feature = "nistp256")]
+            KeyHandle::EcdsaNistP256(_) => Ok(Self::Es256),
+            #[cfg(feature = "nistp384")]
+            KeyHandle::EcdsaNistP384(_) => Ok(Self::Es384),
+            #[cfg(feature = "secp256k1")]
+            KeyHandle::EcdsaSecp256k1(_) => Ok(Self::Es256K),
+            #[cfg(feature = "ed25519")]
+            KeyHandle::Ed25519(_) => Ok(Self::EdDsa),
+            #[allow(unreachable_patterns)]
+            _ => Err(Error::AlgorithmInvalid),
+        }
+    }
+
+    /// Get the `alg` string identifying this algorithm.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Es256 => "ES256",
+            Self::Es384 => "ES384",
+            Self::Es256K => "ES256K",
+            Self::EdDsa => "EdDSA",
+        }
+    }
+}
+
+impl Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = Error;
+
+    fn from_str(alg: &str) -> Result<Self> {
+        match alg {
+            "ES256" => Ok(Self::Es256),
+            "ES384" => Ok(Self::Es384),
+            "ES256K" => Ok(Self::Es256K),
+            "EdDSA" => Ok(Self::EdDsa),
+            _ => Err(Error::AlgorithmInvalid),
+        }
+    }
+}
+
+/// JOSE header.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Header {
+    /// Algorithm used to sign the JWS.
+    pub alg: String,
+
+    /// Key ID: the JWK thumbprint of the signing key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+
+    /// Media type of the complete JWS (e.g. `JWT`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub typ: Option<String>,
+
+    /// Critical extension header parameters which must be understood.
+    ///
+    /// No extensions are supported, so JWSes with this parameter are
+    /// rejected by [`verify`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crit: Option<Vec<String>>,
+}
+
+impl Header {
+    /// Get the [`Algorithm`] specified in the header.
+    pub fn algorithm(&self) -> Result<Algorithm> {
+        self.alg.parse()
+    }
+}
+
+/// Sign the given payload using the key with the given handle, returning a
+/// compact JWS.
+///
+/// The `kid` header parameter is set to the key's JWK thumbprint.
+pub fn sign(key_ring: &KeyRing, key_handle: KeyHandle, payload: &[u8]) -> Result<String> {
+    sign_with_type(key_ring, key_handle, None, payload)
+}
+
+/// Sign the given claims as a JSON Web Token (JWT, [RFC 7519]) using the key
+/// with the given handle.
+///
+/// [RFC 7519]: https://www.rfc-editor.org/rfc/rfc7519
+pub fn sign_jwt<T: Serialize>(
+    key_ring: &KeyRing,
+    key_handle: KeyHandle,
+    claims: &T,
+) -> Result<String> {
+    let payload = serde_json::to_vec(claims).map_err(|_| Error::Encode)?;
+    sign_with_type(key_ring, key_handle, Some("JWT"), &payload)
+}
+
+/// Verify a compact JWS using the given verifying key, returning its payload.
+///
+/// The `alg` header parameter must match the type of the key. JWSes with a
+/// `crit` header parameter are rejected as invalid, since no extensions are
+/// supported ([RFC 7515 Section 4.1.11]).
+///
+/// [RFC 7515 Section 4.1.11]: https://www.rfc-editor.org/rfc/rfc7515#section-4.1.11
+pub fn verify(token: &str, key_handle: KeyHandle) -> Result<Vec<u8>> {
+    let (signing_input, signature) = token.rsplit_once('.').ok_or(Error::Parse)?;
+    let (_, payload) = signing_input.split_once('.').ok_or(Error::Parse)?;
+    let header = decode_header(token)?;
+
+    if header.crit.is_some() {
+        return Err(Error::Parse);
+    }
+
+    if header.algorithm()? != Algorithm::for_key_handle(&key_handle)? {
+        return Err(Error::AlgorithmInvalid);
+    }
+
+    let signature = Base64UrlUnpadded::decode_vec(signature).map_err(|_| Error::Parse)?;
+    verify_signature(key_handle, signing_input.as_bytes(), &signature)?;
+    Base64UrlUnpadded::decode_vec(payload).map_err(|_| Error::Parse)
+}
+
+/// Verify a JSON Web Token (JWT) using the given verifying key, returning
+/// its claims.
+///
+/// Note that this only verifies the signature: validating registered claims
+/// such as `exp` is the responsibility of the caller.
+pub fn verify_jwt<T: DeserializeOwned>(token: &str, key_handle: KeyHandle) -> Result<T> {
+    let payload = verify(token, key_handle)?;
+    serde_json::from_slice(&payload).map_err(|_| Error::Parse)
+}
+
+/// Decode the header of a compact JWS without verifying it.
+///
+/// This can be used to select a verifying key using the `kid` parameter.
+pub fn decode_header(token: &str) -> Result<Header> {
+    let (header, _) = token.split_once('.').ok_or(Error::Parse)?;
+    let header = Base64UrlUnpadded::decode_vec(header).map_err(|_| Error::Parse)?;
+    serde_json::from_slice(&header).map_err(|_| Error::Parse)
+}
+
+/// Sign the given payload, setting the `typ` header parameter (if provided).
+fn sign_with_type(
+    key_ring: &KeyRing,
+    key_handle: KeyHandle,
+    typ: Option<&str>,
+    payload: &[u8],
+) -> Result<String> {
+    let header = Header {
+        alg: Algorithm::for_key_handle(&key_handle)?.to_string(),
+        kid: Some(Jwk::try_from(key_handle)?.thumbprint()?),
+        typ: typ.map(ToOwned::to_owned),
+        crit: None,
+    };
+
+    let header = serde_json::to_vec(&header).map_err(|_| Error::Encode)?;
+    let signing_input = format!(
+        "{}.{}",
+        Base64UrlUnpadded::encode_string(&header),
+        Base64UrlUnpadded::encode_string(payload)
+    );
+
+    let signature = sign_message(key_ring, key_handle, signing_input.as_bytes())?;
+    Ok(format!(
+        "{}.{}",
+        signing_input,
+        Base64UrlUnpadded::encode_string(&signature)
+    ))
+}
+
+/// Sign a message, returning the signature in its JWS encoding.
+fn sign_message(key_ring: &KeyRing, key_handle: KeyHandle, msg: &[u8]) -> Result<Vec<u8>> {
+    match key_handle {
+        #[cfg(feature = "nistp256")]
+        KeyHandle::EcdsaNistP256(pk) => {
+            let signing_key = key_ring.ecdsa.nistp256.get(&pk).ok_or(Error::KeyNotFound)?;
+            let signature: crate::ecdsa::nistp256::Signature =
+                signing_key.try_sign(msg).map_err(|_| Error::Signature)?;
+            Ok(signature.to_bytes().to_vec())
+        }
+        #[cfg(feature = "nistp384")]
+        KeyHandle::EcdsaNistP384(pk) => {
+            let signing_key = key_ring.ecdsa.nistp384.get(&pk).ok_or(Error::KeyNotFound)?;
+            let signature: crate::ecdsa::nistp384::Signature =
+                signing_key.try_sign(msg).map_err(|_| Error::Signature)?;
+            Ok(signature.to_bytes().to_vec())
+        }
+        #[cfg(feature = "secp256k1")]
+        KeyHandle::EcdsaSecp256k1(pk) => {
+            let signing_key = key_ring
+                .ecdsa
+                .secp256k1
+                .get(&pk)
+                .ok_or(Error::KeyNotFound)?;
+            let signature: crate::ecdsa::secp256k1::Signature =
+                signing_key.try_sign(msg).map_err(|_| Error::Signature)?;
+            Ok(signature.to_bytes().to_vec())
+        }
+        #[cfg(feature = "ed25519")]
+        KeyHandle::Ed25519(pk) => {
+            let signing_key = key_ring.ed25519.get(&pk).ok_or(Error::KeyNotFound)?;
+            let signature: crate::ed25519::Signature =
+                signing_key.try_sign(msg).map_err(|_| Error::Signature)?;
+            Ok(signature.to_bytes().to_vec())
+        }
+        #[allow(unreachable_patterns)]
+        _ => Err(Error::AlgorithmInvalid),
+    }
+}
+
+/// Verify a signature in its JWS encoding.
+fn verify_signature(key_handle: KeyHandle, msg: &[u8], signature: &[u8]) -> Result<()> {
+    match key_handle {
+        #[cfg(feature = "nistp256")]
+        KeyHandle::EcdsaNistP256(pk) => crate::ecdsa::nistp256::Signature::from_slice(signature)
+            .and_then(|signature| pk.verify(msg, &signature))
+            .map_err(|_| Error::Signature),
+        #[cfg(feature = "nistp384")]
+        KeyHandle::EcdsaNistP384(pk) => crate::ecdsa::nistp384::Signature::from_slice(signature)
+            .and_then(|signature| pk.verify(msg, &signature))
+            .map_err(|_| Error::Signature),
+        #[cfg(feature = "secp256k1")]
+        KeyHandle::EcdsaSecp256k1(pk) => crate::ecdsa::secp256k1::Signature::from_slice(signature)
+            .and_then(|signature| pk.verify(msg, &signature))
+            .map_err(|_| Error::Signature),
+        #[cfg(feature = "ed25519")]
+        KeyHandle::Ed25519(pk) => crate::ed25519::Signature::from_slice(signature)
+            .and_then(|signature| pk.verify(msg, &signature))
+            .map_err(|_| Error::Signature),
+        #[allow(unreachable_patterns)]
+        _ => Err(Error::AlgorithmInvalid),
+    }
+}
@@ -0,0 +1,156 @@
+//! JSON Web Keys (JWK).
+
+use crate::{Error, KeyHandle, Result};
+use alloc::{borrow::ToOwned, string::String};
+use base64ct::{Base64UrlUnpadded, Encoding};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+#[cfg(feature = "ecdsa")]
+use alloc::vec::Vec;
+
+/// JSON Web Key ([RFC 7517]) containing an elliptic curve public key.
+///
+/// [RFC 7517]: https://www.rfc-editor.org/rfc/rfc7517
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Jwk {
+    /// Key type: `EC` or `OKP`.
+    pub kty: String,
+
+    /// Curve name, e.g. `P-256` or `Ed25519`.
+    pub crv: String,
+
+    /// Base64url-encoded x-coordinate (or public key for `OKP` keys).
+    pub x: String,
+
+    /// Base64url-encoded y-coordinate (`EC` keys only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub y: Option<String>,
+
+    /// Key ID: the JWK thumbprint of the key when exported by this crate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+
+    /// Algorithm intended for use with the key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alg: Option<String>,
+}
+
+impl Jwk {
+    /// Compute the JWK thumbprint of this key ([RFC 7638]) using SHA-256,
+    /// encoded as base64url.
+    ///
+    /// [RFC 7638]: https://www.rfc-editor.org/rfc/rfc7638
+    pub fn thumbprint(&self) -> Result<String> {
+        /// Required members of the JWK, in lexicographic order.
+        #[derive(Serialize)]
+        struct Members<'a> {
+            crv: &'a str,
+            kty: &'a str,
+            x: &'a str,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            y: Option<&'a str>,
+        }
+
+        let members = serde_json::to_vec(&Members {
+            crv: &self.crv,
+            kty: &self.kty,
+            x: &self.x,
+            y: self.y.as_deref(),
+        })
+        .map_err(|_| Error::Encode)?;
+
+        Ok(Base64UrlUnpadded::encode_string(&Sha256::digest(members)))
+    }
+
+    /// Create a JWK for an `EC` key from its uncompressed SEC1 encoding.
+    #[cfg(feature = "ecdsa")]
+    fn ec(crv: &str, alg: &str, sec1_point: &[u8]) -> Result<Self> {
+        let coordinates = &sec1_point[1..];
+        let (x, y) = coordinates.split_at(coordinates.len() / 2);
+        Self::new("EC", crv, alg, x, Some(y))
+    }
+
+    /// Create a JWK with its `kid` set to its thumbprint.
+    fn new(kty: &str, crv: &str, alg: &str, x: &[u8], y: Option<&[u8]>) -> Result<Self> {
+        let mut jwk = Self {
+            kty: kty.to_owned(),
+            crv: crv.to_owned(),
+            x: Base64UrlUnpadded::encode_string(x),
+            y: y.map(Base64UrlUnpadded::encode_string),
+            kid: None,
+            alg: Some(alg.to_owned()),
+        };
+
+        jwk.kid = Some(jwk.thumbprint()?);
+        Ok(jwk)
+    }
+
+    /// Get the uncompressed SEC1 encoding of an `EC` key.
+    #[cfg(feature = "ecdsa")]
+    fn sec1_point(&self) -> Result<Vec<u8>> {
+        let y = self.y.as_ref().ok_or(Error::Parse)?;
+        let mut point = Vec::from([0x04]);
+        point.extend(Base64UrlUnpadded::decode_vec(&self.x).map_err(|_| Error::Parse)?);
+        point.extend(Base64UrlUnpadded::decode_vec(y).map_err(|_| Error::Parse)?);
+        Ok(point)
+    }
+}
+
+impl TryFrom<KeyHandle> for Jwk {
+    type Error = Error;
+
+    fn try_from(key_handle: KeyHandle) -> Result<Self> {
+        match key_handle {
+            #[cfg(feature = "nistp256")]
+            KeyHandle::EcdsaNistP256(pk) => {
+                Self::ec("P-256", "ES256", pk.to_encoded_point(false).as_bytes())
+            }
+            #[cfg(feature = "nistp384")]
+            KeyHandle::EcdsaNistP384(pk) => {
+                Self::ec("P-384", "ES384", pk.to_encoded_point(false).as_bytes())
+            }
+            #[cfg(feature = "secp256k1")]
+            KeyHandle::EcdsaSecp256k1(pk) => {
+                Self::ec("secp256k1", "ES256K", pk.to_encoded_point(false).as_bytes())
+            }
+            #[cfg(feature = "ed25519")]
+            KeyHandle::Ed25519(pk) => Self::new("OKP", "Ed25519", "EdDSA", pk.as_ref(), None),
+            #[allow(unreachable_patterns)]
+            _ => Err(Error::AlgorithmInvalid),
+        }
+    }
+}
+
+impl TryFrom<&Jwk> for KeyHandle {
+    type Error = Error;
+
+    fn try_from(jwk: &Jwk) -> Result<Self> {
+        match (jwk.kty.as_str(), jwk.crv.as_str()) {
+            #[cfg(feature = "nistp256")]
+            ("EC", "P-256") => {
+                crate::ecdsa::nistp256::VerifyingKey::from_sec1_bytes(&jwk.sec1_point()?)
+                    .map(KeyHandle::EcdsaNistP256)
+                    .map_err(|_| Error::Parse)
+            }
+            #[cfg(feature = "nistp384")]
+            ("EC", "P-384") => {
+                crate::ecdsa::nistp384::VerifyingKey::from_sec1_bytes(&jwk.sec1_point()?)
+                    .map(KeyHandle::EcdsaNistP384)
+                    .map_err(|_| Error::Parse)
+            }
+            #[cfg(feature = "secp256k1")]
+            ("EC", "secp256k1") => {
+                crate::ecdsa::secp256k1::VerifyingKey::from_sec1_bytes(&jwk.sec1_point()?)
+                    .map(KeyHandle::EcdsaSecp256k1)
+                    .map_err(|_| Error::Parse)
+            }
+            #[cfg(feature = "ed25519")]
+            ("OKP", "Ed25519") => {
+                let x = Base64UrlUnpadded::decode_vec(&jwk.x).map_err(|_| Error::Parse)?;
+                crate::ed25519::VerifyingKey::from_bytes(&x).map(KeyHandle::Ed25519)
+            }
+            _ => Err(Error::AlgorithmInvalid),
+        }
+    }
+}
@@ -4,4 +4,6 @@ pub(crate) mod handle;
 pub(crate) mod info;
 pub(crate) mod name;
 pub(crate) mod ring;
+#[cfg(any(feature = "ecdsa", feature = "ed25519"))]
+pub(crate) mod self_test;
 pub(crate) mod store;
@@ -7,6 +7,8 @@ use crate::ecdsa;
 #[cfg(feature = "ed25519")]
 use crate::ed25519;
 
+use pkcs8::EncodePublicKey;
+
 /// Handle to a particular key.
 ///
 /// Uniquely identifies a particular key in the keyring.
@@ -71,3 +73,20 @@ impl KeyHandle {
         }
     }
 }
+
+impl EncodePublicKey for KeyHandle {
+    fn to_public_key_der(&self) -> pkcs8::spki::Result<pkcs8::Document> {
+        match self {
+            #[cfg(feature = "nistp256")]
+            KeyHandle::EcdsaNistP256(pk) => pk.to_public_key_der(),
+            #[cfg(feature = "nistp384")]
+            KeyHandle::EcdsaNistP384(pk) => pk.to_public_key_der(),
+            #[cfg(feature = "secp256k1")]
+            KeyHandle::EcdsaSecp256k1(pk) => pk.to_public_key_der(),
+            #[cfg(feature = "ed25519")]
+            KeyHandle::Ed25519(pk) => pk.to_public_key_der(),
+            #[allow(unreachable_patterns)]
+            _ => Err(pkcs8::spki::Error::KeyMalformed),
+        }
+    }
+}
@@ -10,7 +10,7 @@ use core::{
 
 /// Key names.
 ///
-/// These are constrained to the following characters:
+/// These must be non-empty, and are constrained to the following characters:
 /// - Letters: `a-z`, `A-Z`
 /// - Numbers: `0-9`
 /// - Delimiters: `-`, `_`
@@ -22,6 +22,11 @@ impl KeyName {
     pub fn new(name: impl Into<String>) -> Result<Self> {
         let name = name.into();
 
+        // An empty name would make the key's path refer to the keystore itself
+        if name.is_empty() {
+            return Err(Error::KeyNameInvalid);
+        }
+
         if !name
             .as_bytes()
             .iter()
@@ -25,12 +25,37 @@ impl KeyRing {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Does the keyring contain the key with the given handle?
+    pub fn contains(&self, key_handle: &KeyHandle) -> bool {
+        match key_handle {
+            #[cfg(feature = "nistp256")]
+            KeyHandle::EcdsaNistP256(pk) => self.ecdsa.nistp256.get(pk).is_some(),
+            #[cfg(feature = "nistp384")]
+            KeyHandle::EcdsaNistP384(pk) => self.ecdsa.nistp384.get(pk).is_some(),
+            #[cfg(feature = "secp256k1")]
+            KeyHandle::EcdsaSecp256k1(pk) => self.ecdsa.secp256k1.get(pk).is_some(),
+            #[cfg(feature = "ed25519")]
+            KeyHandle::Ed25519(pk) => self.ed25519.get(pk).is_some(),
+            #[allow(unreachable_patterns)]
+            _ => false,
+        }
+    }
 }
 
 /// Support for loading PKCS#8 private keys.
 pub trait LoadPkcs8 {
     /// Load a PKCS#8 key into the key ring.
     fn load_pkcs8(&mut self, private_key: pkcs8::PrivateKeyInfo<'_>) -> Result<KeyHandle>;
+
+    /// Load a PKCS#8 key into the key ring after performing a pairwise
+    /// consistency check on it.
+    ///
+    /// The check signs and verifies a test message, and if the key contains
+    /// an embedded public key (i.e. PKCS#8 v2), ensures it matches the public
+    /// key derived from the private key. Returns [`Error::KeyInconsistent`]
+    /// if the check fails.
+    fn load_pkcs8_checked(&mut self, private_key: pkcs8::PrivateKeyInfo<'_>) -> Result<KeyHandle>;
 }
 
 impl LoadPkcs8 for KeyRing {
@@ -50,4 +75,21 @@ impl LoadPkcs8 for KeyRing {
 
         Err(Error::AlgorithmInvalid)
     }
+
+    fn load_pkcs8_checked(&mut self, private_key: pkcs8::PrivateKeyInfo<'_>) -> Result<KeyHandle> {
+        #[allow(unused_variables)]
+        let algorithm = Algorithm::try_from(private_key.algorithm)?;
+
+        #[cfg(feature = "ecdsa")]
+        if algorithm.is_ecdsa() {
+            return self.ecdsa.load_pkcs8_checked(private_key);
+        }
+
+        #[cfg(feature = "ed25519")]
+        if algorithm == Algorithm::Ed25519 {
+            return self.ed25519.load_pkcs8_checked(private_key);
+        }
+
+        Err(Error::AlgorithmInvalid)
+    }
 }
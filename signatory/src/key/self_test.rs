@@ -0,0 +1,102 @@
+//! Key self-tests.
+
+use crate::{Error, Result};
+use signature::{Signer, Verifier};
+
+#[cfg(feature = "nistp256")]
+use crate::ecdsa::nistp256;
+
+#[cfg(feature = "nistp384")]
+use crate::ecdsa::nistp384;
+
+#[cfg(feature = "secp256k1")]
+use crate::ecdsa::secp256k1;
+
+#[cfg(feature = "ed25519")]
+use crate::ed25519;
+
+/// Message which is signed and verified during a pairwise consistency check.
+const TEST_MESSAGE: &[u8] = b"signatory pairwise consistency check";
+
+/// Perform a pairwise consistency check on an ECDSA/NIST P-256 key.
+///
+/// `public_key` is the SEC1-encoded public key embedded in the PKCS#8
+/// document the key was parsed from, if any.
+#[cfg(feature = "nistp256")]
+pub(crate) fn check_nistp256(
+    signing_key: &nistp256::SigningKey,
+    public_key: Option<&[u8]>,
+) -> Result<()> {
+    let verifying_key = signing_key.verifying_key();
+    let public_key_matches = public_key.is_none_or(|bytes| {
+        nistp256::VerifyingKey::from_sec1_bytes(bytes).ok() == Some(verifying_key)
+    });
+    check_keypair(signing_key, &verifying_key, public_key_matches)
+}
+
+/// Perform a pairwise consistency check on an ECDSA/NIST P-384 key.
+///
+/// `public_key` is the SEC1-encoded public key embedded in the PKCS#8
+/// document the key was parsed from, if any.
+#[cfg(feature = "nistp384")]
+pub(crate) fn check_nistp384(
+    signing_key: &nistp384::SigningKey,
+    public_key: Option<&[u8]>,
+) -> Result<()> {
+    let verifying_key = signing_key.verifying_key();
+    let public_key_matches = public_key.is_none_or(|bytes| {
+        nistp384::VerifyingKey::from_sec1_bytes(bytes).ok() == Some(verifying_key)
+    });
+    check_keypair(signing_key, &verifying_key, public_key_matches)
+}
+
+/// Perform a pairwise consistency check on an ECDSA/secp256k1 key.
+///
+/// `public_key` is the SEC1-encoded public key embedded in the PKCS#8
+/// document the key was parsed from, if any.
+#[cfg(feature = "secp256k1")]
+pub(crate) fn check_secp256k1(
+    signing_key: &secp256k1::SigningKey,
+    public_key: Option<&[u8]>,
+) -> Result<()> {
+    let verifying_key = signing_key.verifying_key();
+    let public_key_matches = public_key.is_none_or(|bytes| {
+        secp256k1::VerifyingKey::from_sec1_bytes(bytes).ok() == Some(verifying_key)
+    });
+    check_keypair(signing_key, &verifying_key, public_key_matches)
+}
+
+/// Perform a pairwise consistency check on an Ed25519 key.
+///
+/// `public_key` is the public key embedded in the PKCS#8 document the key
+/// was parsed from, if any.
+#[cfg(feature = "ed25519")]
+pub(crate) fn check_ed25519(
+    signing_key: &ed25519::SigningKey,
+    public_key: Option<&[u8]>,
+) -> Result<()> {
+    let verifying_key = signing_key.verifying_key();
+    let public_key_matches = public_key
+        .is_none_or(|bytes| ed25519::VerifyingKey::from_bytes(bytes).ok() == Some(verifying_key));
+    check_keypair(signing_key, &verifying_key, public_key_matches)
+}
+
+/// Ensure the embedded public key (if any) matched the derived one, then sign
+/// [`TEST_MESSAGE`] and verify the resulting signature.
+fn check_keypair<S>(
+    signer: &impl Signer<S>,
+    verifier: &impl Verifier<S>,
+    public_key_matches: bool,
+) -> Result<()> {
+    if !public_key_matches {
+        return Err(Error::KeyInconsistent);
+    }
+
+    let signature = signer
+        .try_sign(TEST_MESSAGE)
+        .map_err(|_| Error::KeyInconsistent)?;
+
+    verifier
+        .verify(TEST_MESSAGE, &signature)
+        .map_err(|_| Error::KeyInconsistent)
+}
@@ -23,6 +23,19 @@ pub mod ecdsa;
 #[cfg(feature = "ed25519")]
 pub mod ed25519;
 
+#[cfg(all(feature = "jose", any(feature = "ecdsa", feature = "ed25519")))]
+pub mod jose;
+
+#[cfg(all(
+    feature = "ssh-agent",
+    unix,
+    any(feature = "ed25519", feature = "nistp256", feature = "nistp384")
+))]
+pub mod ssh_agent;
+
+#[cfg(all(feature = "x509", any(feature = "ecdsa", feature = "ed25519")))]
+pub mod x509;
+
 mod algorithm;
 mod error;
 mod key;
@@ -39,10 +52,11 @@ pub use self::{
     },
 };
 pub use pkcs8;
+pub use secrecy;
 pub use signature;
 
 #[cfg(feature = "std")]
-pub use key::store::fs::FsKeyStore;
+pub use key::store::fs::{BackupInfo, FsKeyStore, LockMode};
 
 /// Map type.
 pub type Map<K, V> = alloc::collections::BTreeMap<K, V>;
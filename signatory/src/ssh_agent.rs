@@ -0,0 +1,317 @@
+//! SSH agent protocol server backed by a [`KeyRing`].
+//!
+//! Implements the subset of the SSH agent protocol ([draft-miller-ssh-agent])
+//! needed to list identities and sign with them, allowing keys held in a
+//! [`KeyRing`] to be used by `ssh`, `git`, and other SSH clients via the
+//! `SSH_AUTH_SOCK` environment variable.
+//!
+//! Supported key types are Ed25519 and ECDSA with NIST P-256 or P-384, so
+//! this module is only available when at least one of the corresponding
+//! features is enabled in addition to `ssh-agent`.
+//!
+//! [draft-miller-ssh-agent]: https://datatracker.ietf.org/doc/html/draft-miller-ssh-agent
+
+mod encoding;
+
+use self::encoding::{Decoder, Encoder};
+use crate::{Error, KeyHandle, KeyRing, Result};
+use alloc::{sync::Arc, vec::Vec};
+use signature::Signer;
+use std::{
+    io::{self, Read, Write},
+    os::unix::net::UnixListener,
+    thread,
+};
+
+/// Maximum size of an agent protocol message.
+const MAX_MESSAGE_SIZE: usize = 256 * 1024;
+
+/// Comment reported for identities held by the agent.
+const IDENTITY_COMMENT: &str = "signatory";
+
+/// Agent protocol message numbers.
+const SSH_AGENT_FAILURE: u8 = 5;
+const SSH_AGENTC_REQUEST_IDENTITIES: u8 = 11;
+const SSH_AGENT_IDENTITIES_ANSWER: u8 = 12;
+const SSH_AGENTC_SIGN_REQUEST: u8 = 13;
+const SSH_AGENT_SIGN_RESPONSE: u8 = 14;
+
+/// SSH key algorithm names.
+#[cfg(feature = "ed25519")]
+const SSH_ED25519: &str = "ssh-ed25519";
+#[cfg(feature = "nistp256")]
+const ECDSA_SHA2_NISTP256: &str = "ecdsa-sha2-nistp256";
+#[cfg(feature = "nistp384")]
+const ECDSA_SHA2_NISTP384: &str = "ecdsa-sha2-nistp384";
+
+/// Prefix of the names of ECDSA key algorithms, whose public key blobs
+/// include a curve name ([RFC 5656 Section 3.1]).
+///
+/// [RFC 5656 Section 3.1]: https://www.rfc-editor.org/rfc/rfc5656#section-3.1
+const ECDSA_SHA2_PREFIX: &str = "ecdsa-sha2-";
+
+/// SSH agent which serves the keys in a [`KeyRing`].
+#[derive(Clone, Debug)]
+pub struct Agent {
+    key_ring: Arc<KeyRing>,
+}
+
+impl Agent {
+    /// Create a new agent which serves the keys in the given keyring.
+    pub fn new(key_ring: KeyRing) -> Self {
+        Self {
+            key_ring: Arc::new(key_ring),
+        }
+    }
+
+    /// Accept connections on the given listener, serving each one on its
+    /// own thread.
+    ///
+    /// Errors serving an individual connection (e.g. a client disconnecting
+    /// partway through a message) only end that connection, and are
+    /// discarded. Use [`Agent::handle_connection`] directly to observe them.
+    ///
+    /// This function only returns if accepting a connection fails.
+    pub fn serve(&self, listener: &UnixListener) -> Result<()> {
+        loop {
+            let (stream, _) = listener.accept()?;
+            let agent = self.clone();
+            thread::spawn(move || agent.handle_connection(stream));
+        }
+    }
+
+    /// Serve agent protocol requests on the given connection until the
+    /// client disconnects.
+    pub fn handle_connection(&self, mut stream: impl Read + Write) -> Result<()> {
+        while let Some(request) = read_message(&mut stream)? {
+            let response = self.handle_request(&request);
+            write_message(&mut stream, &response)?;
+        }
+
+        Ok(())
+    }
+
+    /// Handle an individual request message, returning the response message.
+    ///
+    /// Malformed or unsupported requests receive an `SSH_AGENT_FAILURE`
+    /// response.
+    pub fn handle_request(&self, request: &[u8]) -> Vec<u8> {
+        let mut decoder = Decoder::new(request);
+
+        let response = match decoder.read_u8() {
+            Ok(SSH_AGENTC_REQUEST_IDENTITIES) => self.identities(),
+            Ok(SSH_AGENTC_SIGN_REQUEST) => self.sign(decoder),
+            _ => Err(Error::Parse),
+        };
+
+        response.unwrap_or_else(|_| Vec::from([SSH_AGENT_FAILURE]))
+    }
+
+    /// Handle an `SSH_AGENTC_REQUEST_IDENTITIES` request.
+    fn identities(&self) -> Result<Vec<u8>> {
+        let key_handles = self.key_handles();
+        let mut encoder = Encoder::new();
+        encoder.write_u8(SSH_AGENT_IDENTITIES_ANSWER);
+        encoder.write_u32(key_handles.len() as u32);
+
+        for key_handle in key_handles {
+            encoder.write_string(&encode_public_key(&key_handle)?);
+            encoder.write_string(IDENTITY_COMMENT.as_bytes());
+        }
+
+        Ok(encoder.finish())
+    }
+
+    /// Handle an `SSH_AGENTC_SIGN_REQUEST` request.
+    fn sign(&self, mut decoder: Decoder<'_>) -> Result<Vec<u8>> {
+        let key_handle = decode_public_key(decoder.read_string()?)?;
+        let data = decoder.read_string()?;
+
+        // Flags are only defined for RSA keys, so they're ignored
+        let _flags = decoder.read_u32()?;
+        decoder.finish()?;
+
+        let mut encoder = Encoder::new();
+        encoder.write_u8(SSH_AGENT_SIGN_RESPONSE);
+        encoder.write_string(&self.sign_data(key_handle, data)?);
+        Ok(encoder.finish())
+    }
+
+    /// Sign the given data, returning an SSH signature blob.
+    fn sign_data(&self, key_handle: KeyHandle, data: &[u8]) -> Result<Vec<u8>> {
+        let mut encoder = Encoder::new();
+
+        match key_handle {
+            #[cfg(feature = "nistp256")]
+            KeyHandle::EcdsaNistP256(pk) => {
+                let signing_key = self.key_ring.ecdsa.nistp256.get(&pk);
+                let signature: crate::ecdsa::nistp256::Signature = signing_key
+                    .ok_or(Error::KeyNotFound)?
+                    .try_sign(data)
+                    .map_err(|_| Error::Signature)?;
+                let (r, s) = signature.split_bytes();
+                encoder.write_str(ECDSA_SHA2_NISTP256);
+                encoder.write_string(&encode_ecdsa_signature(&r, &s));
+            }
+            #[cfg(feature = "nistp384")]
+            KeyHandle::EcdsaNistP384(pk) => {
+                let signing_key = self.key_ring.ecdsa.nistp384.get(&pk);
+                let signature: crate::ecdsa::nistp384::Signature = signing_key
+                    .ok_or(Error::KeyNotFound)?
+                    .try_sign(data)
+                    .map_err(|_| Error::Signature)?;
+                let (r, s) = signature.split_bytes();
+                encoder.write_str(ECDSA_SHA2_NISTP384);
+                encoder.write_string(&encode_ecdsa_signature(&r, &s));
+            }
+            #[cfg(feature = "ed25519")]
+            KeyHandle::Ed25519(pk) => {
+                let signing_key = self.key_ring.ed25519.get(&pk);
+                let signature: crate::ed25519::Signature = signing_key
+                    .ok_or(Error::KeyNotFound)?
+                    .try_sign(data)
+                    .map_err(|_| Error::Signature)?;
+                encoder.write_str(SSH_ED25519);
+                encoder.write_string(&signature.to_bytes());
+            }
+            #[allow(unreachable_patterns)]
+            _ => return Err(Error::AlgorithmInvalid),
+        }
+
+        Ok(encoder.finish())
+    }
+
+    /// Get the handles of all keys in the keyring usable with SSH.
+    fn key_handles(&self) -> Vec<KeyHandle> {
+        let mut key_handles = Vec::new();
+
+        #[cfg(feature = "ed25519")]
+        key_handles.extend(
+            self.key_ring
+                .ed25519
+                .iter()
+                .map(|key| KeyHandle::Ed25519(key.verifying_key())),
+        );
+
+        #[cfg(feature = "nistp256")]
+        key_handles.extend(
+            self.key_ring
+                .ecdsa
+                .nistp256
+                .iter()
+                .map(|key| KeyHandle::EcdsaNistP256(key.verifying_key())),
+        );
+
+        #[cfg(feature = "nistp384")]
+        key_handles.extend(
+            self.key_ring
+                .ecdsa
+                .nistp384
+                .iter()
+                .map(|key| KeyHandle::EcdsaNistP384(key.verifying_key())),
+        );
+
+        key_handles
+    }
+}
+
+/// Encode a public key as an SSH public key blob.
+pub fn encode_public_key(key_handle: &KeyHandle) -> Result<Vec<u8>> {
+    let mut encoder = Encoder::new();
+
+    match key_handle {
+        #[cfg(feature = "nistp256")]
+        KeyHandle::EcdsaNistP256(pk) => {
+            encoder.write_str(ECDSA_SHA2_NISTP256);
+            encoder.write_str("nistp256");
+            encoder.write_string(pk.to_encoded_point(false).as_bytes());
+        }
+        #[cfg(feature = "nistp384")]
+        KeyHandle::EcdsaNistP384(pk) => {
+            encoder.write_str(ECDSA_SHA2_NISTP384);
+            encoder.write_str("nistp384");
+            encoder.write_string(pk.to_encoded_point(false).as_bytes());
+        }
+        #[cfg(feature = "ed25519")]
+        KeyHandle::Ed25519(pk) => {
+            encoder.write_str(SSH_ED25519);
+            encoder.write_string(pk.as_ref());
+        }
+        #[allow(unreachable_patterns)]
+        _ => return Err(Error::AlgorithmInvalid),
+    }
+
+    Ok(encoder.finish())
+}
+
+/// Decode an SSH public key blob.
+pub fn decode_public_key(blob: &[u8]) -> Result<KeyHandle> {
+    let mut decoder = Decoder::new(blob);
+    let algorithm = decoder.read_str()?;
+
+    let curve = if algorithm.starts_with(ECDSA_SHA2_PREFIX) {
+        Some(decoder.read_str()?)
+    } else {
+        None
+    };
+
+    let key_handle = match (algorithm, curve) {
+        #[cfg(feature = "nistp256")]
+        (ECDSA_SHA2_NISTP256, Some("nistp256")) => {
+            crate::ecdsa::nistp256::VerifyingKey::from_sec1_bytes(decoder.read_string()?)
+                .map(KeyHandle::EcdsaNistP256)
+                .map_err(|_| Error::Parse)?
+        }
+        #[cfg(feature = "nistp384")]
+        (ECDSA_SHA2_NISTP384, Some("nistp384")) => {
+            crate::ecdsa::nistp384::VerifyingKey::from_sec1_bytes(decoder.read_string()?)
+                .map(KeyHandle::EcdsaNistP384)
+                .map_err(|_| Error::Parse)?
+        }
+        #[cfg(feature = "ed25519")]
+        (SSH_ED25519, None) => crate::ed25519::VerifyingKey::from_bytes(decoder.read_string()?)
+            .map(KeyHandle::Ed25519)?,
+        _ => return Err(Error::AlgorithmInvalid),
+    };
+
+    decoder.finish()?;
+    Ok(key_handle)
+}
+
+/// Encode the body of an ECDSA signature: `mpint r || mpint s`.
+#[cfg(any(feature = "nistp256", feature = "nistp384"))]
+fn encode_ecdsa_signature(r: &[u8], s: &[u8]) -> Vec<u8> {
+    let mut encoder = Encoder::new();
+    encoder.write_mpint(r);
+    encoder.write_mpint(s);
+    encoder.finish()
+}
+
+/// Read a length-prefixed agent protocol message, returning `None` if the
+/// stream was closed.
+fn read_message(reader: &mut impl Read) -> Result<Option<Vec<u8>>> {
+    let mut len = [0u8; 4];
+
+    match reader.read_exact(&mut len) {
+        Ok(()) => (),
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(err) => return Err(err.into()),
+    }
+
+    let len = u32::from_be_bytes(len) as usize;
+
+    if len > MAX_MESSAGE_SIZE {
+        return Err(Error::Parse);
+    }
+
+    let mut message = alloc::vec![0u8; len];
+    reader.read_exact(&mut message)?;
+    Ok(Some(message))
+}
+
+/// Write a length-prefixed agent protocol message.
+fn write_message(writer: &mut impl Write, message: &[u8]) -> Result<()> {
+    writer.write_all(&(message.len() as u32).to_be_bytes())?;
+    writer.write_all(message)?;
+    Ok(writer.flush()?)
+}
@@ -0,0 +1,112 @@
+//! SSH wire encoding ([RFC 4251] Section 5).
+//!
+//! [RFC 4251]: https://www.rfc-editor.org/rfc/rfc4251#section-5
+
+use crate::{Error, Result};
+use alloc::vec::Vec;
+
+/// Decoder for SSH wire-encoded data.
+pub(super) struct Decoder<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    /// Create a new decoder for the given bytes.
+    pub(super) fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    /// Read a single byte.
+    pub(super) fn read_u8(&mut self) -> Result<u8> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    /// Read a big endian `uint32`.
+    pub(super) fn read_u32(&mut self) -> Result<u32> {
+        let bytes = self.read_bytes(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Read a length-prefixed byte string.
+    pub(super) fn read_string(&mut self) -> Result<&'a [u8]> {
+        let len = self.read_u32()? as usize;
+        self.read_bytes(len)
+    }
+
+    /// Read a length-prefixed UTF-8 string.
+    pub(super) fn read_str(&mut self) -> Result<&'a str> {
+        core::str::from_utf8(self.read_string()?).map_err(|_| Error::Parse)
+    }
+
+    /// Ensure all input has been consumed.
+    pub(super) fn finish(self) -> Result<()> {
+        if self.bytes.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Parse)
+        }
+    }
+
+    /// Read the given number of bytes.
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        if len > self.bytes.len() {
+            return Err(Error::Parse);
+        }
+
+        let (bytes, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(bytes)
+    }
+}
+
+/// Encoder for SSH wire-encoded data.
+pub(super) struct Encoder {
+    bytes: Vec<u8>,
+}
+
+impl Encoder {
+    /// Create a new encoder.
+    pub(super) fn new() -> Self {
+        Self { bytes: Vec::new() }
+    }
+
+    /// Write a single byte.
+    pub(super) fn write_u8(&mut self, byte: u8) {
+        self.bytes.push(byte);
+    }
+
+    /// Write a big endian `uint32`.
+    pub(super) fn write_u32(&mut self, n: u32) {
+        self.bytes.extend_from_slice(&n.to_be_bytes());
+    }
+
+    /// Write a length-prefixed byte string.
+    pub(super) fn write_string(&mut self, bytes: &[u8]) {
+        self.write_u32(bytes.len() as u32);
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    /// Write a length-prefixed UTF-8 string.
+    pub(super) fn write_str(&mut self, s: &str) {
+        self.write_string(s.as_bytes());
+    }
+
+    /// Write an unsigned big endian integer as an `mpint`.
+    #[cfg(any(feature = "nistp256", feature = "nistp384"))]
+    pub(super) fn write_mpint(&mut self, n: &[u8]) {
+        let n = &n[n.iter().position(|&byte| byte != 0).unwrap_or(n.len())..];
+
+        if n.first().is_some_and(|&byte| byte & 0x80 != 0) {
+            self.write_u32(n.len() as u32 + 1);
+            self.bytes.push(0);
+            self.bytes.extend_from_slice(n);
+        } else {
+            self.write_string(n);
+        }
+    }
+
+    /// Finish encoding, returning the encoded bytes.
+    pub(super) fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+}
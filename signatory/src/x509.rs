@@ -0,0 +1,176 @@
+//! X.509 certificate and PKCS#10 certificate signing request (CSR) support.
+//!
+//! Certificates and CSRs are signed using keys held in a [`KeyRing`],
+//! selected by their [`KeyHandle`].
+//!
+//! This module is only available when at least one signature algorithm
+//! feature is enabled in addition to `x509`.
+
+pub use x509_cert::{
+    self, Certificate, builder::Profile, name::Name, request::CertReq, serial_number::SerialNumber,
+    time::Validity,
+};
+
+use crate::{Error, KeyHandle, KeyRing, Result};
+use alloc::boxed::Box;
+use pkcs8::{
+    der::asn1::BitString,
+    spki::{
+        AlgorithmIdentifierOwned, DynSignatureAlgorithmIdentifier, SignatureBitStringEncoding,
+        SubjectPublicKeyInfoOwned,
+    },
+};
+use signature::{Keypair, Signer};
+use x509_cert::builder::{Builder, CertificateBuilder, RequestBuilder};
+
+/// Build a PKCS#10 certificate signing request for the key with the given
+/// handle, signed by that key.
+pub fn certificate_request(
+    key_ring: &KeyRing,
+    key_handle: KeyHandle,
+    subject: Name,
+) -> Result<CertReq> {
+    let signer = KeyRingSigner::new(key_ring, key_handle)?;
+    Ok(RequestBuilder::new(subject, &signer)?.build::<Signature>()?)
+}
+
+/// Build a self-signed X.509 certificate for the key with the given handle.
+pub fn self_signed_certificate(
+    key_ring: &KeyRing,
+    key_handle: KeyHandle,
+    profile: Profile,
+    serial_number: SerialNumber,
+    validity: Validity,
+    subject: Name,
+) -> Result<Certificate> {
+    let signer = KeyRingSigner::new(key_ring, key_handle)?;
+    let public_key = SubjectPublicKeyInfoOwned::from_key(key_handle)?;
+
+    Ok(CertificateBuilder::new(
+        profile,
+        serial_number,
+        validity,
+        subject,
+        public_key,
+        &signer,
+    )?
+    .build::<Signature>()?)
+}
+
+/// Signer for a particular key in a [`KeyRing`].
+///
+/// Can be used with the builders in [`x509_cert::builder`] to issue
+/// certificates signed by keys in the keyring.
+#[derive(Debug)]
+pub struct KeyRingSigner<'a> {
+    key_ring: &'a KeyRing,
+    key_handle: KeyHandle,
+}
+
+impl<'a> KeyRingSigner<'a> {
+    /// Create a signer for the key with the given handle.
+    ///
+    /// Returns [`Error::KeyNotFound`] if the key isn't in the keyring.
+    pub fn new(key_ring: &'a KeyRing, key_handle: KeyHandle) -> Result<Self> {
+        if !key_ring.contains(&key_handle) {
+            return Err(Error::KeyNotFound);
+        }
+
+        Ok(Self {
+            key_ring,
+            key_handle,
+        })
+    }
+}
+
+impl Keypair for KeyRingSigner<'_> {
+    type VerifyingKey = KeyHandle;
+
+    fn verifying_key(&self) -> KeyHandle {
+        self.key_handle
+    }
+}
+
+impl DynSignatureAlgorithmIdentifier for KeyRingSigner<'_> {
+    fn signature_algorithm_identifier(&self) -> pkcs8::spki::Result<AlgorithmIdentifierOwned> {
+        let oid = match self.key_handle {
+            #[cfg(feature = "nistp256")]
+            KeyHandle::EcdsaNistP256(_) => ::ecdsa::ECDSA_SHA256_OID,
+            #[cfg(feature = "nistp384")]
+            KeyHandle::EcdsaNistP384(_) => ::ecdsa::ECDSA_SHA384_OID,
+            #[cfg(feature = "secp256k1")]
+            KeyHandle::EcdsaSecp256k1(_) => ::ecdsa::ECDSA_SHA256_OID,
+            #[cfg(feature = "ed25519")]
+            KeyHandle::Ed25519(_) => crate::ed25519::ALGORITHM_OID,
+            #[allow(unreachable_patterns)]
+            _ => return Err(pkcs8::spki::Error::KeyMalformed),
+        };
+
+        Ok(AlgorithmIdentifierOwned {
+            oid,
+            parameters: None,
+        })
+    }
+}
+
+impl Signer<Signature> for KeyRingSigner<'_> {
+    fn try_sign(&self, msg: &[u8]) -> signature::Result<Signature> {
+        let bytes: Box<[u8]> = match self.key_handle {
+            #[cfg(feature = "nistp256")]
+            KeyHandle::EcdsaNistP256(pk) => {
+                let signing_key = self.key_ring.ecdsa.nistp256.get(&pk);
+                let signature: crate::ecdsa::nistp256::Signature = signing_key
+                    .ok_or_else(signature::Error::new)?
+                    .try_sign(msg)?;
+                signature.to_der().as_bytes().into()
+            }
+            #[cfg(feature = "nistp384")]
+            KeyHandle::EcdsaNistP384(pk) => {
+                let signing_key = self.key_ring.ecdsa.nistp384.get(&pk);
+                let signature: crate::ecdsa::nistp384::Signature = signing_key
+                    .ok_or_else(signature::Error::new)?
+                    .try_sign(msg)?;
+                signature.to_der().as_bytes().into()
+            }
+            #[cfg(feature = "secp256k1")]
+            KeyHandle::EcdsaSecp256k1(pk) => {
+                let signing_key = self.key_ring.ecdsa.secp256k1.get(&pk);
+                let signature: crate::ecdsa::secp256k1::Signature = signing_key
+                    .ok_or_else(signature::Error::new)?
+                    .try_sign(msg)?;
+                signature.to_der().as_bytes().into()
+            }
+            #[cfg(feature = "ed25519")]
+            KeyHandle::Ed25519(pk) => {
+                let signing_key = self.key_ring.ed25519.get(&pk);
+                let signature: crate::ed25519::Signature = signing_key
+                    .ok_or_else(signature::Error::new)?
+                    .try_sign(msg)?;
+                signature.to_bytes().as_slice().into()
+            }
+            #[allow(unreachable_patterns)]
+            _ => return Err(signature::Error::new()),
+        };
+
+        Ok(Signature(bytes))
+    }
+}
+
+/// Signature as encoded in an X.509 certificate or CSR.
+///
+/// ECDSA signatures are ASN.1 DER encoded, and Ed25519 signatures are raw.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Signature(Box<[u8]>);
+
+impl Signature {
+    /// Borrow the encoded signature as a byte slice.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl SignatureBitStringEncoding for Signature {
+    fn to_bitstring(&self) -> pkcs8::der::Result<BitString> {
+        BitString::from_bytes(&self.0)
+    }
+}
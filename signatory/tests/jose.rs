@@ -0,0 +1,159 @@
+//! JOSE integration tests
+
+#![cfg(all(feature = "jose", any(feature = "ecdsa", feature = "ed25519")))]
+
+use signatory::{
+    GeneratePkcs8, KeyHandle, KeyRing, LoadPkcs8,
+    jose::{self, Algorithm, Jwk},
+};
+
+/// Load a newly generated key into a keyring.
+fn generate_key<K: GeneratePkcs8>(key_ring: &mut KeyRing) -> KeyHandle {
+    let private_key = K::generate_pkcs8();
+    key_ring
+        .load_pkcs8(private_key.decode_msg().unwrap())
+        .unwrap()
+}
+
+/// Sign and verify a payload, checking the resulting header.
+fn sign_and_verify(key_ring: &KeyRing, key_handle: KeyHandle, alg: Algorithm) {
+    let payload = b"Hello, world!";
+    let token = jose::sign(key_ring, key_handle, payload).unwrap();
+
+    let header = jose::decode_header(&token).unwrap();
+    assert_eq!(header.algorithm().unwrap(), alg);
+    assert_eq!(
+        header.kid.unwrap(),
+        Jwk::try_from(key_handle).unwrap().thumbprint().unwrap()
+    );
+
+    assert_eq!(jose::verify(&token, key_handle).unwrap(), payload);
+
+    // Round trip the verifying key through a JWK
+    let jwk: Jwk =
+        serde_json::from_str(&serde_json::to_string(&Jwk::try_from(key_handle).unwrap()).unwrap())
+            .unwrap();
+    assert_eq!(KeyHandle::try_from(&jwk).unwrap(), key_handle);
+
+    // Tamper with the payload
+    let mut parts = token.split('.').collect::<Vec<_>>();
+    parts[1] = "SGVsbG8sIHdvcmxkPw";
+    assert!(jose::verify(&parts.join("."), key_handle).is_err());
+}
+
+#[cfg(feature = "nistp256")]
+#[test]
+fn es256() {
+    let mut key_ring = KeyRing::new();
+    let key_handle = generate_key::<signatory::ecdsa::nistp256::SigningKey>(&mut key_ring);
+    sign_and_verify(&key_ring, key_handle, Algorithm::Es256);
+}
+
+#[cfg(feature = "nistp384")]
+#[test]
+fn es384() {
+    let mut key_ring = KeyRing::new();
+    let key_handle = generate_key::<signatory::ecdsa::nistp384::SigningKey>(&mut key_ring);
+    sign_and_verify(&key_ring, key_handle, Algorithm::Es384);
+}
+
+#[cfg(feature = "secp256k1")]
+#[test]
+fn es256k() {
+    let mut key_ring = KeyRing::new();
+    let key_handle = generate_key::<signatory::ecdsa::secp256k1::SigningKey>(&mut key_ring);
+    sign_and_verify(&key_ring, key_handle, Algorithm::Es256K);
+}
+
+#[cfg(feature = "ed25519")]
+#[test]
+fn eddsa() {
+    let mut key_ring = KeyRing::new();
+    let key_handle = generate_key::<signatory::ed25519::SigningKey>(&mut key_ring);
+    sign_and_verify(&key_ring, key_handle, Algorithm::EdDsa);
+}
+
+#[cfg(feature = "ed25519")]
+#[test]
+fn jwt() {
+    let mut key_ring = KeyRing::new();
+    let key_handle = generate_key::<signatory::ed25519::SigningKey>(&mut key_ring);
+    let claims = serde_json::json!({ "sub": "1234567890", "admin": true });
+
+    let token = jose::sign_jwt(&key_ring, key_handle, &claims).unwrap();
+    assert_eq!(
+        jose::decode_header(&token).unwrap().typ.as_deref(),
+        Some("JWT")
+    );
+
+    let verified: serde_json::Value = jose::verify_jwt(&token, key_handle).unwrap();
+    assert_eq!(verified, claims);
+}
+
+#[cfg(all(feature = "ed25519", feature = "nistp256"))]
+#[test]
+fn algorithm_mismatch() {
+    let mut key_ring = KeyRing::new();
+    let ed25519_key = generate_key::<signatory::ed25519::SigningKey>(&mut key_ring);
+    let p256_key = generate_key::<signatory::ecdsa::nistp256::SigningKey>(&mut key_ring);
+
+    let token = jose::sign(&key_ring, ed25519_key, b"example").unwrap();
+    assert!(matches!(
+        jose::verify(&token, p256_key),
+        Err(signatory::Error::AlgorithmInvalid)
+    ));
+}
+
+#[cfg(feature = "ed25519")]
+#[test]
+fn unknown_critical_extension() {
+    use base64ct::{Base64UrlUnpadded, Encoding};
+    use signatory::signature::Signer;
+
+    let mut key_ring = KeyRing::new();
+    let key_handle = generate_key::<signatory::ed25519::SigningKey>(&mut key_ring);
+    let signing_key = key_ring
+        .ed25519
+        .get(&key_handle.ed25519().unwrap())
+        .unwrap();
+
+    let header = br#"{"alg":"EdDSA","crit":["exp"],"exp":1363284000}"#;
+    let signing_input = format!(
+        "{}.{}",
+        Base64UrlUnpadded::encode_string(header),
+        Base64UrlUnpadded::encode_string(b"example")
+    );
+    let signature: signatory::ed25519::Signature =
+        signing_key.try_sign(signing_input.as_bytes()).unwrap();
+    let token = format!(
+        "{}.{}",
+        signing_input,
+        Base64UrlUnpadded::encode_string(&signature.to_bytes())
+    );
+
+    assert!(matches!(
+        jose::verify(&token, key_handle),
+        Err(signatory::Error::Parse)
+    ));
+}
+
+/// Ed25519 JWK and JWS from RFC 8037 Appendix A.
+#[cfg(feature = "ed25519")]
+#[test]
+fn rfc8037_test_vectors() {
+    let jwk: Jwk = serde_json::from_str(
+        r#"{"kty":"OKP","crv":"Ed25519","x":"11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo"}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        jwk.thumbprint().unwrap(),
+        "kPrK_qmxVWaYVA9wwBF6Iuo3vVzz7TxHCTwXBygrS4k"
+    );
+
+    let token = "eyJhbGciOiJFZERTQSJ9.RXhhbXBsZSBvZiBFZDI1NTE5IHNpZ25pbmc.hgyY0il_MGCjP0JzlnLWG1PPOt7-09PGcvMg3AIbQR6dWbhijcNR4ki4iylGjg5BhVsPt9g7sVvpAr_MuM0KAg";
+    let key_handle = KeyHandle::try_from(&jwk).unwrap();
+    assert_eq!(
+        jose::verify(token, key_handle).unwrap(),
+        b"Example of Ed25519 signing"
+    );
+}
@@ -0,0 +1,97 @@
+//! Tests for initializing signing keys from raw bytes held in a `SecretSlice`
+
+#![cfg(any(feature = "ecdsa", feature = "ed25519"))]
+
+use signatory::secrecy::SecretSlice;
+
+#[cfg(feature = "nistp256")]
+#[test]
+fn nistp256_from_secret_bytes() {
+    use signatory::{
+        ecdsa::nistp256::{Signature, SigningKey},
+        signature::{Signer, Verifier},
+    };
+
+    let bytes = [0x42; 32];
+    let signing_key = SigningKey::from_secret_bytes(&SecretSlice::from(bytes.to_vec())).unwrap();
+    let verifying_key = SigningKey::from_bytes(&bytes).unwrap().verifying_key();
+    assert_eq!(signing_key.verifying_key(), verifying_key);
+
+    let signature: Signature = signing_key.sign(b"example");
+    assert!(verifying_key.verify(b"example", &signature).is_ok());
+
+    let too_long = SecretSlice::from(vec![0x42; 33]);
+    assert!(SigningKey::from_secret_bytes(&too_long).is_err());
+}
+
+#[cfg(feature = "nistp384")]
+#[test]
+fn nistp384_from_secret_bytes() {
+    use signatory::{
+        ecdsa::nistp384::{Signature, SigningKey},
+        signature::{Signer, Verifier},
+    };
+
+    let bytes = [0x42; 48];
+    let signing_key = SigningKey::from_secret_bytes(&SecretSlice::from(bytes.to_vec())).unwrap();
+    let verifying_key = SigningKey::from_bytes(&bytes).unwrap().verifying_key();
+    assert_eq!(signing_key.verifying_key(), verifying_key);
+
+    let signature: Signature = signing_key.sign(b"example");
+    assert!(verifying_key.verify(b"example", &signature).is_ok());
+
+    let too_long = SecretSlice::from(vec![0x42; 49]);
+    assert!(SigningKey::from_secret_bytes(&too_long).is_err());
+}
+
+#[cfg(feature = "secp256k1")]
+#[test]
+fn secp256k1_from_secret_bytes() {
+    use signatory::{
+        ecdsa::secp256k1::{Signature, SigningKey},
+        signature::{Signer, Verifier},
+    };
+
+    let bytes = [0x42; 32];
+    let signing_key = SigningKey::from_secret_bytes(&SecretSlice::from(bytes.to_vec())).unwrap();
+    let verifying_key = SigningKey::from_bytes(&bytes).unwrap().verifying_key();
+    assert_eq!(signing_key.verifying_key(), verifying_key);
+
+    let signature: Signature = signing_key.sign(b"example");
+    assert!(verifying_key.verify(b"example", &signature).is_ok());
+
+    let too_long = SecretSlice::from(vec![0x42; 33]);
+    assert!(SigningKey::from_secret_bytes(&too_long).is_err());
+}
+
+/// Ed25519 test vector from RFC 8032 Section 7.1 (TEST 1).
+#[cfg(feature = "ed25519")]
+#[test]
+fn ed25519_from_secret_bytes() {
+    use signatory::{
+        ed25519::{Signature, SigningKey},
+        signature::{Signer, Verifier},
+    };
+
+    let seed = hex_decode("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60");
+    let public_key = hex_decode("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a");
+
+    let signing_key = SigningKey::from_secret_bytes(&SecretSlice::from(seed)).unwrap();
+    let verifying_key = signing_key.verifying_key();
+    assert_eq!(verifying_key.as_ref(), public_key.as_slice());
+
+    let signature: Signature = signing_key.sign(b"example");
+    assert!(verifying_key.verify(b"example", &signature).is_ok());
+
+    let too_short = SecretSlice::from(vec![0x42; 31]);
+    assert!(SigningKey::from_secret_bytes(&too_short).is_err());
+}
+
+/// Decode a hex string.
+#[cfg(feature = "ed25519")]
+fn hex_decode(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
@@ -0,0 +1,227 @@
+//! SSH agent integration tests
+
+#![cfg(all(feature = "ssh-agent", unix))]
+#![cfg(all(feature = "ed25519", feature = "nistp256", feature = "nistp384"))]
+
+use signatory::{
+    GeneratePkcs8, KeyHandle, KeyRing, LoadPkcs8,
+    signature::Verifier,
+    ssh_agent::{self, Agent},
+};
+use std::{
+    io::{Read, Write},
+    os::unix::net::{UnixListener, UnixStream},
+    thread,
+};
+
+const SSH_AGENT_FAILURE: u8 = 5;
+const SSH_AGENTC_REQUEST_IDENTITIES: u8 = 11;
+const SSH_AGENT_IDENTITIES_ANSWER: u8 = 12;
+const SSH_AGENTC_SIGN_REQUEST: u8 = 13;
+const SSH_AGENT_SIGN_RESPONSE: u8 = 14;
+
+/// Minimal SSH agent client.
+struct Client(UnixStream);
+
+impl Client {
+    fn request(&mut self, message: &[u8]) -> Vec<u8> {
+        self.0
+            .write_all(&(message.len() as u32).to_be_bytes())
+            .unwrap();
+        self.0.write_all(message).unwrap();
+
+        let mut len = [0u8; 4];
+        self.0.read_exact(&mut len).unwrap();
+        let mut response = vec![0u8; u32::from_be_bytes(len) as usize];
+        self.0.read_exact(&mut response).unwrap();
+        response
+    }
+
+    fn identities(&mut self) -> Vec<Vec<u8>> {
+        let response = self.request(&[SSH_AGENTC_REQUEST_IDENTITIES]);
+        let mut reader = Reader(&response);
+        assert_eq!(reader.u8(), SSH_AGENT_IDENTITIES_ANSWER);
+
+        (0..reader.u32())
+            .map(|_| {
+                let key_blob = reader.string().to_vec();
+                assert_eq!(reader.string(), b"signatory");
+                key_blob
+            })
+            .collect()
+    }
+
+    fn sign(&mut self, key_blob: &[u8], data: &[u8]) -> Vec<u8> {
+        let mut request = vec![SSH_AGENTC_SIGN_REQUEST];
+        write_string(&mut request, key_blob);
+        write_string(&mut request, data);
+        request.extend_from_slice(&0u32.to_be_bytes());
+
+        let response = self.request(&request);
+        let mut reader = Reader(&response);
+        assert_eq!(reader.u8(), SSH_AGENT_SIGN_RESPONSE);
+        reader.string().to_vec()
+    }
+}
+
+/// Reader for SSH wire-encoded data.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn u8(&mut self) -> u8 {
+        self.bytes(1)[0]
+    }
+
+    fn u32(&mut self) -> u32 {
+        u32::from_be_bytes(self.bytes(4).try_into().unwrap())
+    }
+
+    fn string(&mut self) -> &'a [u8] {
+        let len = self.u32() as usize;
+        self.bytes(len)
+    }
+
+    /// Read an `mpint`, left-padding it to the given size.
+    fn mpint(&mut self, size: usize) -> Vec<u8> {
+        let n = self.string();
+        let n = n.strip_prefix(&[0]).unwrap_or(n);
+        let mut padded = vec![0u8; size - n.len()];
+        padded.extend_from_slice(n);
+        padded
+    }
+
+    fn bytes(&mut self, len: usize) -> &'a [u8] {
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        bytes
+    }
+}
+
+fn write_string(buf: &mut Vec<u8>, bytes: &[u8]) {
+    buf.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+    buf.extend_from_slice(bytes);
+}
+
+/// Load a newly generated key into a keyring.
+fn generate_key<K: GeneratePkcs8>(key_ring: &mut KeyRing) -> KeyHandle {
+    let private_key = K::generate_pkcs8();
+    key_ring
+        .load_pkcs8(private_key.decode_msg().unwrap())
+        .unwrap()
+}
+
+/// Start an agent serving the given keyring, returning a connected client.
+fn start_agent(key_ring: KeyRing, dir: &tempfile::TempDir) -> Client {
+    let socket_path = dir.path().join("agent.sock");
+    let listener = UnixListener::bind(&socket_path).unwrap();
+    let agent = Agent::new(key_ring);
+    thread::spawn(move || agent.serve(&listener));
+    Client(UnixStream::connect(&socket_path).unwrap())
+}
+
+#[test]
+fn list_identities_and_sign() {
+    let mut key_ring = KeyRing::new();
+    let ed25519_key = generate_key::<signatory::ed25519::SigningKey>(&mut key_ring);
+    let p256_key = generate_key::<signatory::ecdsa::nistp256::SigningKey>(&mut key_ring);
+    let p384_key = generate_key::<signatory::ecdsa::nistp384::SigningKey>(&mut key_ring);
+
+    let dir = tempfile::tempdir().unwrap();
+    let mut client = start_agent(key_ring, &dir);
+
+    let identities = client.identities();
+    assert_eq!(identities.len(), 3);
+
+    let data = b"session identifier";
+    for key_blob in identities {
+        let key_handle = ssh_agent::decode_public_key(&key_blob).unwrap();
+        let signature = client.sign(&key_blob, data);
+        let mut reader = Reader(&signature);
+        let algorithm = reader.string();
+        let signature = reader.string();
+
+        if key_handle == ed25519_key {
+            assert_eq!(algorithm, b"ssh-ed25519");
+            let signature = signatory::ed25519::Signature::from_slice(signature).unwrap();
+            assert!(
+                ed25519_key
+                    .ed25519()
+                    .unwrap()
+                    .verify(data, &signature)
+                    .is_ok()
+            );
+        } else if key_handle == p256_key {
+            assert_eq!(algorithm, b"ecdsa-sha2-nistp256");
+            let mut reader = Reader(signature);
+            let (r, s) = (reader.mpint(32), reader.mpint(32));
+            let signature = signatory::ecdsa::nistp256::Signature::from_scalars(
+                <[u8; 32]>::try_from(r).unwrap(),
+                <[u8; 32]>::try_from(s).unwrap(),
+            )
+            .unwrap();
+            assert!(
+                p256_key
+                    .ecdsa_nistp256()
+                    .unwrap()
+                    .verify(data, &signature)
+                    .is_ok()
+            );
+        } else if key_handle == p384_key {
+            assert_eq!(algorithm, b"ecdsa-sha2-nistp384");
+            let mut reader = Reader(signature);
+            let (r, s) = (reader.mpint(48), reader.mpint(48));
+            let signature = signatory::ecdsa::nistp384::Signature::from_scalars(
+                <[u8; 48]>::try_from(r).unwrap(),
+                <[u8; 48]>::try_from(s).unwrap(),
+            )
+            .unwrap();
+            assert!(
+                p384_key
+                    .ecdsa_nistp384()
+                    .unwrap()
+                    .verify(data, &signature)
+                    .is_ok()
+            );
+        } else {
+            panic!("unexpected identity");
+        }
+    }
+}
+
+#[test]
+fn unknown_key_and_unsupported_request() {
+    let mut key_ring = KeyRing::new();
+    let ed25519_key = generate_key::<signatory::ed25519::SigningKey>(&mut key_ring);
+    let key_blob = ssh_agent::encode_public_key(&ed25519_key).unwrap();
+
+    let dir = tempfile::tempdir().unwrap();
+    let mut client = start_agent(KeyRing::new(), &dir);
+
+    let mut request = vec![SSH_AGENTC_SIGN_REQUEST];
+    write_string(&mut request, &key_blob);
+    write_string(&mut request, b"data");
+    request.extend_from_slice(&0u32.to_be_bytes());
+    assert_eq!(client.request(&request), [SSH_AGENT_FAILURE]);
+
+    // SSH_AGENTC_ADD_IDENTITY
+    assert_eq!(client.request(&[17]), [SSH_AGENT_FAILURE]);
+}
+
+#[test]
+fn decode_public_key() {
+    let mut key_ring = KeyRing::new();
+    let p256_key = generate_key::<signatory::ecdsa::nistp256::SigningKey>(&mut key_ring);
+    let key_blob = ssh_agent::encode_public_key(&p256_key).unwrap();
+    assert_eq!(ssh_agent::decode_public_key(&key_blob).unwrap(), p256_key);
+
+    // A P-256 key with a mismatched curve name
+    let point = p256_key.ecdsa_nistp256().unwrap().to_encoded_point(false);
+    let mut mismatched_blob = Vec::new();
+    write_string(&mut mismatched_blob, b"ecdsa-sha2-nistp256");
+    write_string(&mut mismatched_blob, b"nistp384");
+    write_string(&mut mismatched_blob, point.as_bytes());
+    assert!(matches!(
+        ssh_agent::decode_public_key(&mismatched_blob),
+        Err(signatory::Error::AlgorithmInvalid)
+    ));
+}
@@ -0,0 +1,110 @@
+//! X.509 certificate and CSR integration tests
+
+#![cfg(all(feature = "x509", any(feature = "ecdsa", feature = "ed25519")))]
+
+use core::{str::FromStr, time::Duration};
+use signatory::{
+    GeneratePkcs8, KeyHandle, KeyRing, LoadPkcs8,
+    pkcs8::der::{Decode, Encode},
+    signature::Verifier,
+    x509::{self, CertReq, Certificate, Name, Profile, SerialNumber, Validity},
+};
+
+/// Load a newly generated key into a keyring.
+fn generate_key<K: GeneratePkcs8>(key_ring: &mut KeyRing) -> KeyHandle {
+    let private_key = K::generate_pkcs8();
+    key_ring
+        .load_pkcs8(private_key.decode_msg().unwrap())
+        .unwrap()
+}
+
+/// Build a CSR and a self-signed certificate, returning their
+/// `(message, signature)` pairs after a DER round trip.
+fn build_and_parse(key_ring: &KeyRing, key_handle: KeyHandle) -> [(Vec<u8>, Vec<u8>); 2] {
+    let subject = Name::from_str("CN=signatory.example,O=iqlusion").unwrap();
+    let csr = x509::certificate_request(key_ring, key_handle, subject.clone()).unwrap();
+    let csr = CertReq::from_der(&csr.to_der().unwrap()).unwrap();
+    assert_eq!(csr.info.subject, subject);
+
+    let cert = x509::self_signed_certificate(
+        key_ring,
+        key_handle,
+        Profile::Root,
+        SerialNumber::from(42u32),
+        Validity::from_now(Duration::from_secs(3600)).unwrap(),
+        subject.clone(),
+    )
+    .unwrap();
+    let cert = Certificate::from_der(&cert.to_der().unwrap()).unwrap();
+    assert_eq!(cert.tbs_certificate.issuer, subject);
+    assert_eq!(cert.tbs_certificate.subject, subject);
+
+    [
+        (
+            csr.info.to_der().unwrap(),
+            csr.signature.raw_bytes().to_vec(),
+        ),
+        (
+            cert.tbs_certificate.to_der().unwrap(),
+            cert.signature.raw_bytes().to_vec(),
+        ),
+    ]
+}
+
+#[cfg(feature = "nistp256")]
+#[test]
+fn ecdsa_nistp256() {
+    use signatory::ecdsa::nistp256::SigningKey;
+
+    let mut key_ring = KeyRing::new();
+    let key_handle = generate_key::<SigningKey>(&mut key_ring);
+    let verifying_key = key_handle.ecdsa_nistp256().unwrap();
+
+    for (msg, sig) in build_and_parse(&key_ring, key_handle) {
+        let sig = p256::ecdsa::DerSignature::from_bytes(&sig).unwrap();
+        assert!(verifying_key.verify(&msg, &sig).is_ok());
+    }
+}
+
+#[cfg(feature = "nistp384")]
+#[test]
+fn ecdsa_nistp384() {
+    use signatory::ecdsa::nistp384::SigningKey;
+
+    let mut key_ring = KeyRing::new();
+    let key_handle = generate_key::<SigningKey>(&mut key_ring);
+    let verifying_key = key_handle.ecdsa_nistp384().unwrap();
+
+    for (msg, sig) in build_and_parse(&key_ring, key_handle) {
+        let sig = p384::ecdsa::DerSignature::from_bytes(&sig).unwrap();
+        assert!(verifying_key.verify(&msg, &sig).is_ok());
+    }
+}
+
+#[cfg(feature = "ed25519")]
+#[test]
+fn ed25519() {
+    use signatory::ed25519::{Signature, SigningKey};
+
+    let mut key_ring = KeyRing::new();
+    let key_handle = generate_key::<SigningKey>(&mut key_ring);
+    let verifying_key = key_handle.ed25519().unwrap();
+
+    for (msg, sig) in build_and_parse(&key_ring, key_handle) {
+        let sig = Signature::from_slice(&sig).unwrap();
+        assert!(verifying_key.verify(&msg, &sig).is_ok());
+    }
+}
+
+#[cfg(feature = "nistp256")]
+#[test]
+fn key_not_found() {
+    use signatory::ecdsa::nistp256::SigningKey;
+
+    let mut key_ring = KeyRing::new();
+    let key_handle = generate_key::<SigningKey>(&mut key_ring);
+    let subject = Name::from_str("CN=signatory.example").unwrap();
+
+    let result = x509::certificate_request(&KeyRing::new(), key_handle, subject);
+    assert!(matches!(result, Err(signatory::Error::KeyNotFound)));
+}